@@ -1,11 +1,12 @@
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 use tauri::State;
 
-use crate::download_file::DownloadState;
+use super::error::SwiftKitError;
+use super::jobs::manager::{JobKind, JobManager};
 
+// Cancels every running download, prefer `cancel_job` for a single one
 #[tauri::command(rename_all = "snake_case")]
-pub async fn abort_download(state: State<'_, Arc<Mutex<DownloadState>>>) -> Result<(), String> {
-    let mut download_state = state.lock().map_err(|e| e.to_string())?;
-    download_state.abort = true;
+pub async fn abort_download(jobs: State<'_, Arc<JobManager>>) -> Result<(), SwiftKitError> {
+    jobs.cancel_kind(JobKind::Download);
     Ok(())
 }
@@ -1,6 +1,8 @@
 use std::sync::{Arc, Mutex};
 use tauri::Window;
 
+use super::error::SwiftKitError;
+
 pub struct AppWindowState {
     pub is_pinned: bool,
 }
@@ -10,15 +12,17 @@ pub fn always_on_top(
     window: Window,
     only_state: bool,
     state: tauri::State<Arc<Mutex<AppWindowState>>>,
-) -> bool {
-    let mut state = state.lock().unwrap();
+) -> Result<bool, SwiftKitError> {
+    let mut state = state
+        .lock()
+        .map_err(|e| SwiftKitError::internal(e.to_string()))?;
 
     if only_state {
-        return state.is_pinned;
+        return Ok(state.is_pinned);
     }
 
     let new_state = !state.is_pinned;
-    window.set_always_on_top(new_state).unwrap();
+    window.set_always_on_top(new_state)?;
     state.is_pinned = new_state;
-    new_state
+    Ok(new_state)
 }
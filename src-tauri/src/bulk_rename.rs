@@ -1,46 +1,74 @@
+use serde::Serialize;
 use std::fs;
 use std::path::Path;
 
+use super::error::SwiftKitError;
+
+#[derive(Debug, Serialize)]
+pub struct RenamedFile {
+    pub from: String,
+    pub to: String,
+}
+
 #[tauri::command(rename_all = "snake_case")]
 pub fn bulk_rename(
     folder_path: String,
     search: String,
     replace: String,
     extension_filter: Option<String>,
-) -> Result<(), String> {
-    let path = Path::new(&folder_path);
+) -> Result<(), SwiftKitError> {
+    rename_matching(&folder_path, &search, &replace, extension_filter.as_deref())?;
+    Ok(())
+}
+
+pub fn rename_matching(
+    folder_path: &str,
+    search: &str,
+    replace: &str,
+    extension_filter: Option<&str>,
+) -> Result<Vec<RenamedFile>, SwiftKitError> {
+    let path = Path::new(folder_path);
     if !path.exists() || !path.is_dir() {
-        return Err("Invalid folder path".into());
+        return Err(SwiftKitError::validation("Invalid folder path"));
     }
 
-    for entry in fs::read_dir(path).map_err(|e| e.to_string())? {
-        let entry = entry.map_err(|e| e.to_string())?;
-        let metadata = entry.metadata().map_err(|e| e.to_string())?;
+    let mut renamed = Vec::new();
+    for entry in fs::read_dir(path).map_err(|e| SwiftKitError::io(e, path))? {
+        let entry = entry.map_err(|e| SwiftKitError::io(e, path))?;
+        let metadata = entry
+            .metadata()
+            .map_err(|e| SwiftKitError::io(e, entry.path()))?;
         if !metadata.is_file() {
             continue;
         }
 
-        let filename = entry
-            .file_name()
-            .into_string()
-            .map_err(|e| e.to_string_lossy().into_owned())?;
+        let filename = entry.file_name().into_string().map_err(|name| {
+            SwiftKitError::validation(format!(
+                "File name is not valid UTF-8: {}",
+                name.to_string_lossy()
+            ))
+        })?;
 
         if filename.starts_with('.') {
             continue;
         }
 
-        if let Some(ref ext_filter) = extension_filter {
+        if let Some(ext_filter) = extension_filter {
             if !filename.ends_with(ext_filter) {
                 continue;
             }
         }
 
-        if filename.contains(&search) {
-            let new_filename = filename.replace(&search, &replace);
+        if filename.contains(search) {
+            let new_filename = filename.replace(search, replace);
             let new_path = entry.path().with_file_name(new_filename);
-            fs::rename(entry.path(), new_path).map_err(|e| e.to_string())?;
+            fs::rename(entry.path(), &new_path).map_err(|e| SwiftKitError::io(e, &new_path))?;
+            renamed.push(RenamedFile {
+                from: entry.path().to_string_lossy().to_string(),
+                to: new_path.to_string_lossy().to_string(),
+            });
         }
     }
 
-    Ok(())
+    Ok(renamed)
 }
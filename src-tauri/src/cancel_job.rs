@@ -0,0 +1,20 @@
+use std::sync::Arc;
+use tauri::State;
+
+use super::error::SwiftKitError;
+use super::jobs::scheduler::JobScheduler;
+
+// Works for running jobs as well as queued ones that haven't started yet
+#[tauri::command(rename_all = "snake_case")]
+pub async fn cancel_job(
+    job_id: String,
+    scheduler: State<'_, Arc<JobScheduler>>,
+) -> Result<(), SwiftKitError> {
+    if !scheduler.cancel(&job_id).await? {
+        return Err(SwiftKitError::validation(format!(
+            "Job {} is not running",
+            job_id
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use sqlx::SqlitePool;
+use tauri::{AppHandle, Manager};
+use tokio::task;
+
+use super::error::SwiftKitError;
+use super::tools::probe::{probe_tool, ToolStatus};
+use super::tools::registry::Tool;
+use super::tools::resolver::configured_path;
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn check_tools(handle: AppHandle) -> Result<Vec<ToolStatus>, SwiftKitError> {
+    let pool = handle.try_state::<SqlitePool>();
+    probe_all(pool.as_deref()).await
+}
+
+pub async fn probe_all(pool: Option<&SqlitePool>) -> Result<Vec<ToolStatus>, SwiftKitError> {
+    let mut overrides = Vec::with_capacity(Tool::ALL.len());
+    for tool in Tool::ALL {
+        let override_path = match pool {
+            Some(pool) => configured_path(pool, tool).await,
+            None => None,
+        };
+        overrides.push((tool, override_path));
+    }
+
+    let statuses = task::spawn_blocking(move || {
+        overrides
+            .into_iter()
+            .map(|(tool, override_path)| probe_tool(tool, override_path.as_deref()))
+            .collect()
+    })
+    .await?;
+
+    Ok(statuses)
+}
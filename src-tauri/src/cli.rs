@@ -0,0 +1,595 @@
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::SqlitePool;
+use std::fs;
+use std::future::Future;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use crate::bulk_rename::rename_matching;
+use crate::check_tools::probe_all;
+use crate::db::{connect_existing, default_database_path};
+use crate::error::SwiftKitError;
+use crate::get_video_details::probe_video_details;
+use crate::image_compress::{compress, CompressOptions};
+use crate::image_convert::image_convert;
+use crate::image_resize::image_resize;
+use crate::img_compressors::backend::CompressionBackend;
+use crate::img_encoders::options::{EncodeOptions, PngCompression};
+use crate::img_metadata::mode::MetadataMode;
+use crate::img_transforms::gravity::Gravity;
+use crate::img_transforms::resize::{ResampleFilter, ResizeMode, ResizeOptions};
+use crate::scrape_anime;
+use crate::scrape_game;
+use crate::scrape_movie;
+use crate::settings::model::Settings;
+use crate::settings::service::load;
+use crate::tools::registry::Tool;
+use crate::tools::resolver::resolve_tool_with;
+use crate::yt_download::{run_yt_dlp, YtDlpOptions};
+
+const IMAGE_EXTENSIONS: [&str; 10] = [
+    "png", "jpg", "jpeg", "webp", "bmp", "tif", "tiff", "gif", "tga", "qoi",
+];
+const COMPRESSIBLE_EXTENSIONS: [&str; 4] = ["png", "jpg", "jpeg", "webp"];
+const VIDEO_EXTENSIONS: [&str; 2] = ["mp4", "mkv"];
+
+/// Run Swift Kit tools from a terminal. Results are printed as JSON on stdout.
+#[derive(Parser)]
+#[command(name = "swift-kit-cli", version)]
+struct Cli {
+    /// Settings database, defaults to the one used by the desktop app
+    #[arg(long, global = true)]
+    db: Option<PathBuf>,
+
+    /// Pretty-print the JSON output
+    #[arg(long, global = true)]
+    pretty: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Compress PNG, JPEG and WebP images, folders are compressed file by file
+    Compress {
+        #[arg(required = true)]
+        paths: Vec<String>,
+        #[arg(short, long)]
+        quality: Option<u8>,
+        #[arg(short, long)]
+        output: Option<String>,
+        /// Defaults to the compression backend from the settings
+        #[arg(long, value_enum)]
+        backend: Option<Backend>,
+        /// Lower the quality until the file fits, --quality becomes the upper bound
+        #[arg(long)]
+        target_size_kb: Option<u64>,
+        /// Keep the compressed file even when it is larger than the original
+        #[arg(long)]
+        force: bool,
+        /// Raise the quality until the SSIM against the original reaches this (0-1)
+        #[arg(long)]
+        min_ssim: Option<f64>,
+    },
+    /// Convert images to another format, AVIF can be written but not read
+    Convert {
+        #[arg(required = true)]
+        paths: Vec<String>,
+        #[arg(short, long)]
+        to: String,
+        #[arg(short, long)]
+        output: Option<String>,
+        #[command(flatten)]
+        encoder: EncoderArgs,
+        /// What of the EXIF and XMP metadata to keep
+        #[arg(long, value_enum, default_value_t = Metadata::Preserve)]
+        metadata: Metadata,
+    },
+    /// Resize images, the aspect ratio is kept when only one side is given
+    Resize {
+        #[arg(required = true)]
+        paths: Vec<String>,
+        #[arg(short, long)]
+        output: Option<String>,
+        #[command(flatten)]
+        resize: ResizeArgs,
+        /// What of the EXIF and XMP metadata to keep
+        #[arg(long, value_enum, default_value_t = Metadata::Preserve)]
+        metadata: Metadata,
+    },
+    /// Rename the files in a folder by replacing part of their name
+    Rename {
+        folder: String,
+        #[arg(short, long)]
+        search: String,
+        #[arg(short, long)]
+        replace: String,
+        /// Only rename files ending with this extension
+        #[arg(short, long)]
+        ext: Option<String>,
+    },
+    /// Print duration, tracks and size of MP4 and MKV videos
+    VideoDetails {
+        #[arg(required = true)]
+        paths: Vec<String>,
+    },
+    /// Scrape IMDb, MyAnimeList or Steam pages
+    Scrape {
+        kind: ScrapeKind,
+        #[arg(required = true)]
+        urls: Vec<String>,
+    },
+    /// Download a video with yt-dlp, its output is forwarded to stderr
+    YtDownload {
+        url: String,
+        /// Defaults to the download folder from the settings
+        #[arg(short, long)]
+        output: Option<String>,
+        /// Download rate limit in KB/s
+        #[arg(long)]
+        rate: Option<u32>,
+    },
+    /// Report which external tools were found and their versions
+    Tools,
+}
+
+#[derive(Args)]
+struct EncoderArgs {
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=100))]
+    jpeg_quality: Option<u8>,
+    #[arg(long)]
+    progressive: bool,
+    /// Lossy WebP quality, WebP is lossless without it
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=100))]
+    webp_quality: Option<u8>,
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=100))]
+    avif_quality: Option<u8>,
+    /// 1 is the slowest and smallest, 10 the fastest
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=10))]
+    avif_speed: Option<u8>,
+    #[arg(long, value_enum, default_value_t = PngLevel::Default)]
+    png_compression: PngLevel,
+    /// Lossy PNG palette quantization, PNG is lossless without it
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=100))]
+    png_quality: Option<u8>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum PngLevel {
+    Fast,
+    Default,
+    Best,
+}
+
+#[derive(Args)]
+struct ResizeArgs {
+    #[arg(long, value_enum, default_value_t = Mode::Exact)]
+    mode: Mode,
+    #[arg(short = 'W', long)]
+    width: Option<u32>,
+    #[arg(short = 'H', long)]
+    height: Option<u32>,
+    /// Scale for --mode percentage
+    #[arg(long)]
+    percent: Option<f32>,
+    /// Longest side for --mode longest-edge
+    #[arg(long)]
+    longest_edge: Option<u32>,
+    /// Which part is kept by --mode cover, or where --mode pad places the image
+    #[arg(long, value_enum, default_value_t = Anchor::Center)]
+    gravity: Anchor,
+    /// Fill colour for --mode pad, e.g. #ffffff
+    #[arg(long)]
+    background: Option<String>,
+    #[arg(long)]
+    no_upscale: bool,
+    #[arg(long, value_enum, default_value_t = Filter::Lanczos3)]
+    filter: Filter,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Mode {
+    Exact,
+    Contain,
+    Cover,
+    Pad,
+    Percentage,
+    LongestEdge,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Anchor {
+    Center,
+    North,
+    South,
+    East,
+    West,
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Filter {
+    Nearest,
+    Bilinear,
+    Bicubic,
+    Gaussian,
+    Lanczos3,
+}
+
+impl From<ResizeArgs> for ResizeOptions {
+    fn from(args: ResizeArgs) -> Self {
+        ResizeOptions {
+            mode: match args.mode {
+                Mode::Exact => ResizeMode::Exact,
+                Mode::Contain => ResizeMode::Contain,
+                Mode::Cover => ResizeMode::Cover,
+                Mode::Pad => ResizeMode::Pad,
+                Mode::Percentage => ResizeMode::Percentage,
+                Mode::LongestEdge => ResizeMode::LongestEdge,
+            },
+            width: args.width,
+            height: args.height,
+            percent: args.percent,
+            longest_edge: args.longest_edge,
+            gravity: match args.gravity {
+                Anchor::Center => Gravity::Center,
+                Anchor::North => Gravity::North,
+                Anchor::South => Gravity::South,
+                Anchor::East => Gravity::East,
+                Anchor::West => Gravity::West,
+                Anchor::NorthEast => Gravity::NorthEast,
+                Anchor::NorthWest => Gravity::NorthWest,
+                Anchor::SouthEast => Gravity::SouthEast,
+                Anchor::SouthWest => Gravity::SouthWest,
+            },
+            background: args.background,
+            no_upscale: args.no_upscale,
+            filter: match args.filter {
+                Filter::Nearest => ResampleFilter::Nearest,
+                Filter::Bilinear => ResampleFilter::Bilinear,
+                Filter::Bicubic => ResampleFilter::Bicubic,
+                Filter::Gaussian => ResampleFilter::Gaussian,
+                Filter::Lanczos3 => ResampleFilter::Lanczos3,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Backend {
+    Auto,
+    External,
+    Builtin,
+}
+
+impl From<Backend> for CompressionBackend {
+    fn from(backend: Backend) -> Self {
+        match backend {
+            Backend::Auto => CompressionBackend::Auto,
+            Backend::External => CompressionBackend::External,
+            Backend::Builtin => CompressionBackend::Builtin,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Metadata {
+    Preserve,
+    Strip,
+    StripGps,
+}
+
+impl From<Metadata> for MetadataMode {
+    fn from(metadata: Metadata) -> Self {
+        match metadata {
+            Metadata::Preserve => MetadataMode::Preserve,
+            Metadata::Strip => MetadataMode::Strip,
+            Metadata::StripGps => MetadataMode::StripGps,
+        }
+    }
+}
+
+impl From<EncoderArgs> for EncodeOptions {
+    fn from(args: EncoderArgs) -> Self {
+        EncodeOptions {
+            jpeg_quality: args.jpeg_quality,
+            jpeg_progressive: args.progressive,
+            webp_quality: args.webp_quality,
+            avif_quality: args.avif_quality,
+            avif_speed: args.avif_speed,
+            png_compression: match args.png_compression {
+                PngLevel::Fast => PngCompression::Fast,
+                PngLevel::Default => PngCompression::Default,
+                PngLevel::Best => PngCompression::Best,
+            },
+            png_quality: args.png_quality,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ScrapeKind {
+    Movie,
+    Anime,
+    Game,
+}
+
+// One entry of a batch, a failed input doesn't stop the others
+#[derive(Serialize)]
+struct BatchItem {
+    input: String,
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<SwiftKitError>,
+}
+
+struct Outcome {
+    value: Value,
+    failed: bool,
+}
+
+pub fn run() -> ExitCode {
+    let cli = Cli::parse();
+    let pretty = cli.pretty;
+
+    let result = tokio::runtime::Runtime::new()
+        .map_err(SwiftKitError::from)
+        .and_then(|runtime| runtime.block_on(execute(cli)));
+
+    match result {
+        Ok(outcome) => {
+            print_json(&mut std::io::stdout(), &outcome.value, pretty);
+            if outcome.failed {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        Err(e) => {
+            print_json(&mut std::io::stderr(), &e, pretty);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn execute(cli: Cli) -> Result<Outcome, SwiftKitError> {
+    let pool = open_settings(cli.db.as_deref()).await?;
+    let pool = pool.as_ref();
+    let settings = match pool {
+        Some(pool) => load(pool).await?,
+        None => Settings::default(),
+    };
+    let settings = &settings;
+
+    match cli.command {
+        Command::Compress {
+            paths,
+            quality,
+            output,
+            backend,
+            target_size_kb,
+            force,
+            min_ssim,
+        } => {
+            let options = CompressOptions {
+                quality: quality.or(settings.image_quality),
+                backend: backend.map_or(settings.compression_backend, CompressionBackend::from),
+                target_size_kb,
+                force,
+                min_ssim,
+            };
+            let options = &options;
+            let output = output.or_else(|| settings.image_output_folder.clone());
+            let output = output.as_deref();
+            let inputs = expand_paths(paths, &COMPRESSIBLE_EXTENSIONS)?;
+            batch(inputs, |input| async move {
+                compress(pool, &input, output, options).await
+            })
+            .await
+        }
+        Command::Convert {
+            paths,
+            to,
+            output,
+            encoder,
+            metadata,
+        } => {
+            let options = EncodeOptions::from(encoder);
+            let inputs = expand_paths(paths, &IMAGE_EXTENSIONS)?;
+            batch(inputs, |input| {
+                image_convert(
+                    input,
+                    to.clone(),
+                    output.clone(),
+                    Some(options.clone()),
+                    Some(metadata.into()),
+                )
+            })
+            .await
+        }
+        Command::Resize {
+            paths,
+            output,
+            resize,
+            metadata,
+        } => {
+            let options = ResizeOptions::from(resize);
+            let inputs = expand_paths(paths, &IMAGE_EXTENSIONS)?;
+            batch(inputs, |input| {
+                image_resize(
+                    input,
+                    options.clone(),
+                    output.clone(),
+                    None,
+                    Some(metadata.into()),
+                )
+            })
+            .await
+        }
+        Command::Rename {
+            folder,
+            search,
+            replace,
+            ext,
+        } => {
+            let renamed = rename_matching(&folder, &search, &replace, ext.as_deref())?;
+            single(renamed)
+        }
+        Command::VideoDetails { paths } => {
+            let ffprobe = resolve_tool_with(pool, Tool::Ffprobe).await?;
+            let ffprobe = ffprobe.as_path();
+            let inputs = expand_paths(paths, &VIDEO_EXTENSIONS)?;
+            batch(inputs, |input| async move {
+                probe_video_details(ffprobe, &input)
+            })
+            .await
+        }
+        Command::Scrape { kind, urls } => {
+            batch(urls, |url| async move {
+                let settings = settings.clone();
+                match kind {
+                    ScrapeKind::Movie => {
+                        serde_json::to_value(scrape_movie::scrape_with(settings, url).await?)
+                    }
+                    ScrapeKind::Anime => {
+                        serde_json::to_value(scrape_anime::scrape_with(settings, url).await?)
+                    }
+                    ScrapeKind::Game => {
+                        serde_json::to_value(scrape_game::scrape_with(settings, url).await?)
+                    }
+                }
+                .map_err(SwiftKitError::from)
+            })
+            .await
+        }
+        Command::YtDownload { url, output, rate } => {
+            let yt_dlp = resolve_tool_with(pool, Tool::YtDlp).await?;
+            let ffmpeg = resolve_tool_with(pool, Tool::Ffmpeg).await?;
+            let output = output
+                .or_else(|| settings.download_folder.clone())
+                .ok_or_else(|| {
+                    SwiftKitError::validation("Pass --output or set a download folder.")
+                })?;
+            let options = YtDlpOptions {
+                url: &url,
+                output_path: &output,
+                download_rate: rate.or(settings.yt_download_rate_kb),
+                proxy: settings.proxy.as_deref(),
+            };
+            let on_output = |output: String| {
+                let mut stderr = std::io::stderr();
+                let _ = stderr.write_all(output.as_bytes());
+                let _ = stderr.flush();
+            };
+            let response = run_yt_dlp(&yt_dlp, &ffmpeg, options, interrupted(), on_output).await?;
+            single(response)
+        }
+        Command::Tools => single(probe_all(pool).await?),
+    }
+}
+
+// The desktop app's database is optional, tools then resolve from sidecars and PATH only
+async fn open_settings(path: Option<&Path>) -> Result<Option<SqlitePool>, SwiftKitError> {
+    if let Some(path) = path {
+        return connect_existing(path).await.map(Some);
+    }
+
+    match default_database_path().filter(|path| path.is_file()) {
+        Some(path) => connect_existing(&path).await.map(Some),
+        None => Ok(None),
+    }
+}
+
+// Folders are replaced by the files directly inside them with one of the extensions
+fn expand_paths(paths: Vec<String>, extensions: &[&str]) -> Result<Vec<String>, SwiftKitError> {
+    let mut inputs = Vec::new();
+    for path in paths {
+        let dir = Path::new(&path);
+        if !dir.is_dir() {
+            inputs.push(path);
+            continue;
+        }
+
+        let mut files = Vec::new();
+        for entry in fs::read_dir(dir).map_err(|e| SwiftKitError::io(e, dir))? {
+            let file = entry.map_err(|e| SwiftKitError::io(e, dir))?.path();
+            let matches = file
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| extensions.contains(&ext.to_lowercase().as_str()));
+            if matches && file.is_file() {
+                files.push(file.to_string_lossy().to_string());
+            }
+        }
+        files.sort();
+        inputs.extend(files);
+    }
+
+    Ok(inputs)
+}
+
+async fn batch<F, Fut, T>(inputs: Vec<String>, run: F) -> Result<Outcome, SwiftKitError>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<T, SwiftKitError>>,
+    T: Serialize,
+{
+    let mut items = Vec::with_capacity(inputs.len());
+    for input in inputs {
+        let item = match run(input.clone()).await {
+            Ok(result) => BatchItem {
+                input,
+                ok: true,
+                result: Some(serde_json::to_value(result)?),
+                error: None,
+            },
+            Err(e) => BatchItem {
+                input,
+                ok: false,
+                result: None,
+                error: Some(e),
+            },
+        };
+        items.push(item);
+    }
+
+    Ok(Outcome {
+        failed: items.iter().any(|item| !item.ok),
+        value: serde_json::to_value(items)?,
+    })
+}
+
+fn single<T: Serialize>(result: T) -> Result<Outcome, SwiftKitError> {
+    Ok(Outcome {
+        value: serde_json::to_value(result)?,
+        failed: false,
+    })
+}
+
+// Resolves on Ctrl+C so long running tools get killed instead of orphaned
+async fn interrupted() {
+    if tokio::signal::ctrl_c().await.is_err() {
+        std::future::pending::<()>().await;
+    }
+}
+
+fn print_json<T: Serialize>(out: &mut impl Write, value: &T, pretty: bool) {
+    let json = if pretty {
+        serde_json::to_string_pretty(value)
+    } else {
+        serde_json::to_string(value)
+    };
+
+    if let Ok(json) = json {
+        let _ = writeln!(out, "{}", json);
+    }
+}
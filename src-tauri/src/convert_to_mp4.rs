@@ -1,29 +1,43 @@
-use std::future::poll_fn;
-use std::path::Path;
-use std::task::Context;
-use tauri::{Emitter, Listener};
-use tauri_plugin_shell::process::{CommandEvent, TerminatedPayload};
-use tauri_plugin_shell::ShellExt;
-use tokio::sync::oneshot;
+use std::sync::Arc;
+use std::{fs, path::Path};
+use tauri::{AppHandle, State};
+
+use super::error::SwiftKitError;
+use super::ffmpeg::runner::{probe_duration, FfmpegRunner};
+use super::jobs::manager::{JobHandle, JobKind, JobManager};
+use super::tools::registry::Tool;
+use super::tools::resolver::resolve_tool;
 
 #[tauri::command(rename_all = "snake_case")]
-pub async fn convert_to_mp4(handle: tauri::AppHandle, video_path: String) -> Result<(), String> {
-    let video_path = Path::new(&video_path);
+pub async fn convert_to_mp4(
+    handle: AppHandle,
+    video_path: String,
+    job_id: Option<String>,
+    jobs: State<'_, Arc<JobManager>>,
+) -> Result<(), SwiftKitError> {
+    let job = jobs.start(&handle, job_id, JobKind::ConvertToMp4, video_path.clone())?;
+    let result = convert(&handle, &job, &video_path).await;
+    job.finish(&result);
+    result
+}
+
+pub async fn convert(
+    handle: &AppHandle,
+    job: &JobHandle,
+    video_path: &str,
+) -> Result<(), SwiftKitError> {
+    let video_path = Path::new(video_path);
     if !video_path.exists() || !video_path.is_file() {
-        return Err("Invalid directory path".into());
+        return Err(SwiftKitError::validation("Invalid directory path"));
     }
 
     if let Some(video_extension) = video_path.extension() {
         if video_extension != "mkv" {
-            return Err("Unsupported video type".into());
+            return Err(SwiftKitError::validation("Unsupported video type"));
         }
     }
 
-    let stem = video_path.file_stem().unwrap().to_str().unwrap();
-    let output_path = video_path
-        .parent()
-        .unwrap()
-        .join(format!("{}.{}", stem, "mp4"));
+    let output_path = video_path.with_extension("mp4");
     let input_escaped_path = video_path
         .to_string_lossy()
         .to_string()
@@ -44,58 +58,18 @@ pub async fn convert_to_mp4(handle: tauri::AppHandle, video_path: String) -> Res
 		output_path.to_string_lossy().to_string()
     ];
 
-    let (mut rx, child) = handle
-        .shell()
-        .sidecar("ffmpeg")
-        .map_err(|e| format!("Failed to create ffmpeg sidecar: {}", e))?
-        .args(args.clone())
-        .spawn()
-        .map_err(|e| format!("Failed to execute sidecar: {}", e))?;
+    let ffprobe = resolve_tool(handle, Tool::Ffprobe).await?;
+    let duration = probe_duration(&ffprobe, video_path).await;
 
-    let (cancel_tx, mut cancel_rx) = oneshot::channel();
-    let handle_clone = handle.clone();
-    tokio::spawn(async move {
-        handle_clone.once("cancel_convert_to_mp4", move |_| {
-            let _ = cancel_tx.send(());
-        });
-    });
-
-    loop {
-        tokio::select! {
-            event = rx.recv() => {
-                match event {
-                    Some(CommandEvent::Stdout(line) | CommandEvent::Stderr(line)) => {
-                        if let Ok(text) = String::from_utf8(line) {
-                            if text.to_lowercase().contains("error")
-                                || text.to_lowercase().contains("failed")
-                            {
-                                handle.emit("convert_to_mp4_stderr", text).unwrap();
-                            } else {
-                                handle.emit("convert_to_mp4_stdout", text).unwrap();
-                            }
-                        }
-                    }
-                    Some(CommandEvent::Terminated(TerminatedPayload { code, signal })) => {
-                        if code.unwrap_or(-1) != 0 {
-                            return Err(format!(
-                                "FFmpeg process failed with exit code: {:?}, signal: {:?}",
-                                code, signal
-                            ));
-                        }
-                        break;
-                    }
-                    None => break,
-                    _ => {}
-                }
-            }
-            _ = poll_fn(|cx: &mut Context<'_>| Pin::new(&mut cancel_rx).poll(cx)) => {
-                if let Err(e) = child.kill() {
-                    eprintln!("Failed to kill ffmpeg process: {}", e);
-                }
-                return Ok(());
-            }
-        }
+    let result = FfmpegRunner::new(handle, job)
+        .event("convert_to_mp4_progress")
+        .duration(duration)
+        .run(args)
+        .await;
+    if result.as_ref().is_err_and(|e| e.is_cancelled()) {
+        // Don't leave a half written file next to the source
+        let _ = fs::remove_file(&output_path);
     }
 
-    Ok(())
+    result
 }
@@ -0,0 +1,73 @@
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions};
+use std::path::{Path, PathBuf};
+
+use crate::error::SwiftKitError;
+
+// Same database the frontend opens through tauri-plugin-sql, it lives in the AppConfig directory
+pub const DATABASE_URL: &str = "sqlite:swiftkit.db";
+pub const DATABASE_FILE: &str = "swiftkit.db";
+// Must match `identifier` in tauri.conf.json, the AppConfig directory is named after it
+pub const APP_IDENTIFIER: &str = "com.swift-kit.app";
+
+pub fn connect_lazy(app_config_dir: &Path) -> SqlitePool {
+    let options = SqliteConnectOptions::new()
+        .filename(app_config_dir.join(DATABASE_FILE))
+        .create_if_missing(true)
+        .journal_mode(SqliteJournalMode::Wal);
+
+    SqlitePoolOptions::new()
+        .max_connections(4)
+        .connect_lazy_with(options)
+}
+
+// Where the desktop app keeps its database, for code running without an AppHandle
+pub fn default_database_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(APP_IDENTIFIER).join(DATABASE_FILE))
+}
+
+// Opens a database the app already created, migrations are left to the app
+pub async fn connect_existing(path: &Path) -> Result<SqlitePool, SwiftKitError> {
+    if !path.is_file() {
+        return Err(SwiftKitError::validation(format!(
+            "Database not found: {}",
+            path.display()
+        )));
+    }
+
+    let options = SqliteConnectOptions::new()
+        .filename(path)
+        .journal_mode(SqliteJournalMode::Wal);
+    Ok(SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await?)
+}
+
+pub async fn get_setting(pool: &SqlitePool, name: &str) -> Option<String> {
+    sqlx::query_scalar::<_, Option<String>>("SELECT value FROM settings WHERE name = ?")
+        .bind(name)
+        .fetch_optional(pool)
+        .await
+        .ok()
+        .flatten()
+        .flatten()
+        .filter(|value| !value.trim().is_empty())
+}
+
+pub async fn set_setting(
+    pool: &SqlitePool,
+    name: &str,
+    description: &str,
+    value: &str,
+) -> Result<(), SwiftKitError> {
+    sqlx::query(
+        "INSERT INTO settings (name, description, value) VALUES (?, ?, ?)
+        ON CONFLICT(name) DO UPDATE SET value = excluded.value",
+    )
+    .bind(name)
+    .bind(description)
+    .bind(value)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
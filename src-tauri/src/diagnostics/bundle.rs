@@ -0,0 +1,206 @@
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::SqlitePool;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use super::logging::{logs_dir, CRASH_FILE_PREFIX};
+use super::redact::{redact, REDACTED};
+use crate::check_tools::probe_all;
+use crate::error::SwiftKitError;
+
+const MAX_CRASH_REPORTS: usize = 5;
+
+#[derive(Serialize)]
+struct SystemInfo {
+    app_version: &'static str,
+    os: &'static str,
+    os_family: &'static str,
+    arch: &'static str,
+    generated_at: u64,
+}
+
+#[derive(Serialize)]
+struct DatabaseInfo {
+    schema_version: Option<i64>,
+    migrations: Vec<AppliedMigration>,
+    tables: Vec<TableCount>,
+    // Only the names are exported, values may hold paths or API keys
+    settings: Vec<RedactedSetting>,
+}
+
+#[derive(Serialize, sqlx::FromRow)]
+struct AppliedMigration {
+    version: i64,
+    description: String,
+    success: bool,
+}
+
+#[derive(Serialize)]
+struct TableCount {
+    name: String,
+    rows: i64,
+}
+
+#[derive(Serialize)]
+struct RedactedSetting {
+    name: String,
+    value: Option<&'static str>,
+}
+
+// Everything that goes in the archive, collected before the blocking zip write
+struct Bundle {
+    system: Value,
+    tools: String,
+    database: Value,
+    logs: Vec<PathBuf>,
+}
+
+pub async fn export(
+    app_config_dir: &Path,
+    pool: Option<&SqlitePool>,
+    output: PathBuf,
+) -> Result<PathBuf, SwiftKitError> {
+    let system = SystemInfo {
+        app_version: env!("CARGO_PKG_VERSION"),
+        os: std::env::consts::OS,
+        os_family: std::env::consts::FAMILY,
+        arch: std::env::consts::ARCH,
+        generated_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default(),
+    };
+
+    // Tool paths and version banners can include the user's home folder
+    let tools = redact(&serde_json::to_string_pretty(&probe_all(pool).await?)?);
+
+    let database = match pool {
+        Some(pool) => serde_json::to_value(database_info(pool).await?)?,
+        None => Value::Null,
+    };
+
+    let bundle = Bundle {
+        system: serde_json::to_value(system)?,
+        tools,
+        database,
+        logs: recent_logs(&logs_dir(app_config_dir)),
+    };
+
+    tokio::task::spawn_blocking(move || write_zip(&output, bundle).map(|_| output)).await?
+}
+
+async fn database_info(pool: &SqlitePool) -> Result<DatabaseInfo, SwiftKitError> {
+    // tauri-plugin-sql records its migrations through sqlx
+    let migrations: Vec<AppliedMigration> = sqlx::query_as(
+        "SELECT version, description, success FROM _sqlx_migrations ORDER BY version",
+    )
+    .fetch_all(pool)
+    .await
+    .unwrap_or_default();
+    let schema_version = migrations
+        .iter()
+        .filter(|migration| migration.success)
+        .map(|migration| migration.version)
+        .max();
+
+    let table_names: Vec<String> = sqlx::query_scalar(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+    )
+    .fetch_all(pool)
+    .await?;
+    let mut tables = Vec::with_capacity(table_names.len());
+    for name in table_names {
+        let query = format!("SELECT COUNT(*) FROM \"{}\"", name.replace('"', "\"\""));
+        let rows = sqlx::query_scalar(&query).fetch_one(pool).await?;
+        tables.push(TableCount { name, rows });
+    }
+
+    let settings = sqlx::query_as::<_, (String, Option<String>)>(
+        "SELECT name, value FROM settings ORDER BY name",
+    )
+    .fetch_all(pool)
+    .await
+    .unwrap_or_default()
+    .into_iter()
+    .map(|(name, value)| RedactedSetting {
+        name,
+        value: value.filter(|value| !value.is_empty()).map(|_| REDACTED),
+    })
+    .collect();
+
+    Ok(DatabaseInfo {
+        schema_version,
+        migrations,
+        tables,
+        settings,
+    })
+}
+
+// All rotated log files plus the latest crash reports, oldest first
+fn recent_logs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .collect();
+    files.sort();
+
+    let is_crash = |path: &PathBuf| {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with(CRASH_FILE_PREFIX))
+    };
+    let (crashes, mut logs): (Vec<_>, Vec<_>) = files.into_iter().partition(is_crash);
+    let skip = crashes.len().saturating_sub(MAX_CRASH_REPORTS);
+    logs.extend(crashes.into_iter().skip(skip));
+    logs
+}
+
+fn write_zip(output: &Path, bundle: Bundle) -> Result<(), SwiftKitError> {
+    let file = File::create(output).map_err(|e| SwiftKitError::io(e, output))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default();
+
+    let mut add = |name: &str, contents: &[u8]| -> Result<(), SwiftKitError> {
+        zip.start_file(name, options).map_err(zip_error)?;
+        zip.write_all(contents)
+            .map_err(|e| SwiftKitError::io(e, output))
+    };
+
+    add(
+        "system.json",
+        serde_json::to_string_pretty(&bundle.system)?.as_bytes(),
+    )?;
+    add("tools.json", bundle.tools.as_bytes())?;
+    add(
+        "database.json",
+        serde_json::to_string_pretty(&bundle.database)?.as_bytes(),
+    )?;
+
+    for log in &bundle.logs {
+        let Some(name) = log.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        // Lossy so a log cut mid-character doesn't drop the whole file
+        let contents = match fs::read(log) {
+            Ok(bytes) => redact(&String::from_utf8_lossy(&bytes)),
+            Err(e) => format!("Failed to read {}: {}", name, e),
+        };
+        add(&format!("logs/{}", name), contents.as_bytes())?;
+    }
+
+    zip.finish().map_err(zip_error)?;
+    Ok(())
+}
+
+fn zip_error(e: zip::result::ZipError) -> SwiftKitError {
+    SwiftKitError::internal(format!("Failed to write diagnostics archive: {}", e))
+}
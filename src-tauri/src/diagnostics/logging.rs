@@ -0,0 +1,82 @@
+use std::backtrace::Backtrace;
+use std::fs;
+use std::panic::{self, PanicHookInfo};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, EnvFilter, Layer};
+
+pub const LOG_FILE_PREFIX: &str = "swift-kit";
+pub const CRASH_FILE_PREFIX: &str = "crash-";
+const MAX_LOG_FILES: usize = 7;
+const DEFAULT_FILTER: &str = "warn,tauri_app_lib=info";
+
+pub fn logs_dir(app_config_dir: &Path) -> PathBuf {
+    app_config_dir.join("logs")
+}
+
+// Daily log files in <AppConfig>/logs, RUST_LOG overrides the default filter
+pub fn init(app_config_dir: &Path) {
+    let dir = logs_dir(app_config_dir);
+    if let Err(e) = fs::create_dir_all(&dir) {
+        eprintln!("Failed to create log directory {}: {}", dir.display(), e);
+    }
+
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .filename_suffix("log")
+        .max_log_files(MAX_LOG_FILES)
+        .build(&dir);
+
+    let file_layer = appender
+        .ok()
+        .map(|appender| fmt::layer().with_ansi(false).with_writer(appender).boxed());
+    // The terminal is only there while developing
+    let stderr_layer = cfg!(debug_assertions).then(|| fmt::layer().with_writer(std::io::stderr));
+
+    let filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+    let _ = tracing_subscriber::registry()
+        .with(filter)
+        .with(file_layer)
+        .with(stderr_layer)
+        .try_init();
+
+    install_panic_hook(dir);
+}
+
+// Writes a crash report next to the logs before handing over to the default hook
+fn install_panic_hook(dir: PathBuf) {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let report = crash_report(info);
+        tracing::error!("{}", info);
+
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis())
+            .unwrap_or_default();
+        let path = dir.join(format!("{}{}.log", CRASH_FILE_PREFIX, millis));
+        if let Err(e) = fs::write(&path, report) {
+            eprintln!("Failed to write crash report {}: {}", path.display(), e);
+        }
+
+        previous(info);
+    }));
+}
+
+fn crash_report(info: &PanicHookInfo) -> String {
+    let thread = std::thread::current();
+    format!(
+        "Swift Kit {}\nOS: {} ({})\nThread: {}\n\n{}\n\nBacktrace:\n{}\n",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        thread.name().unwrap_or("<unnamed>"),
+        info,
+        Backtrace::force_capture()
+    )
+}
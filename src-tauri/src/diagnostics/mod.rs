@@ -0,0 +1,3 @@
+pub mod bundle;
+pub mod logging;
+pub mod redact;
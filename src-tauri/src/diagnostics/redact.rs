@@ -0,0 +1,21 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+pub const REDACTED: &str = "[redacted]";
+
+static SECRET_PAIR: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?i)\b(password|passwd|token|access_token|api[_-]?key|secret|authorization|cookie|session)(\s*[=:]\s*)("[^"]*"|[^\s&,;]+)"#,
+    )
+    .expect("secret pattern is valid")
+});
+
+// Masks `key=value` style secrets and the user's home folder, which usually holds their name
+pub fn redact(text: &str) -> String {
+    let redacted = SECRET_PAIR.replace_all(text, format!("${{1}}${{2}}{}", REDACTED));
+
+    match dirs::home_dir().map(|home| home.to_string_lossy().to_string()) {
+        Some(home) if home.len() > 1 => redacted.replace(&home, "~"),
+        _ => redacted.into_owned(),
+    }
+}
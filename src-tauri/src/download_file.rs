@@ -1,17 +1,16 @@
-use reqwest::Client;
 use serde::Serialize;
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tauri::path::BaseDirectory;
 use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::fs::{self, File};
 use tokio::io::AsyncWriteExt;
 
-#[derive(Default)]
-pub struct DownloadState {
-    pub abort: bool,
-}
+use super::error::SwiftKitError;
+use super::jobs::manager::{JobHandle, JobKind, JobManager};
+use super::settings::service::current;
+use super::utils::request_client::request_client;
 
 #[derive(Serialize, Clone)]
 struct DownloadProgress {
@@ -32,17 +31,19 @@ fn is_within_app_config(destination: &Path, app_config_path: &Path) -> bool {
     destination.starts_with(app_config_path)
 }
 
-async fn create_dirs_limited(path: &Path, max_depth: usize) -> Result<(), String> {
+async fn create_dirs_limited(path: &Path, max_depth: usize) -> Result<(), SwiftKitError> {
     let mut current = PathBuf::new();
     let mut depth = 0;
 
     for component in path.components() {
         current.push(component);
         if !current.exists() {
-            fs::create_dir(&current).await.map_err(|e| e.to_string())?;
+            fs::create_dir(&current)
+                .await
+                .map_err(|e| SwiftKitError::io(e, &current))?;
             depth += 1;
             if depth > max_depth {
-                return Err("Exceeded max directory depth".to_string());
+                return Err(SwiftKitError::validation("Exceeded max directory depth"));
             }
         }
     }
@@ -54,17 +55,33 @@ pub async fn download_file(
     url: String,
     destination: String,
     rate_limit_kb: Option<u64>,
+    job_id: Option<String>,
     handle: AppHandle,
-    state: State<'_, Arc<Mutex<DownloadState>>>,
-) -> Result<String, String> {
+    jobs: State<'_, Arc<JobManager>>,
+) -> Result<String, SwiftKitError> {
+    let job = jobs.start(&handle, job_id, JobKind::Download, url.clone())?;
+    let result = download(&handle, &job, url, destination, rate_limit_kb).await;
+    job.finish(&result);
+    result
+}
+
+pub async fn download(
+    handle: &AppHandle,
+    job: &JobHandle,
+    url: String,
+    destination: String,
+    rate_limit_kb: Option<u64>,
+) -> Result<String, SwiftKitError> {
     let app_config_path = handle
         .path()
-        .resolve("".to_string(), BaseDirectory::AppConfig)
-        .map_err(|e| format!("Failed to resolve App Config directory: {}", e))?;
+        .resolve("", BaseDirectory::AppConfig)
+        .map_err(|e| {
+            SwiftKitError::internal(format!("Failed to resolve App Config directory: {}", e))
+        })?;
 
     let dest_path = Path::new(&destination);
     if !is_within_app_config(dest_path, &app_config_path) {
-        return Err("Invalid destination path".to_string());
+        return Err(SwiftKitError::validation("Invalid destination path"));
     }
 
     // Create a temporary file path
@@ -76,30 +93,38 @@ pub async fn download_file(
         create_dirs_limited(parent, 3).await?;
     }
 
-    let client = Client::new();
-    let mut response = client.get(&url).send().await.map_err(|e| e.to_string())?;
+    let settings = current(handle).await;
+    let client = request_client(&settings)?;
+    let mut response = client.get(&url).send().await?.error_for_status()?;
     let total_size = response
         .content_length()
-        .ok_or("Failed to get content length")?;
+        .ok_or_else(|| SwiftKitError::Network("Failed to get content length".to_string()))?;
 
     // Create and write to temporary file
-    let mut file = File::create(temp_path).await.map_err(|e| e.to_string())?;
+    let mut file = File::create(temp_path)
+        .await
+        .map_err(|e| SwiftKitError::io(e, temp_path))?;
     let mut bytes_downloaded = 0u64;
     let start_time = Instant::now();
-    let rate_limit_bytes = rate_limit_kb.unwrap_or(50000) * 1024;
+    let rate_limit_bytes = rate_limit_kb
+        .or(settings.download_rate_limit_kb)
+        .unwrap_or(50000)
+        * 1024;
     let mut bucket = 0u64;
     let mut last_refill = Instant::now();
 
-    while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
-        if state.lock().map_err(|e| e.to_string())?.abort {
+    while let Some(chunk) = response.chunk().await? {
+        if job.is_cancelled() {
             // Clean up temp file if download is aborted
             let _ = fs::remove_file(temp_path).await;
-            return Err("Download aborted".into());
+            return Err(SwiftKitError::Cancelled("Download aborted".to_string()));
         }
 
         let chunk_len = chunk.len() as u64;
         bytes_downloaded += chunk_len;
-        file.write_all(&chunk).await.map_err(|e| e.to_string())?;
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| SwiftKitError::io(e, temp_path))?;
 
         let progress = (bytes_downloaded as f64 / total_size as f64) * 100.0;
         let elapsed = start_time.elapsed().as_secs_f64();
@@ -121,6 +146,7 @@ pub async fn download_file(
             0
         };
         let eta = format_eta(eta_secs);
+        job.progress(Some(progress), Some(format!("ETA {}", eta)));
 
         handle
             .emit(
@@ -132,7 +158,7 @@ pub async fn download_file(
                     eta,
                 },
             )
-            .map_err(|e| e.to_string())?;
+            .unwrap_or_default();
 
         bucket += chunk_len;
         let now = Instant::now();
@@ -152,16 +178,18 @@ pub async fn download_file(
     }
 
     // Ensure all data is written and close the file handle
-    file.flush().await.map_err(|e| e.to_string())?;
+    file.flush()
+        .await
+        .map_err(|e| SwiftKitError::io(e, temp_path))?;
     drop(file);
 
     // Wait a bit to ensure file handle is fully released
     tokio::time::sleep(Duration::from_millis(100)).await;
 
     // Rename temp file to final destination
-    fs::rename(temp_path, &destination)
-        .await
-        .map_err(|e| format!("Failed to rename temp file: {}", e))?;
+    fs::rename(temp_path, &destination).await.map_err(|e| {
+        SwiftKitError::io(format!("Failed to rename temp file: {}", e), &destination)
+    })?;
 
     Ok(destination)
 }
@@ -0,0 +1,15 @@
+use std::sync::Arc;
+use tauri::State;
+
+use super::error::SwiftKitError;
+use super::jobs::params::JobParams;
+use super::jobs::scheduler::JobScheduler;
+
+// Persists the job so it survives restarts, returns its id
+#[tauri::command(rename_all = "snake_case")]
+pub async fn enqueue_job(
+    params: JobParams,
+    scheduler: State<'_, Arc<JobScheduler>>,
+) -> Result<String, SwiftKitError> {
+    scheduler.enqueue(params).await
+}
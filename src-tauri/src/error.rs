@@ -0,0 +1,177 @@
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json::{json, Value};
+use std::fmt;
+use std::path::Path;
+
+// Error returned by every command, serialized as `{ code, message, details }`
+// so the frontend can branch on `code` instead of parsing messages.
+#[derive(Debug)]
+pub enum SwiftKitError {
+    Io {
+        message: String,
+        path: Option<String>,
+    },
+    ToolMissing {
+        tool: String,
+    },
+    ToolFailed {
+        tool: String,
+        message: String,
+        stderr: Option<String>,
+    },
+    Parse(String),
+    Network(String),
+    Validation(String),
+    Cancelled(String),
+    Internal(String),
+}
+
+impl SwiftKitError {
+    pub fn io(e: impl fmt::Display, path: impl AsRef<Path>) -> Self {
+        SwiftKitError::Io {
+            message: e.to_string(),
+            path: Some(path.as_ref().to_string_lossy().to_string()),
+        }
+    }
+
+    pub fn tool_failed(
+        tool: impl Into<String>,
+        message: impl Into<String>,
+        stderr: Option<String>,
+    ) -> Self {
+        SwiftKitError::ToolFailed {
+            tool: tool.into(),
+            message: message.into(),
+            stderr: stderr.filter(|stderr| !stderr.trim().is_empty()),
+        }
+    }
+
+    pub fn parse(message: impl Into<String>) -> Self {
+        SwiftKitError::Parse(message.into())
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        SwiftKitError::Validation(message.into())
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        SwiftKitError::Internal(message.into())
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            SwiftKitError::Io { .. } => "io",
+            SwiftKitError::ToolMissing { .. } => "tool_missing",
+            SwiftKitError::ToolFailed { .. } => "tool_failed",
+            SwiftKitError::Parse(_) => "parse",
+            SwiftKitError::Network(_) => "network",
+            SwiftKitError::Validation(_) => "validation",
+            SwiftKitError::Cancelled(_) => "cancelled",
+            SwiftKitError::Internal(_) => "internal",
+        }
+    }
+
+    pub fn details(&self) -> Option<Value> {
+        match self {
+            SwiftKitError::Io {
+                path: Some(path), ..
+            } => Some(json!({ "path": path })),
+            SwiftKitError::ToolMissing { tool } => Some(json!({ "tool": tool })),
+            SwiftKitError::ToolFailed { tool, stderr, .. } => {
+                Some(json!({ "tool": tool, "stderr": stderr }))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self, SwiftKitError::Cancelled(_))
+    }
+}
+
+impl fmt::Display for SwiftKitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SwiftKitError::Io {
+                message,
+                path: Some(path),
+            } => write!(f, "{}: {}", message, path),
+            SwiftKitError::Io { message, .. } => write!(f, "{}", message),
+            SwiftKitError::ToolMissing { tool } => write!(
+                f,
+                "{} was not found. Install it or set its path in the settings.",
+                tool
+            ),
+            SwiftKitError::ToolFailed { tool, message, .. } => {
+                write!(f, "{} failed: {}", tool, message)
+            }
+            SwiftKitError::Parse(message)
+            | SwiftKitError::Network(message)
+            | SwiftKitError::Validation(message)
+            | SwiftKitError::Cancelled(message)
+            | SwiftKitError::Internal(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for SwiftKitError {}
+
+impl Serialize for SwiftKitError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("SwiftKitError", 3)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("details", &self.details())?;
+        state.end()
+    }
+}
+
+impl From<std::io::Error> for SwiftKitError {
+    fn from(e: std::io::Error) -> Self {
+        SwiftKitError::Io {
+            message: e.to_string(),
+            path: None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for SwiftKitError {
+    fn from(e: reqwest::Error) -> Self {
+        SwiftKitError::Network(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for SwiftKitError {
+    fn from(e: serde_json::Error) -> Self {
+        SwiftKitError::Parse(e.to_string())
+    }
+}
+
+impl From<sqlx::Error> for SwiftKitError {
+    fn from(e: sqlx::Error) -> Self {
+        SwiftKitError::Internal(format!("Database error: {}", e))
+    }
+}
+
+impl From<tauri::Error> for SwiftKitError {
+    fn from(e: tauri::Error) -> Self {
+        SwiftKitError::Internal(e.to_string())
+    }
+}
+
+impl From<tokio::task::JoinError> for SwiftKitError {
+    fn from(e: tokio::task::JoinError) -> Self {
+        SwiftKitError::Internal(format!("Background task failed: {}", e))
+    }
+}
+
+impl From<image::ImageError> for SwiftKitError {
+    fn from(e: image::ImageError) -> Self {
+        match e {
+            image::ImageError::IoError(e) => e.into(),
+            image::ImageError::Unsupported(e) => SwiftKitError::Validation(e.to_string()),
+            image::ImageError::Parameter(e) => SwiftKitError::Validation(e.to_string()),
+            e => SwiftKitError::Parse(e.to_string()),
+        }
+    }
+}
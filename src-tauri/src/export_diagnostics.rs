@@ -0,0 +1,40 @@
+use sqlx::SqlitePool;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+use super::diagnostics::bundle;
+use super::error::SwiftKitError;
+
+// Zips recent logs, crash reports, tool versions and database info for bug reports
+#[tauri::command(rename_all = "snake_case")]
+pub async fn export_diagnostics(
+    handle: AppHandle,
+    output_path: Option<String>,
+) -> Result<String, SwiftKitError> {
+    let app_config_dir = handle.path().app_config_dir()?;
+    let pool = handle.try_state::<SqlitePool>();
+
+    let output = output_file(output_path)?;
+    let output = bundle::export(&app_config_dir, pool.as_deref(), output).await?;
+    tracing::info!("Exported diagnostics to {}", output.display());
+
+    Ok(output.to_string_lossy().to_string())
+}
+
+// A folder, or nothing for Downloads, gets a timestamped file name
+fn output_file(output_path: Option<String>) -> Result<PathBuf, SwiftKitError> {
+    let folder = match output_path.map(PathBuf::from) {
+        Some(path) if !path.is_dir() => return Ok(path),
+        Some(path) => path,
+        None => dirs::download_dir()
+            .or_else(dirs::home_dir)
+            .ok_or_else(|| SwiftKitError::internal("Downloads folder could not be found."))?,
+    };
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    Ok(folder.join(format!("swift-kit-diagnostics-{}.zip", timestamp)))
+}
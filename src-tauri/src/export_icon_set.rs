@@ -0,0 +1,159 @@
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tokio::task;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use super::error::SwiftKitError;
+use super::icon_set::common::{IconFile, IconPlatform, IconSetOptions};
+use super::icon_set::{android, ios, pwa};
+use super::img_encoders::encode::open_image;
+use super::img_encoders::icon::square_canvas;
+use super::utils::parse_color::parse_color;
+use super::utils::validate_file_name::validate_file_name;
+
+const DEFAULT_BACKGROUND: &str = "#ffffff";
+
+#[derive(Debug, Serialize)]
+pub struct IconSetResult {
+    // The folder the set was written to, or the zip file
+    pub output: String,
+    pub files: Vec<String>,
+}
+
+// Writes one folder per platform: `android/`, `ios/` and `pwa/`.
+// With `zip` the same layout goes into a single archive instead.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn export_icon_set(
+    img_path: String,
+    platforms: Vec<IconPlatform>,
+    output_path: String,
+    zip: Option<bool>,
+    background: Option<String>,
+    app_name: Option<String>,
+) -> Result<IconSetResult, SwiftKitError> {
+    let input_path = PathBuf::from(&img_path);
+    if !input_path.is_file() {
+        return Err(SwiftKitError::validation("Invalid image path"));
+    }
+    if platforms.is_empty() {
+        return Err(SwiftKitError::validation("No platforms were selected."));
+    }
+
+    let app_name = match app_name.filter(|name| !name.trim().is_empty()) {
+        Some(name) => name,
+        None => input_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default(),
+    };
+    let options = IconSetOptions {
+        background: parse_color(background.as_deref().unwrap_or(DEFAULT_BACKGROUND))?,
+        app_name,
+    };
+
+    let output = resolve_output(Path::new(&output_path), zip.unwrap_or(false), &options)?;
+
+    task::spawn_blocking(move || {
+        let square = square_canvas(&open_image(&input_path)?);
+
+        let mut files = Vec::new();
+        for platform in platforms {
+            let (folder, platform_files) = match platform {
+                IconPlatform::Android => ("android", android::files(&square, &options)?),
+                IconPlatform::Ios => ("ios", ios::files(&square, &options)?),
+                IconPlatform::Pwa => ("pwa", pwa::files(&square, &options)?),
+            };
+            files.extend(platform_files.into_iter().map(|file| IconFile {
+                path: format!("{}/{}", folder, file.path),
+                bytes: file.bytes,
+            }));
+        }
+
+        match &output {
+            Output::Folder(folder) => write_folder(folder, &files)?,
+            Output::Zip(path) => write_zip(path, &files)?,
+        }
+
+        let output = match output {
+            Output::Folder(path) | Output::Zip(path) => path,
+        };
+        Ok(IconSetResult {
+            output: output.to_string_lossy().to_string(),
+            files: files.into_iter().map(|file| file.path).collect(),
+        })
+    })
+    .await?
+}
+
+enum Output {
+    Folder(PathBuf),
+    Zip(PathBuf),
+}
+
+// A zip target may be the archive itself or the folder to put `<app name>-icons.zip` in
+fn resolve_output(
+    path: &Path,
+    zip: bool,
+    options: &IconSetOptions,
+) -> Result<Output, SwiftKitError> {
+    if !zip {
+        if !path.is_dir() {
+            return Err(SwiftKitError::validation(
+                "Output path could not be resolved.",
+            ));
+        }
+        return Ok(Output::Folder(path.to_path_buf()));
+    }
+
+    if path.is_dir() {
+        // The name also ends up in manifests, where these characters are fine
+        validate_file_name(&options.app_name).map_err(|_| {
+            SwiftKitError::validation(
+                "The app name can't be used as a file name, choose the zip file path instead.",
+            )
+        })?;
+        return Ok(Output::Zip(
+            path.join(format!("{}-icons.zip", options.app_name)),
+        ));
+    }
+    if !path
+        .parent()
+        .is_some_and(|parent| parent.as_os_str().is_empty() || parent.is_dir())
+    {
+        return Err(SwiftKitError::validation(
+            "Output path could not be resolved.",
+        ));
+    }
+    Ok(Output::Zip(path.to_path_buf()))
+}
+
+fn write_folder(folder: &Path, files: &[IconFile]) -> Result<(), SwiftKitError> {
+    for file in files {
+        let path = folder.join(&file.path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| SwiftKitError::io(e, parent))?;
+        }
+        fs::write(&path, &file.bytes).map_err(|e| SwiftKitError::io(e, &path))?;
+    }
+    Ok(())
+}
+
+fn write_zip(path: &Path, files: &[IconFile]) -> Result<(), SwiftKitError> {
+    let zip_error = |e: zip::result::ZipError| {
+        SwiftKitError::internal(format!("Failed to write icon archive: {}", e))
+    };
+
+    let archive = File::create(path).map_err(|e| SwiftKitError::io(e, path))?;
+    let mut zip = ZipWriter::new(archive);
+    for file in files {
+        zip.start_file(file.path.as_str(), SimpleFileOptions::default())
+            .map_err(zip_error)?;
+        zip.write_all(&file.bytes)
+            .map_err(|e| SwiftKitError::io(e, path))?;
+    }
+    zip.finish().map_err(zip_error)?;
+    Ok(())
+}
@@ -1,30 +1,35 @@
 use std::fs;
 use std::path::Path;
 
+use super::error::SwiftKitError;
 use super::utils::file_types::FileMeta;
 
 #[tauri::command(rename_all = "snake_case")]
 pub fn fetch_files(
     folder_path: String,
     extension_filter: Option<String>,
-) -> Result<Vec<FileMeta>, String> {
+) -> Result<Vec<FileMeta>, SwiftKitError> {
     let path = Path::new(&folder_path);
     if !path.exists() || !path.is_dir() {
-        return Err("Invalid folder path".into());
+        return Err(SwiftKitError::validation("Invalid folder path"));
     }
 
     let mut files = Vec::new();
-    for entry in fs::read_dir(path).map_err(|e| e.to_string())? {
-        let entry = entry.map_err(|e| e.to_string())?;
-        let metadata = entry.metadata().map_err(|e| e.to_string())?;
+    for entry in fs::read_dir(path).map_err(|e| SwiftKitError::io(e, path))? {
+        let entry = entry.map_err(|e| SwiftKitError::io(e, path))?;
+        let metadata = entry
+            .metadata()
+            .map_err(|e| SwiftKitError::io(e, entry.path()))?;
         if !metadata.is_file() {
             continue;
         }
 
-        let filename = entry
-            .file_name()
-            .into_string()
-            .map_err(|e| e.to_string_lossy().into_owned())?;
+        let filename = entry.file_name().into_string().map_err(|name| {
+            SwiftKitError::validation(format!(
+                "File name is not valid UTF-8: {}",
+                name.to_string_lossy()
+            ))
+        })?;
 
         if filename.starts_with('.') {
             continue;
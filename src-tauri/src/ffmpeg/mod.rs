@@ -0,0 +1,3 @@
+pub mod progress;
+pub mod runner;
+pub mod stderr_tail;
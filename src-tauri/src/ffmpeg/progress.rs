@@ -0,0 +1,76 @@
+use serde::Serialize;
+
+// One `-progress` block, emitted every time ffmpeg prints `progress=continue|end`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FfmpegProgress {
+    pub frame: Option<u64>,
+    pub fps: Option<f64>,
+    pub speed: Option<f64>,
+    // Seconds of output written so far
+    pub out_time: f64,
+    pub duration: Option<f64>,
+    pub percent: Option<f64>,
+    // Seconds left at the current speed
+    pub eta: Option<f64>,
+    pub done: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct ProgressParser {
+    duration: Option<f64>,
+    current: FfmpegProgress,
+}
+
+impl ProgressParser {
+    pub fn new(duration: Option<f64>) -> Self {
+        let duration = duration.filter(|duration| *duration > 0.0);
+        ProgressParser {
+            duration,
+            current: FfmpegProgress {
+                duration,
+                ..Default::default()
+            },
+        }
+    }
+
+    // Feeds a single `key=value` line, returns a snapshot once a block is complete
+    pub fn push_line(&mut self, line: &str) -> Option<FfmpegProgress> {
+        let (key, value) = line.trim().split_once('=')?;
+        let value = value.trim();
+
+        match key {
+            "frame" => self.current.frame = value.parse().ok(),
+            "fps" => self.current.fps = value.parse().ok(),
+            // Both are in microseconds, out_time_ms is misnamed in ffmpeg
+            "out_time_us" | "out_time_ms" => {
+                if let Ok(micros) = value.parse::<i64>() {
+                    self.current.out_time = micros.max(0) as f64 / 1_000_000.0;
+                }
+            }
+            "speed" => self.current.speed = value.trim_end_matches('x').parse().ok(),
+            "progress" => return Some(self.snapshot(value == "end")),
+            _ => {}
+        }
+
+        None
+    }
+
+    fn snapshot(&mut self, done: bool) -> FfmpegProgress {
+        let progress = &mut self.current;
+        progress.done = done;
+
+        if let Some(duration) = self.duration {
+            progress.percent = Some(if done {
+                100.0
+            } else {
+                (progress.out_time / duration * 100.0).clamp(0.0, 100.0)
+            });
+            progress.eta = progress
+                .speed
+                .filter(|speed| *speed > 0.0)
+                .map(|speed| ((duration - progress.out_time) / speed).max(0.0));
+        }
+
+        progress.clone()
+    }
+}
@@ -0,0 +1,147 @@
+use serde::Serialize;
+use std::path::Path;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_shell::process::{CommandEvent, TerminatedPayload};
+use tauri_plugin_shell::ShellExt;
+
+use super::progress::{FfmpegProgress, ProgressParser};
+use super::stderr_tail::StderrTail;
+use crate::error::SwiftKitError;
+use crate::jobs::manager::JobHandle;
+use crate::tools::registry::Tool;
+use crate::tools::resolver::resolve_tool;
+use crate::utils::format_duration::format_duration;
+use crate::utils::hidden_command::hidden_command;
+
+#[derive(Clone, Serialize)]
+struct ProgressEvent<'a> {
+    job_id: &'a str,
+    #[serde(flatten)]
+    progress: &'a FfmpegProgress,
+}
+
+// Runs ffmpeg for a job, reading `-progress` output from stdout and keeping
+// stderr only for the error message.
+pub struct FfmpegRunner<'a> {
+    handle: &'a AppHandle,
+    job: &'a JobHandle,
+    event: Option<&'a str>,
+    duration: Option<f64>,
+}
+
+impl<'a> FfmpegRunner<'a> {
+    pub fn new(handle: &'a AppHandle, job: &'a JobHandle) -> Self {
+        FfmpegRunner {
+            handle,
+            job,
+            event: None,
+            duration: None,
+        }
+    }
+
+    // Event the typed progress is emitted on, next to the job's own progress
+    pub fn event(mut self, event: &'a str) -> Self {
+        self.event = Some(event);
+        self
+    }
+
+    // Expected output duration in seconds, without it there is no percent or ETA
+    pub fn duration(mut self, duration: Option<f64>) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    pub async fn run(self, args: Vec<String>) -> Result<(), SwiftKitError> {
+        let ffmpeg = resolve_tool(self.handle, Tool::Ffmpeg).await?;
+        let (mut rx, child) = self
+            .handle
+            .shell()
+            .command(ffmpeg)
+            .args(["-hide_banner", "-nostats", "-progress", "pipe:1"])
+            .args(args)
+            .spawn()
+            .map_err(|e| {
+                SwiftKitError::tool_failed("ffmpeg", format!("Failed to start: {}", e), None)
+            })?;
+
+        let mut parser = ProgressParser::new(self.duration);
+        let mut stderr = StderrTail::default();
+        loop {
+            tokio::select! {
+                event = rx.recv() => {
+                    match event {
+                        Some(CommandEvent::Stdout(line)) => {
+                            if let Some(progress) = parser.push_line(&String::from_utf8_lossy(&line)) {
+                                self.report(&progress);
+                            }
+                        }
+                        Some(CommandEvent::Stderr(line)) => {
+                            stderr.push(&String::from_utf8_lossy(&line));
+                        }
+                        Some(CommandEvent::Error(e)) => {
+                            return Err(SwiftKitError::tool_failed("ffmpeg", e, Some(stderr.to_text())));
+                        }
+                        Some(CommandEvent::Terminated(TerminatedPayload { code, signal })) => {
+                            if code == Some(0) {
+                                return Ok(());
+                            }
+
+                            let status = match code {
+                                Some(code) => format!("exited with code {}", code),
+                                None => format!("was terminated by signal {:?}", signal),
+                            };
+                            let message = match stderr.last() {
+                                Some(last) => format!("{}: {}", status, last),
+                                None => status,
+                            };
+                            return Err(SwiftKitError::tool_failed("ffmpeg", message, Some(stderr.to_text())));
+                        }
+                        Some(_) => {}
+                        None => return Ok(()),
+                    }
+                }
+                _ = self.job.cancelled() => {
+                    if let Err(e) = child.kill() {
+                        tracing::warn!("Failed to kill ffmpeg process: {}", e);
+                    }
+                    return Err(SwiftKitError::Cancelled("ffmpeg was cancelled".to_string()));
+                }
+            }
+        }
+    }
+
+    fn report(&self, progress: &FfmpegProgress) {
+        let mut message = format_duration(progress.out_time, false);
+        if let Some(duration) = progress.duration {
+            message.push_str(&format!(" / {}", format_duration(duration, false)));
+        }
+        if let Some(speed) = progress.speed {
+            message.push_str(&format!(" at {:.2}x", speed));
+        }
+        self.job.progress(progress.percent, Some(message));
+
+        if let Some(event) = self.event {
+            let payload = ProgressEvent {
+                job_id: self.job.id(),
+                progress,
+            };
+            self.handle.emit(event, payload).unwrap_or_default();
+        }
+    }
+}
+
+// Container duration in seconds, None when ffprobe can't tell
+pub async fn probe_duration(ffprobe: &Path, input: &Path) -> Option<f64> {
+    let mut command = hidden_command(ffprobe);
+    command
+        .args(["-v", "error", "-show_entries", "format=duration"])
+        .args(["-of", "default=noprint_wrappers=1:nokey=1"])
+        .arg(input);
+    // Awaited on tokio's process driver, so the worker thread isn't held up
+    let output = tokio::process::Command::from(command).output().await.ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
@@ -0,0 +1,49 @@
+use std::collections::VecDeque;
+
+const DEFAULT_CAPACITY: usize = 40;
+
+// Keeps the last lines a tool printed to stderr for the error message
+#[derive(Debug)]
+pub struct StderrTail {
+    lines: VecDeque<String>,
+    capacity: usize,
+}
+
+impl Default for StderrTail {
+    fn default() -> Self {
+        StderrTail::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl StderrTail {
+    pub fn new(capacity: usize) -> Self {
+        StderrTail {
+            lines: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, line: &str) {
+        let line = line.trim_end();
+        if line.is_empty() {
+            return;
+        }
+
+        if self.lines.len() == self.capacity {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line.to_string());
+    }
+
+    pub fn last(&self) -> Option<&str> {
+        self.lines.back().map(String::as_str)
+    }
+
+    pub fn to_text(&self) -> String {
+        self.lines
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
@@ -0,0 +1,276 @@
+use rayon::prelude::*;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::fs::Metadata;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::UNIX_EPOCH;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::task;
+use walkdir::WalkDir;
+
+use super::error::SwiftKitError;
+use super::img_encoders::encode::{open_image, OutputFormat};
+use super::img_metrics::hash::ImageHash;
+use super::img_metrics::hash_cache::{self, HashedImage};
+
+// Resized and re-encoded copies usually stay within a few bits
+const DEFAULT_THRESHOLD: u32 = 8;
+const MAX_THRESHOLD: u32 = 32;
+
+#[derive(Debug, Serialize)]
+pub struct SimilarImage {
+    pub path: String,
+    pub width: u32,
+    pub height: u32,
+    pub size: u64,
+    // Hamming distance to the first image of the group
+    pub distance: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SimilarGroup {
+    // Highest resolution first, then largest file, the rest are trash candidates
+    pub images: Vec<SimilarImage>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SimilarImages {
+    pub scanned: usize,
+    // Files with an image extension that couldn't be opened, AVIF included
+    pub skipped: Vec<String>,
+    pub groups: Vec<SimilarGroup>,
+}
+
+#[derive(Clone, Serialize)]
+struct HashProgress {
+    completed: usize,
+    total: usize,
+}
+
+struct Candidate {
+    path: String,
+    mtime: i64,
+    size: u64,
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn find_similar_images(
+    handle: AppHandle,
+    folder: String,
+    recursive: bool,
+    threshold: Option<u32>,
+) -> Result<SimilarImages, SwiftKitError> {
+    if !Path::new(&folder).is_dir() {
+        return Err(SwiftKitError::validation("Invalid folder path"));
+    }
+
+    let threshold = threshold.unwrap_or(DEFAULT_THRESHOLD);
+    if threshold > MAX_THRESHOLD {
+        return Err(SwiftKitError::validation(format!(
+            "Threshold must be between 0 and {}.",
+            MAX_THRESHOLD
+        )));
+    }
+
+    let candidates = {
+        let folder = folder.clone();
+        task::spawn_blocking(move || collect_images(Path::new(&folder), recursive)).await?
+    };
+
+    // The cache only saves time, a missing or broken database just means hashing everything
+    let pool = handle
+        .try_state::<SqlitePool>()
+        .map(|pool| pool.inner().clone());
+    let mut cached = match &pool {
+        Some(pool) => hash_cache::load(pool, &folder).await.unwrap_or_else(|e| {
+            tracing::warn!("Image hash cache unavailable: {}", e);
+            HashMap::new()
+        }),
+        None => HashMap::new(),
+    };
+
+    let mut images = Vec::with_capacity(candidates.len());
+    let mut pending = Vec::new();
+    for candidate in candidates {
+        match cached.remove(&candidate.path) {
+            Some(image) if image.mtime == candidate.mtime && image.size == candidate.size => {
+                images.push(image)
+            }
+            _ => pending.push(candidate),
+        }
+    }
+
+    // What's left wasn't found by this scan. Only rows the scan could have seen are
+    // gone, not sibling folders sharing the prefix or subfolders of a shallow scan.
+    let gone: Vec<String> = cached
+        .into_keys()
+        .filter(|path| {
+            let path = Path::new(path);
+            if recursive {
+                path.starts_with(&folder)
+            } else {
+                path.parent() == Some(Path::new(&folder))
+            }
+        })
+        .collect();
+
+    let (hashed, skipped) = task::spawn_blocking(move || hash_images(&handle, pending)).await?;
+    if let Some(pool) = &pool {
+        if let Err(e) = hash_cache::store(pool, &hashed).await {
+            tracing::warn!("{}", e);
+        }
+        if let Err(e) = hash_cache::remove(pool, &gone).await {
+            tracing::warn!("{}", e);
+        }
+    }
+    images.extend(hashed);
+
+    Ok(SimilarImages {
+        scanned: images.len() + skipped.len(),
+        skipped,
+        groups: group(images, threshold),
+    })
+}
+
+// Files directly in `folder`, or below it when recursive, hidden entries are left out
+fn collect_images(folder: &Path, recursive: bool) -> Vec<Candidate> {
+    WalkDir::new(folder)
+        .follow_links(false)
+        .max_depth(if recursive { usize::MAX } else { 1 })
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0 || !entry.file_name().to_string_lossy().starts_with('.')
+        })
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file() && is_image(entry.path()))
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            Some(Candidate {
+                path: entry.path().to_string_lossy().to_string(),
+                mtime: modified_millis(&metadata),
+                size: metadata.len(),
+            })
+        })
+        .collect()
+}
+
+// Output-only formats (AVIF, ICNS) are kept so they show up as skipped instead of vanishing
+fn is_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(OutputFormat::from_extension)
+        .is_some()
+}
+
+fn modified_millis(metadata: &Metadata) -> i64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |duration| duration.as_millis() as i64)
+}
+
+// Decodes in parallel, returns the hashed images and the paths that failed to decode
+fn hash_images(handle: &AppHandle, pending: Vec<Candidate>) -> (Vec<HashedImage>, Vec<String>) {
+    let total = pending.len();
+    let completed = AtomicUsize::new(0);
+    let results: Vec<Result<HashedImage, String>> = pending
+        .into_par_iter()
+        .map(|candidate| {
+            let result = match open_image(Path::new(&candidate.path)) {
+                Ok(img) => Ok(HashedImage {
+                    width: img.width(),
+                    height: img.height(),
+                    hash: ImageHash::new(&img),
+                    path: candidate.path,
+                    mtime: candidate.mtime,
+                    size: candidate.size,
+                }),
+                Err(_) => Err(candidate.path),
+            };
+            let progress = HashProgress {
+                completed: completed.fetch_add(1, Ordering::Relaxed) + 1,
+                total,
+            };
+            handle
+                .emit("find-similar-images-progress", progress)
+                .unwrap_or_default();
+            result
+        })
+        .collect();
+
+    let mut hashed = Vec::with_capacity(results.len());
+    let mut skipped = Vec::new();
+    for result in results {
+        match result {
+            Ok(image) => hashed.push(image),
+            Err(path) => skipped.push(path),
+        }
+    }
+    (hashed, skipped)
+}
+
+// Images within `threshold` of each other are chained into the same group
+fn group(images: Vec<HashedImage>, threshold: u32) -> Vec<SimilarGroup> {
+    let mut parents: Vec<usize> = (0..images.len()).collect();
+    for i in 0..images.len() {
+        for j in i + 1..images.len() {
+            if images[i].hash.distance(&images[j].hash) <= threshold {
+                let (a, b) = (root(&mut parents, i), root(&mut parents, j));
+                parents[a.max(b)] = a.min(b);
+            }
+        }
+    }
+
+    let mut members: HashMap<usize, Vec<HashedImage>> = HashMap::new();
+    for (i, image) in images.into_iter().enumerate() {
+        let root = root(&mut parents, i);
+        members.entry(root).or_default().push(image);
+    }
+
+    let mut groups: Vec<SimilarGroup> = members
+        .into_values()
+        .filter(|members| members.len() > 1)
+        .map(|mut members| {
+            members.sort_by(|a, b| {
+                let pixels = |image: &HashedImage| u64::from(image.width) * u64::from(image.height);
+                pixels(b)
+                    .cmp(&pixels(a))
+                    .then(b.size.cmp(&a.size))
+                    .then(a.path.cmp(&b.path))
+            });
+            let best = members[0].hash;
+            SimilarGroup {
+                images: members
+                    .into_iter()
+                    .map(|image| SimilarImage {
+                        distance: best.distance(&image.hash),
+                        path: image.path,
+                        width: image.width,
+                        height: image.height,
+                        size: image.size,
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    groups.sort_by(|a, b| {
+        b.images
+            .len()
+            .cmp(&a.images.len())
+            .then(a.images[0].path.cmp(&b.images[0].path))
+    });
+    groups
+}
+
+fn root(parents: &mut [usize], mut i: usize) -> usize {
+    while parents[i] != i {
+        parents[i] = parents[parents[i]];
+        i = parents[i];
+    }
+    i
+}
@@ -4,6 +4,7 @@ use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Listener};
 use walkdir::WalkDir;
 
+use super::error::SwiftKitError;
 use super::get_available_disks::get_available_disks;
 
 #[tauri::command(rename_all = "snake_case")]
@@ -11,34 +12,33 @@ pub async fn finder(
     handle: AppHandle,
     search_term: String,
     disk: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), SwiftKitError> {
     if search_term.len() < 3 {
-        return Err("Search term must be at least 3 characters long".to_string());
+        return Err(SwiftKitError::validation(
+            "Search term must be at least 3 characters long",
+        ));
     }
 
-    let available_disks = get_available_disks()
-        .await
-        .map_err(|e| format!("Failed to get available disks: {}", e))?;
+    let available_disks = get_available_disks().await?;
+
+    let mount_points = available_disks
+        .iter()
+        .map(|d| d.mount_point.as_str())
+        .collect::<Vec<_>>();
 
     let search_path = match disk {
-        Some(ref d) if d == "*" => available_disks
-            .iter()
-            .map(|d| Path::new(d))
-            .collect::<Vec<_>>(),
+        Some(ref d) if d == "*" => mount_points.iter().map(Path::new).collect::<Vec<_>>(),
         Some(ref d) => {
-            if available_disks.contains(d) {
+            if mount_points.contains(&d.as_str()) {
                 vec![Path::new(d)]
             } else {
-                return Err(format!(
+                return Err(SwiftKitError::validation(format!(
                     "Invalid disk: {}. Available disks: {:?}",
-                    d, available_disks
-                ));
+                    d, mount_points
+                )));
             }
         }
-        None => available_disks
-            .iter()
-            .map(|d| Path::new(d))
-            .collect::<Vec<_>>(),
+        None => mount_points.iter().map(Path::new).collect::<Vec<_>>(),
     };
 
     let should_cancel = Arc::new(AtomicBool::new(false));
@@ -53,8 +53,10 @@ pub async fn finder(
             break;
         }
 
+        // Nested mounts are listed as disks of their own, don't walk into them twice
         let walker = WalkDir::new(disk_path)
             .follow_links(false)
+            .same_file_system(true)
             .into_iter()
             .filter_map(Result::ok)
             .filter(|entry| entry.file_type().is_file());
@@ -66,9 +68,7 @@ pub async fn finder(
 
             if entry.file_name().to_string_lossy().contains(&search_term) {
                 let path = entry.path().to_string_lossy().into_owned();
-                handle
-                    .emit("search-result", path)
-                    .map_err(|e| format!("Failed to emit result: {}", e))?;
+                handle.emit("search-result", path)?;
             }
         }
     }
@@ -0,0 +1,120 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tokio::task;
+
+use super::error::SwiftKitError;
+use super::img_encoders::encode::open_image;
+use super::img_encoders::icon::{icns, ico, square_canvas, ICNS_SIZES, ICO_SIZES};
+use super::utils::validate_file_name::validate_file_name;
+
+#[derive(Debug, Serialize)]
+pub struct GeneratedIcons {
+    pub ico: Option<String>,
+    pub icns: Option<String>,
+}
+
+// Writes `<file_name>.ico` and `<file_name>.icns` from a single source image.
+// Each file gets the requested sizes its format supports, ICO tops out at 256.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn generate_icons(
+    img_path: String,
+    sizes: Option<Vec<u32>>,
+    output_folder: Option<String>,
+    file_name: Option<String>,
+) -> Result<GeneratedIcons, SwiftKitError> {
+    let input_path = PathBuf::from(&img_path);
+    if !input_path.is_file() {
+        return Err(SwiftKitError::validation("Invalid image path"));
+    }
+
+    let (ico_sizes, icns_sizes) = match sizes {
+        Some(sizes) => split_sizes(sizes)?,
+        None => (ICO_SIZES.to_vec(), ICNS_SIZES.to_vec()),
+    };
+
+    let output_folder = match output_folder.filter(|folder| !folder.is_empty()) {
+        Some(folder) => PathBuf::from(folder),
+        None => input_path
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .to_path_buf(),
+    };
+    if !output_folder.is_dir() {
+        return Err(SwiftKitError::validation(
+            "Output path could not be resolved.",
+        ));
+    }
+    let file_name = match file_name.filter(|name| !name.is_empty()) {
+        Some(name) => {
+            validate_file_name(&name)?;
+            name
+        }
+        None => input_path
+            .file_stem()
+            .ok_or_else(|| SwiftKitError::validation("Invalid file name"))?
+            .to_string_lossy()
+            .to_string(),
+    };
+
+    let (ico_bytes, icns_bytes) = task::spawn_blocking(move || {
+        let square = square_canvas(&open_image(&input_path)?);
+        let ico_bytes = if ico_sizes.is_empty() {
+            None
+        } else {
+            Some(ico(&square, &ico_sizes)?)
+        };
+        let icns_bytes = if icns_sizes.is_empty() {
+            None
+        } else {
+            Some(icns(&square, &icns_sizes)?)
+        };
+        Ok::<_, SwiftKitError>((ico_bytes, icns_bytes))
+    })
+    .await??;
+
+    let write =
+        |bytes: Option<Vec<u8>>, extension: &str| -> Result<Option<String>, SwiftKitError> {
+            let Some(bytes) = bytes else {
+                return Ok(None);
+            };
+            let path = output_folder.join(format!("{}.{}", file_name, extension));
+            fs::write(&path, bytes).map_err(|e| SwiftKitError::io(e, &path))?;
+            Ok(Some(path.to_string_lossy().to_string()))
+        };
+
+    Ok(GeneratedIcons {
+        ico: write(ico_bytes, "ico")?,
+        icns: write(icns_bytes, "icns")?,
+    })
+}
+
+fn split_sizes(mut sizes: Vec<u32>) -> Result<(Vec<u32>, Vec<u32>), SwiftKitError> {
+    sizes.sort_unstable();
+    sizes.dedup();
+
+    let ico_sizes: Vec<u32> = sizes
+        .iter()
+        .copied()
+        .filter(|size| (1..=256).contains(size))
+        .collect();
+    let icns_sizes: Vec<u32> = sizes
+        .iter()
+        .copied()
+        .filter(|size| ICNS_SIZES.contains(size))
+        .collect();
+
+    if let Some(size) = sizes
+        .iter()
+        .find(|size| !ico_sizes.contains(size) && !icns_sizes.contains(size))
+    {
+        return Err(SwiftKitError::validation(format!(
+            "{}x{} fits neither ICO (up to 256) nor ICNS ({}).",
+            size,
+            size,
+            ICNS_SIZES.map(|size| size.to_string()).join(", ")
+        )));
+    }
+
+    Ok((ico_sizes, icns_sizes))
+}
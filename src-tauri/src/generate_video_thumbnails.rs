@@ -1,15 +1,18 @@
 use serde::{Deserialize, Serialize};
 use std::fs::{self, File};
 use std::io::Write;
-use std::path::{Path, PathBuf};
-use std::sync::{Arc, Mutex};
+use std::path::Path;
+use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tauri::path::BaseDirectory;
 use tauri::{AppHandle, Manager, State};
-use tauri_plugin_shell::process::{CommandChild, CommandEvent};
-use tauri_plugin_shell::ShellExt;
 use trash::delete;
 
+use super::error::SwiftKitError;
+use super::ffmpeg::runner::{probe_duration, FfmpegRunner};
+use super::jobs::manager::{JobHandle, JobKind, JobManager};
+use super::tools::registry::Tool;
+use super::tools::resolver::resolve_tool;
 use super::utils::format_duration::format_duration;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -19,113 +22,89 @@ pub struct VideoThumbnailResult {
     vtt_file_path: String,
 }
 
-pub struct FfmpegState {
-    pub process: Mutex<Option<CommandChild>>,
-    pub output_folder: Mutex<Option<PathBuf>>,
-}
-
 #[tauri::command(rename_all = "snake_case")]
-pub async fn generate_video_thumbnails<'a>(
+pub async fn generate_video_thumbnails(
     video_path: String,
+    job_id: Option<String>,
     handle: AppHandle,
-    state: State<'a, Arc<FfmpegState>>,
-) -> Result<VideoThumbnailResult, String> {
+    jobs: State<'_, Arc<JobManager>>,
+) -> Result<VideoThumbnailResult, SwiftKitError> {
+    let job = jobs.start(
+        &handle,
+        job_id,
+        JobKind::VideoThumbnails,
+        video_path.clone(),
+    )?;
+    let result = generate(&handle, &job, video_path).await;
+    job.finish(&result);
+    result
+}
+
+async fn generate(
+    handle: &AppHandle,
+    job: &JobHandle,
+    video_path: String,
+) -> Result<VideoThumbnailResult, SwiftKitError> {
     let video_path_absolute = Path::new(&video_path);
     if !video_path_absolute.exists() || !video_path_absolute.is_file() {
-        return Err("Invalid file path".into());
+        return Err(SwiftKitError::validation("Invalid file path"));
     }
 
     let start = SystemTime::now();
     let unique_folder = start
         .duration_since(UNIX_EPOCH)
-        .map_err(|e| e.to_string())?
+        .map_err(|e| SwiftKitError::internal(e.to_string()))?
         .as_millis()
         .to_string();
 
     let base_dir = handle
         .path()
-        .resolve("".to_string(), BaseDirectory::AppConfig)
-        .map_err(|e| format!("Failed to resolve App Config directory: {}", e))?;
+        .resolve("", BaseDirectory::AppConfig)
+        .map_err(|e| {
+            SwiftKitError::internal(format!("Failed to resolve App Config directory: {}", e))
+        })?;
     let thumbnails_dir = base_dir.join(&unique_folder);
-    fs::create_dir_all(&thumbnails_dir).map_err(|e| e.to_string())?;
-
-    // Store folder path in state
-    {
-        let mut output_folder = state.output_folder.lock().unwrap();
-        *output_folder = Some(thumbnails_dir.clone());
-    }
-
-    let ffmpeg_command = handle
-        .shell()
-        .sidecar("ffmpeg")
-        .map_err(|e| format!("Failed to create ffmpeg sidecar: {}", e))?
-        .arg("-accurate_seek")
-        .arg("-i")
-        .arg(&video_path_absolute)
-        .arg("-vf")
-        .arg("fps=1/5,scale=320:-1")
-        .arg("-fps_mode")
-        .arg("passthrough")
-        .arg("-q:v")
-        .arg("2")
-        .arg("-f")
-        .arg("image2")
-        .arg(
-            thumbnails_dir
-                .join("thumb%04d.jpg")
-                .to_string_lossy()
-                .to_string(),
-        );
-
-    let (mut rx, child) = ffmpeg_command
-        .spawn()
-        .map_err(|e| format!("Failed to start ffmpeg process: {}", e))?;
-
-    // Store process in state
-    {
-        let mut process_lock = state.process.lock().unwrap();
-        *process_lock = Some(child);
-    }
-
-    // Wait for FFmpeg to finish and collect output
-    let mut output = String::new();
-    while let Some(event) = rx.recv().await {
-        match event {
-            CommandEvent::Stdout(line) => {
-                if let Ok(str_line) = String::from_utf8(line) {
-                    output.push_str(&str_line);
-                    output.push('\n');
-                }
-            }
-            CommandEvent::Stderr(line) => {
-                if let Ok(str_line) = String::from_utf8(line) {
-                    output.push_str(&str_line);
-                    output.push('\n');
-                }
-            }
-            CommandEvent::Error(err) => {
-                return Err(format!("FFmpeg process error: {}", err));
-            }
-            CommandEvent::Terminated(status) => {
-                if status.code != Some(0) {
-                    return Err(format!("FFmpeg process failed with output: {}", output));
-                }
-                break;
-            }
-            _ => {
-                return Err("Unexpected error".to_string());
-            }
-        }
+    fs::create_dir_all(&thumbnails_dir).map_err(|e| SwiftKitError::io(e, &thumbnails_dir))?;
+
+    let ffprobe = resolve_tool(handle, Tool::Ffprobe).await?;
+    let duration = probe_duration(&ffprobe, video_path_absolute).await;
+    let args = vec![
+        "-accurate_seek".to_string(),
+        "-i".to_string(),
+        video_path_absolute.to_string_lossy().to_string(),
+        "-vf".to_string(),
+        "fps=1/5,scale=320:-1".to_string(),
+        "-fps_mode".to_string(),
+        "passthrough".to_string(),
+        "-q:v".to_string(),
+        "2".to_string(),
+        "-f".to_string(),
+        "image2".to_string(),
+        thumbnails_dir
+            .join("thumb%04d.jpg")
+            .to_string_lossy()
+            .to_string(),
+    ];
+
+    let run = FfmpegRunner::new(handle, job)
+        .event("video_thumbnails_progress")
+        .duration(duration)
+        .run(args);
+    if let Err(e) = run.await {
+        // The folder is unique to this run, nothing else can use it
+        delete(&thumbnails_dir).map_err(|e| SwiftKitError::io(e, &thumbnails_dir))?;
+        return Err(e);
     }
 
     // Generate VTT file AFTER FFmpeg finishes
     let vtt_file_path = thumbnails_dir.join("thumbnails.vtt");
-    let mut vtt_file = File::create(&vtt_file_path).map_err(|e| e.to_string())?;
+    let mut vtt_file =
+        File::create(&vtt_file_path).map_err(|e| SwiftKitError::io(e, &vtt_file_path))?;
 
-    writeln!(vtt_file, "WEBVTT\n").map_err(|e| e.to_string())?;
+    writeln!(vtt_file, "WEBVTT\n")?;
     let mut thumbnail_count = 0;
-    for entry in fs::read_dir(&thumbnails_dir).unwrap() {
-        let entry = entry.unwrap();
+    for entry in fs::read_dir(&thumbnails_dir).map_err(|e| SwiftKitError::io(e, &thumbnails_dir))? {
+        let entry = entry?;
         if entry.path().extension().and_then(|s| s.to_str()) == Some("jpg") {
             thumbnail_count += 1;
             let start_seconds = (thumbnail_count - 1) as f64 * 5.0;
@@ -137,8 +116,7 @@ pub async fn generate_video_thumbnails<'a>(
                 format_duration(start_seconds, true),
                 format_duration(end_seconds, true),
                 entry.path().to_string_lossy().replace("\\", "/")
-            )
-            .map_err(|e| e.to_string())?;
+            )?;
         }
     }
 
@@ -149,23 +127,11 @@ pub async fn generate_video_thumbnails<'a>(
     })
 }
 
+// Cancels every running thumbnail job, their output folders are removed by the jobs themselves
 #[tauri::command(rename_all = "snake_case")]
-pub async fn stop_video_thumbnail_generation<'a>(
-    state: State<'a, Arc<FfmpegState>>,
-) -> Result<(), String> {
-    let mut process_lock = state.process.lock().unwrap();
-    if let Some(child) = process_lock.take() {
-        match child.kill() {
-            Ok(_) => println!("FFmpeg process killed"),
-            Err(e) => return Err(format!("Failed to kill process: {}", e)),
-        }
-    }
-
-    // Remove generated folder
-    let mut output_folder = state.output_folder.lock().unwrap();
-    if let Some(folder) = output_folder.take() {
-        delete(&folder).map_err(|e| e.to_string())?;
-    }
-
+pub async fn stop_video_thumbnail_generation(
+    jobs: State<'_, Arc<JobManager>>,
+) -> Result<(), SwiftKitError> {
+    jobs.cancel_kind(JobKind::VideoThumbnails);
     Ok(())
 }
@@ -1,28 +1,332 @@
-use std::{os::windows::process::CommandExt, process::Command};
+use serde::{Deserialize, Serialize};
+
+use super::error::SwiftKitError;
+
+#[cfg(target_os = "windows")]
+use super::utils::hidden_command::hidden_command;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskInfo {
+    pub mount_point: String,
+    pub file_system: String,
+    pub label: Option<String>,
+    pub total_bytes: u64,
+    pub free_bytes: u64,
+    pub is_removable: bool,
+}
 
 #[tauri::command(rename_all = "snake_case")]
-pub async fn get_available_disks() -> Result<Vec<String>, String> {
-    if cfg!(target_os = "windows") {
-        let available_disks = get_windows_drives()?;
-        Ok(available_disks)
-    } else {
-        Err("Unsupported OS".to_string())
+pub async fn get_available_disks() -> Result<Vec<DiskInfo>, SwiftKitError> {
+    #[cfg(target_os = "windows")]
+    {
+        get_windows_drives()
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        get_linux_mounts()
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        get_macos_mounts()
     }
+
+    #[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+    {
+        Err(SwiftKitError::internal("Unsupported OS"))
+    }
+}
+
+#[cfg(target_os = "windows")]
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct WindowsLogicalDisk {
+    #[serde(rename = "DeviceID")]
+    device_id: String,
+    file_system: Option<String>,
+    volume_name: Option<String>,
+    size: Option<u64>,
+    free_space: Option<u64>,
+    drive_type: Option<u32>,
 }
 
 // Get list of valid Windows drives
-pub fn get_windows_drives() -> Result<Vec<String>, String> {
-    let output = Command::new("powershell")
-        .creation_flags(0x08000000)
-        .args(&[
+#[cfg(target_os = "windows")]
+pub fn get_windows_drives() -> Result<Vec<DiskInfo>, SwiftKitError> {
+    let output = hidden_command("powershell")
+        .args([
+            "-NoProfile",
             "-Command",
-            "Get-PSDrive -PSProvider FileSystem | Select-Object -ExpandProperty Root",
+            "ConvertTo-Json -Compress -InputObject @(Get-CimInstance Win32_LogicalDisk | Select-Object DeviceID,FileSystem,VolumeName,Size,FreeSpace,DriveType)",
         ])
         .output()
-        .map_err(|e| format!("Failed to get Windows drives: {}", e))?;
+        .map_err(|e| {
+            SwiftKitError::tool_failed("powershell", format!("Failed to execute: {}", e), None)
+        })?;
+
+    if !output.status.success() {
+        return Err(SwiftKitError::tool_failed(
+            "powershell",
+            "Failed to get Windows drives",
+            Some(String::from_utf8_lossy(&output.stderr).to_string()),
+        ));
+    }
+
+    let logical_disks: Vec<WindowsLogicalDisk> = serde_json::from_slice(&output.stdout)
+        .map_err(|e| SwiftKitError::parse(format!("Failed to parse Windows drives: {}", e)))?;
 
-    Ok(String::from_utf8_lossy(&output.stdout)
-        .lines()
-        .map(|s| s.trim().to_string())
+    // DriveType 2 is removable media, 4 is a network drive and 5 an optical disc
+    Ok(logical_disks
+        .into_iter()
+        .filter(|disk| disk.size.is_some())
+        .map(|disk| DiskInfo {
+            mount_point: format!("{}\\", disk.device_id),
+            file_system: disk.file_system.unwrap_or_default(),
+            label: disk.volume_name.filter(|name| !name.is_empty()),
+            total_bytes: disk.size.unwrap_or(0),
+            free_bytes: disk.free_space.unwrap_or(0),
+            is_removable: matches!(disk.drive_type, Some(2) | Some(5)),
+        })
         .collect())
 }
+
+#[cfg(target_os = "linux")]
+const PSEUDO_FILE_SYSTEMS: [&str; 27] = [
+    "autofs",
+    "binfmt_misc",
+    "bpf",
+    "cgroup",
+    "cgroup2",
+    "configfs",
+    "debugfs",
+    "devpts",
+    "devtmpfs",
+    "efivarfs",
+    "fusectl",
+    "fuse.gvfsd-fuse",
+    "fuse.portal",
+    "hugetlbfs",
+    "mqueue",
+    "nsfs",
+    "proc",
+    "pstore",
+    "ramfs",
+    "rpc_pipefs",
+    "securityfs",
+    "selinuxfs",
+    "squashfs",
+    "sysfs",
+    "tmpfs",
+    "tracefs",
+    "zram",
+];
+
+// Enumerate real mounts from /proc/self/mountinfo, skipping kernel pseudo filesystems
+#[cfg(target_os = "linux")]
+pub fn get_linux_mounts() -> Result<Vec<DiskInfo>, SwiftKitError> {
+    use std::collections::HashMap;
+    use std::fs;
+    use std::path::Path;
+
+    let mountinfo = fs::read_to_string("/proc/self/mountinfo")
+        .map_err(|e| SwiftKitError::io(e, "/proc/self/mountinfo"))?;
+
+    // Map canonical device paths to their filesystem labels
+    let mut labels: HashMap<String, String> = HashMap::new();
+    if let Ok(entries) = fs::read_dir("/dev/disk/by-label") {
+        for entry in entries.flatten() {
+            if let Ok(device) = fs::canonicalize(entry.path()) {
+                labels.insert(
+                    device.to_string_lossy().to_string(),
+                    unescape_mount_field(&entry.file_name().to_string_lossy()),
+                );
+            }
+        }
+    }
+
+    let mut disks: Vec<DiskInfo> = Vec::new();
+    for line in mountinfo.lines() {
+        let Some((mount_fields, fs_fields)) = line.split_once(" - ") else {
+            continue;
+        };
+
+        let Some(mount_point) = mount_fields.split(' ').nth(4) else {
+            continue;
+        };
+        let mut fs_fields = fs_fields.split(' ');
+        let file_system = fs_fields.next().unwrap_or_default();
+        let source = fs_fields.next().unwrap_or_default();
+
+        if PSEUDO_FILE_SYSTEMS.contains(&file_system) {
+            continue;
+        }
+
+        let mount_point = unescape_mount_field(mount_point);
+        let Some((total_bytes, free_bytes)) = statvfs_capacity(&mount_point) else {
+            continue;
+        };
+        if total_bytes == 0 {
+            continue;
+        }
+
+        let device = fs::canonicalize(source)
+            .map(|path| path.to_string_lossy().to_string())
+            .unwrap_or_else(|_| source.to_string());
+        let is_removable = Path::new(&device)
+            .file_name()
+            .map(|name| is_linux_block_device_removable(&name.to_string_lossy()))
+            .unwrap_or(false);
+
+        let disk = DiskInfo {
+            mount_point,
+            file_system: file_system.to_string(),
+            label: labels.get(&device).cloned(),
+            total_bytes,
+            free_bytes,
+            is_removable,
+        };
+
+        // Later entries shadow earlier ones mounted on the same path
+        match disks.iter_mut().find(|d| d.mount_point == disk.mount_point) {
+            Some(existing) => *existing = disk,
+            None => disks.push(disk),
+        }
+    }
+
+    Ok(disks)
+}
+
+// Partitions don't carry the removable flag themselves, their parent block device does
+#[cfg(target_os = "linux")]
+fn is_linux_block_device_removable(device_name: &str) -> bool {
+    use std::fs;
+    use std::path::Path;
+
+    let block_path = Path::new("/sys/class/block").join(device_name);
+    let read_flag = |path: &Path| {
+        fs::read_to_string(path.join("removable"))
+            .map(|flag| flag.trim() == "1")
+            .ok()
+    };
+
+    if let Some(flag) = read_flag(&block_path) {
+        return flag;
+    }
+
+    fs::canonicalize(&block_path)
+        .ok()
+        .and_then(|path| path.parent().and_then(read_flag))
+        .unwrap_or(false)
+}
+
+// Mount tables and udev links escape whitespace as octal (\040) or hex (\x20)
+#[cfg(target_os = "linux")]
+fn unescape_mount_field(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut output = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'\\' {
+            let escaped = if bytes.get(i + 1) == Some(&b'x') {
+                field
+                    .get(i + 2..i + 4)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            } else {
+                field
+                    .get(i + 1..i + 4)
+                    .and_then(|octal| u8::from_str_radix(octal, 8).ok())
+            };
+
+            if let Some(byte) = escaped {
+                output.push(byte);
+                i += 4;
+                continue;
+            }
+        }
+
+        output.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&output).to_string()
+}
+
+#[cfg(target_os = "macos")]
+pub fn get_macos_mounts() -> Result<Vec<DiskInfo>, SwiftKitError> {
+    use std::ffi::CStr;
+
+    let mut mounts: *mut libc::statfs = std::ptr::null_mut();
+    let count = unsafe { libc::getmntinfo(&mut mounts, libc::MNT_NOWAIT) };
+    if count <= 0 || mounts.is_null() {
+        return Err(SwiftKitError::Io {
+            message: format!(
+                "Failed to read mount table: {}",
+                std::io::Error::last_os_error()
+            ),
+            path: None,
+        });
+    }
+
+    let mounts = unsafe { std::slice::from_raw_parts(mounts, count as usize) };
+    let mut disks = Vec::new();
+    for mount in mounts {
+        if mount.f_flags & libc::MNT_LOCAL as u32 == 0 {
+            continue;
+        }
+
+        let mount_point = unsafe { CStr::from_ptr(mount.f_mntonname.as_ptr()) }
+            .to_string_lossy()
+            .to_string();
+        let file_system = unsafe { CStr::from_ptr(mount.f_fstypename.as_ptr()) }
+            .to_string_lossy()
+            .to_string();
+
+        if file_system == "devfs" || file_system == "autofs" || mount.f_blocks == 0 {
+            continue;
+        }
+
+        // System volumes live under /System/Volumes, user mounted disks under /Volumes
+        if mount_point.starts_with("/System/Volumes") && mount_point != "/System/Volumes/Data" {
+            continue;
+        }
+
+        let is_removable = mount_point.starts_with("/Volumes/");
+        let label = if mount_point == "/" {
+            None
+        } else {
+            std::path::Path::new(&mount_point)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+        };
+
+        disks.push(DiskInfo {
+            total_bytes: mount.f_blocks * mount.f_bsize as u64,
+            free_bytes: mount.f_bavail * mount.f_bsize as u64,
+            mount_point,
+            file_system,
+            label,
+            is_removable,
+        });
+    }
+
+    Ok(disks)
+}
+
+#[cfg(target_os = "linux")]
+fn statvfs_capacity(mount_point: &str) -> Option<(u64, u64)> {
+    use std::ffi::CString;
+
+    let path = CString::new(mount_point).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+
+    let fragment_size = stat.f_frsize as u64;
+    Some((
+        stat.f_blocks as u64 * fragment_size,
+        stat.f_bavail as u64 * fragment_size,
+    ))
+}
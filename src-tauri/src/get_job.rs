@@ -0,0 +1,11 @@
+use std::sync::Arc;
+use tauri::State;
+
+use super::error::SwiftKitError;
+use super::jobs::manager::{JobInfo, JobManager};
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_job(job_id: String, jobs: State<'_, Arc<JobManager>>) -> Result<JobInfo, SwiftKitError> {
+    jobs.get(&job_id)
+        .ok_or_else(|| SwiftKitError::validation(format!("Job {} was not found", job_id)))
+}
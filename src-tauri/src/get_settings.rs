@@ -0,0 +1,13 @@
+use std::sync::Arc;
+use tauri::State;
+
+use super::error::SwiftKitError;
+use super::settings::model::Settings;
+use super::settings::service::SettingsService;
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_settings(
+    settings: State<'_, Arc<SettingsService>>,
+) -> Result<Settings, SwiftKitError> {
+    settings.get().await
+}
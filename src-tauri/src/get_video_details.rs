@@ -1,11 +1,15 @@
 use serde::Deserialize;
 use std::fs;
 use std::path::Path;
-use std::{os::windows::process::CommandExt, process::Command};
+use tauri::AppHandle;
 
+use super::error::SwiftKitError;
+use super::tools::registry::Tool;
+use super::tools::resolver::resolve_tool;
 use super::utils::file_types::IVideoMeta;
 use super::utils::file_types::VideoTrackDetail;
 use super::utils::format_duration::format_duration;
+use super::utils::hidden_command::hidden_command;
 
 #[derive(Debug, Deserialize)]
 struct FFProbeStream {
@@ -29,10 +33,18 @@ struct FFProbeOutput {
 }
 
 #[tauri::command(rename_all = "snake_case")]
-pub async fn get_video_details(video_path: String) -> Result<IVideoMeta, String> {
-    let input_path = Path::new(&video_path);
+pub async fn get_video_details(
+    handle: AppHandle,
+    video_path: String,
+) -> Result<IVideoMeta, SwiftKitError> {
+    let ffprobe = resolve_tool(&handle, Tool::Ffprobe).await?;
+    probe_video_details(&ffprobe, &video_path)
+}
+
+pub fn probe_video_details(ffprobe: &Path, video_path: &str) -> Result<IVideoMeta, SwiftKitError> {
+    let input_path = Path::new(video_path);
     if !input_path.exists() || !input_path.is_file() {
-        return Err("Invalid video path".into());
+        return Err(SwiftKitError::validation("Invalid video path"));
     }
 
     let extension = input_path
@@ -40,11 +52,10 @@ pub async fn get_video_details(video_path: String) -> Result<IVideoMeta, String>
         .and_then(|ext| ext.to_str())
         .map(|ext| ext.to_lowercase());
     if extension != Some("mp4".to_string()) && extension != Some("mkv".to_string()) {
-        return Err("Unsupported video format".into());
+        return Err(SwiftKitError::validation("Unsupported video format"));
     }
 
-    let metadata =
-        fs::metadata(&input_path).map_err(|e| format!("Failed to get file metadata: {}", e))?;
+    let metadata = fs::metadata(input_path).map_err(|e| SwiftKitError::io(e, input_path))?;
     let filename = input_path
         .file_name()
         .unwrap_or_default()
@@ -52,9 +63,8 @@ pub async fn get_video_details(video_path: String) -> Result<IVideoMeta, String>
         .to_string();
     let filesize = metadata.len();
 
-    let mut ffprobe_command = Command::new("ffprobe");
+    let mut ffprobe_command = hidden_command(ffprobe);
     ffprobe_command
-        .creation_flags(0x08000000)
         .arg("-v")
         .arg("error")
         .arg("-show_entries")
@@ -67,24 +77,23 @@ pub async fn get_video_details(video_path: String) -> Result<IVideoMeta, String>
         .arg("stream_disposition")
         .arg("-of")
         .arg("json")
-        .arg(&video_path);
+        .arg(video_path);
 
-    let output = ffprobe_command
-        .output()
-        .map_err(|e| format!("Failed to execute sidecar: {}", e))?;
+    let output = ffprobe_command.output().map_err(|e| {
+        SwiftKitError::tool_failed("ffprobe", format!("Failed to execute: {}", e), None)
+    })?;
 
     // Check if the command failed
     if !output.status.success() {
-        return Err(format!(
-            "Ffprobe failed with error:\nCommand: ffprobe -v error -show_entries stream=codec_type,tags -show_entries format=duration -of json {}\nStderr:\n{}\nStdout:\n{}",
-            &video_path,
-            String::from_utf8_lossy(&output.stderr),
-            String::from_utf8_lossy(&output.stdout)
+        return Err(SwiftKitError::tool_failed(
+            "ffprobe",
+            format!("exited with {} while probing {}", output.status, video_path),
+            Some(String::from_utf8_lossy(&output.stderr).to_string()),
         ));
     }
 
     let ffprobe_json: FFProbeOutput = serde_json::from_slice(&output.stdout)
-        .map_err(|e| format!("Failed to parse ffprobe output: {}", e))?;
+        .map_err(|e| SwiftKitError::parse(format!("Failed to parse ffprobe output: {}", e)))?;
 
     let duration_in_seconds = ffprobe_json
         .format
@@ -93,7 +102,10 @@ pub async fn get_video_details(video_path: String) -> Result<IVideoMeta, String>
         .unwrap_or(0.0);
     let duration = format_duration(duration_in_seconds, false);
 
-    let stream = ffprobe_json.streams.get(0).ok_or("No video stream found")?;
+    let stream = ffprobe_json
+        .streams
+        .first()
+        .ok_or_else(|| SwiftKitError::parse("No video stream found"))?;
 
     let width = stream.width.unwrap_or(0);
     let height = stream.height.unwrap_or(0);
@@ -159,12 +171,12 @@ pub async fn get_video_details(video_path: String) -> Result<IVideoMeta, String>
         }
     }
 
-    if default_audio.is_none() && audio_tracks.len() > 0 {
-        default_audio = Some(audio_tracks[0].value.try_into().unwrap());
+    if default_audio.is_none() {
+        default_audio = audio_tracks.first().map(|track| track.value);
     }
 
-    if default_subtitle.is_none() && subtitle_tracks.len() > 0 {
-        default_subtitle = Some(subtitle_tracks[0].value.try_into().unwrap());
+    if default_subtitle.is_none() {
+        default_subtitle = subtitle_tracks.first().map(|track| track.value);
     }
 
     Ok(IVideoMeta {
@@ -1,43 +1,137 @@
-use std::path::Path;
-use std::{os::windows::process::CommandExt, process::Command};
+use std::path::{Path, PathBuf};
+
+use super::error::SwiftKitError;
+use super::utils::hidden_command::hidden_command;
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn highlight_file(file_path: String) -> Result<(), SwiftKitError> {
+    highlight_files(vec![file_path])
+}
 
 #[tauri::command(rename_all = "snake_case")]
-pub fn highlight_file(file_path: String) -> Result<(), String> {
-    let sanitized_path = sanitize_path(&file_path)?;
+pub fn highlight_files(file_paths: Vec<String>) -> Result<(), SwiftKitError> {
+    if file_paths.is_empty() {
+        return Err(SwiftKitError::validation("No files were provided."));
+    }
+
+    let mut files = Vec::with_capacity(file_paths.len());
+    for file_path in &file_paths {
+        let path = Path::new(file_path);
+        if !path.is_file() {
+            return Err(SwiftKitError::validation(format!(
+                "The provided path is not a valid file: {}",
+                file_path
+            )));
+        }
+
+        // dunce keeps Windows paths free of the \\?\ prefix explorer can't handle
+        files.push(dunce::canonicalize(path).map_err(|e| SwiftKitError::io(e, path))?);
+    }
+
+    reveal_files(&files)
+}
 
-    if !Path::new(&sanitized_path).is_file() {
-        return Err("The provided path is not a valid file.".to_string());
+// Unique parent folders in the order their files were given
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn parent_folders(files: &[PathBuf]) -> Vec<&Path> {
+    let mut folders: Vec<&Path> = Vec::new();
+    for parent in files.iter().filter_map(|file| file.parent()) {
+        if !folders.contains(&parent) {
+            folders.push(parent);
+        }
     }
+    folders
+}
 
-    let command = if cfg!(target_os = "windows") {
-        format!("explorer /select,{}", sanitized_path)
-    } else {
-        return Err("Unsupported OS".to_string());
-    };
+#[cfg(target_os = "windows")]
+fn reveal_files(files: &[PathBuf]) -> Result<(), SwiftKitError> {
+    use std::os::windows::process::CommandExt;
+
+    // Explorer can only select a single item per window, so open each
+    // folder once with the first requested file in it selected.
+    for folder in parent_folders(files) {
+        let Some(file) = files.iter().find(|file| file.parent() == Some(folder)) else {
+            continue;
+        };
+
+        // Windows paths can't contain quotes, so wrapping the path is enough
+        // for explorer to parse it without going through cmd.
+        hidden_command("explorer")
+            .raw_arg(format!("/select,\"{}\"", file.display()))
+            .spawn()
+            .map_err(|e| {
+                SwiftKitError::tool_failed("explorer", format!("Failed to open: {}", e), None)
+            })?;
+    }
 
-    Command::new("cmd")
-        .creation_flags(0x08000000)
-        .arg("/C")
-        .arg(command)
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn reveal_files(files: &[PathBuf]) -> Result<(), SwiftKitError> {
+    hidden_command("open")
+        .arg("-R")
+        .args(files)
         .spawn()
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| {
+            SwiftKitError::tool_failed("open", format!("Failed to open Finder: {}", e), None)
+        })?;
 
     Ok(())
 }
 
-fn sanitize_path(file_path: &str) -> Result<String, String> {
-    let path = Path::new(file_path);
-    let sanitized_path = path
-        .to_str()
-        .ok_or_else(|| "Invalid file path".to_string())?;
-    let safe_path = sanitized_path
-        .replace(";", "")
-        .replace("&", "")
-        .replace("|", "")
-        .replace("`", "");
-    if safe_path != sanitized_path {
-        return Err("Unsafe characters detected in file path.".to_string());
+#[cfg(target_os = "linux")]
+fn reveal_files(files: &[PathBuf]) -> Result<(), SwiftKitError> {
+    if show_items_over_dbus(files).is_ok() {
+        return Ok(());
     }
 
-    Ok(safe_path.to_string())
+    // No FileManager1 implementation on the session bus, open the folders instead
+    for folder in parent_folders(files) {
+        hidden_command("xdg-open")
+            .arg(folder)
+            .spawn()
+            .map_err(|e| {
+                SwiftKitError::tool_failed(
+                    "xdg-open",
+                    format!("Failed to open file manager: {}", e),
+                    None,
+                )
+            })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn show_items_over_dbus(files: &[PathBuf]) -> Result<(), SwiftKitError> {
+    let uris = files
+        .iter()
+        .map(|file| {
+            url::Url::from_file_path(file)
+                .map(|uri| uri.to_string())
+                .map_err(|_| {
+                    SwiftKitError::validation(format!("Invalid file path: {}", file.display()))
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let connection = zbus::blocking::Connection::session()
+        .map_err(|e| SwiftKitError::internal(e.to_string()))?;
+    connection
+        .call_method(
+            Some("org.freedesktop.FileManager1"),
+            "/org/freedesktop/FileManager1",
+            Some("org.freedesktop.FileManager1"),
+            "ShowItems",
+            &(uris, ""),
+        )
+        .map_err(|e| SwiftKitError::internal(e.to_string()))?;
+
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+fn reveal_files(_files: &[PathBuf]) -> Result<(), SwiftKitError> {
+    Err(SwiftKitError::internal("Unsupported OS"))
 }
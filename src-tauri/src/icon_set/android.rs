@@ -0,0 +1,63 @@
+use image::RgbaImage;
+
+use super::common::{padded, rounded, solid, IconFile, IconSetOptions};
+use crate::error::SwiftKitError;
+use crate::img_encoders::icon::render;
+
+// Density bucket and the launcher icon size (48dp) in pixels
+const DENSITIES: [(&str, u32); 5] = [
+    ("mdpi", 48),
+    ("hdpi", 72),
+    ("xhdpi", 96),
+    ("xxhdpi", 144),
+    ("xxxhdpi", 192),
+];
+
+// Adaptive icon layers are 108dp, only the inner 72dp is guaranteed to be visible
+const ADAPTIVE_SCALE: f32 = 108.0 / 48.0;
+const ADAPTIVE_SAFE_ZONE: f32 = 72.0 / 108.0;
+
+const ADAPTIVE_ICON_XML: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<adaptive-icon xmlns:android="http://schemas.android.com/apk/res/android">
+    <background android:drawable="@mipmap/ic_launcher_background" />
+    <foreground android:drawable="@mipmap/ic_launcher_foreground" />
+</adaptive-icon>
+"#;
+
+// Laid out like `app/src/main/res`
+pub fn files(square: &RgbaImage, options: &IconSetOptions) -> Result<Vec<IconFile>, SwiftKitError> {
+    let mut files = Vec::new();
+
+    for (density, size) in DENSITIES {
+        let folder = format!("res/mipmap-{}", density);
+        let adaptive = (size as f32 * ADAPTIVE_SCALE).round() as u32;
+
+        files.push(IconFile::png(
+            format!("{}/ic_launcher.png", folder),
+            &render(square, size),
+        )?);
+        files.push(IconFile::png(
+            format!("{}/ic_launcher_round.png", folder),
+            &rounded(square, size),
+        )?);
+        files.push(IconFile::png(
+            format!("{}/ic_launcher_foreground.png", folder),
+            &padded(square, adaptive, ADAPTIVE_SAFE_ZONE, None),
+        )?);
+        files.push(IconFile::png(
+            format!("{}/ic_launcher_background.png", folder),
+            &solid(adaptive, options.background),
+        )?);
+    }
+
+    for name in ["ic_launcher", "ic_launcher_round"] {
+        files.push(IconFile {
+            path: format!("res/mipmap-anydpi-v26/{}.xml", name),
+            bytes: ADAPTIVE_ICON_XML.as_bytes().to_vec(),
+        });
+    }
+
+    files.push(IconFile::png("playstore-icon.png", &render(square, 512))?);
+
+    Ok(files)
+}
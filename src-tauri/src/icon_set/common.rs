@@ -0,0 +1,85 @@
+use image::imageops;
+use image::{Rgba, RgbaImage};
+use serde::Deserialize;
+
+use crate::error::SwiftKitError;
+use crate::img_encoders::icon::{png, render};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IconPlatform {
+    Android,
+    Ios,
+    Pwa,
+}
+
+pub struct IconSetOptions {
+    pub background: Rgba<u8>,
+    pub app_name: String,
+}
+
+// A file of the set, `path` is relative to the set's root and uses forward slashes
+pub struct IconFile {
+    pub path: String,
+    pub bytes: Vec<u8>,
+}
+
+impl IconFile {
+    pub fn png(path: impl Into<String>, image: &RgbaImage) -> Result<Self, SwiftKitError> {
+        Ok(Self {
+            path: path.into(),
+            bytes: png(image)?,
+        })
+    }
+
+    pub fn json(path: impl Into<String>, value: &serde_json::Value) -> Result<Self, SwiftKitError> {
+        Ok(Self {
+            path: path.into(),
+            bytes: serde_json::to_vec_pretty(value)?,
+        })
+    }
+}
+
+// Scales the icon to `content` of the canvas and centers it, for safe zones of adaptive icons
+pub fn padded(
+    square: &RgbaImage,
+    size: u32,
+    content: f32,
+    background: Option<Rgba<u8>>,
+) -> RgbaImage {
+    let inner = ((size as f32 * content).round() as u32).clamp(1, size);
+    let icon = render(square, inner);
+
+    let mut canvas = RgbaImage::from_pixel(size, size, background.unwrap_or(Rgba([0, 0, 0, 0])));
+    let offset = i64::from((size - inner) / 2);
+    imageops::overlay(&mut canvas, &icon, offset, offset);
+    canvas
+}
+
+// App stores reject transparent icons, so they are put on the background color
+pub fn flattened(square: &RgbaImage, size: u32, background: Rgba<u8>) -> RgbaImage {
+    let mut canvas = RgbaImage::from_pixel(
+        size,
+        size,
+        Rgba([background[0], background[1], background[2], 255]),
+    );
+    imageops::overlay(&mut canvas, &render(square, size), 0, 0);
+    canvas
+}
+
+// Clears everything outside the inscribed circle, with one pixel of antialiasing
+pub fn rounded(square: &RgbaImage, size: u32) -> RgbaImage {
+    let mut icon = render(square, size);
+    let radius = size as f32 / 2.0;
+    for (x, y, pixel) in icon.enumerate_pixels_mut() {
+        let dx = x as f32 + 0.5 - radius;
+        let dy = y as f32 + 0.5 - radius;
+        let coverage = (radius - (dx * dx + dy * dy).sqrt()).clamp(0.0, 1.0);
+        pixel[3] = (pixel[3] as f32 * coverage).round() as u8;
+    }
+    icon
+}
+
+pub fn solid(size: u32, color: Rgba<u8>) -> RgbaImage {
+    RgbaImage::from_pixel(size, size, color)
+}
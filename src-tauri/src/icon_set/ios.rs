@@ -0,0 +1,67 @@
+use image::RgbaImage;
+use serde_json::{json, Value};
+
+use super::common::{flattened, IconFile, IconSetOptions};
+use crate::error::SwiftKitError;
+
+const FOLDER: &str = "AppIcon.appiconset";
+
+// Idiom, size in points and scale, as Xcode lists them for an iOS app icon
+const ICONS: [(&str, f32, u32); 18] = [
+    ("iphone", 20.0, 2),
+    ("iphone", 20.0, 3),
+    ("iphone", 29.0, 2),
+    ("iphone", 29.0, 3),
+    ("iphone", 40.0, 2),
+    ("iphone", 40.0, 3),
+    ("iphone", 60.0, 2),
+    ("iphone", 60.0, 3),
+    ("ipad", 20.0, 1),
+    ("ipad", 20.0, 2),
+    ("ipad", 29.0, 1),
+    ("ipad", 29.0, 2),
+    ("ipad", 40.0, 1),
+    ("ipad", 40.0, 2),
+    ("ipad", 76.0, 1),
+    ("ipad", 76.0, 2),
+    ("ipad", 83.5, 2),
+    ("ios-marketing", 1024.0, 1),
+];
+
+pub fn files(square: &RgbaImage, options: &IconSetOptions) -> Result<Vec<IconFile>, SwiftKitError> {
+    let mut files: Vec<IconFile> = Vec::new();
+    let mut images: Vec<Value> = Vec::with_capacity(ICONS.len());
+
+    for (idiom, points, scale) in ICONS {
+        // 83.5 is the only fractional size, "{}" prints whole points without decimals
+        let size = format!("{}x{}", points, points);
+        let filename = format!("Icon-App-{}@{}x.png", size, scale);
+        let pixels = (points * scale as f32).round() as u32;
+
+        // Sizes shared between idioms point at the same file
+        let path = format!("{}/{}", FOLDER, filename);
+        if !files.iter().any(|file| file.path == path) {
+            files.push(IconFile::png(
+                path,
+                &flattened(square, pixels, options.background),
+            )?);
+        }
+
+        images.push(json!({
+            "size": size,
+            "idiom": idiom,
+            "filename": filename,
+            "scale": format!("{}x", scale),
+        }));
+    }
+
+    files.push(IconFile::json(
+        format!("{}/Contents.json", FOLDER),
+        &json!({
+            "images": images,
+            "info": { "version": 1, "author": "xcode" },
+        }),
+    )?);
+
+    Ok(files)
+}
@@ -0,0 +1,4 @@
+pub mod android;
+pub mod common;
+pub mod ios;
+pub mod pwa;
@@ -0,0 +1,71 @@
+use image::RgbaImage;
+use serde_json::json;
+
+use super::common::{flattened, padded, IconFile, IconSetOptions};
+use crate::error::SwiftKitError;
+use crate::img_encoders::icon::{ico, render};
+
+const FAVICON_SIZES: [u32; 3] = [16, 32, 48];
+const MANIFEST_SIZES: [u32; 2] = [192, 512];
+
+// Maskable icons may be cropped to a circle of 80% of their size
+const MASKABLE_SAFE_ZONE: f32 = 0.8;
+
+pub fn files(square: &RgbaImage, options: &IconSetOptions) -> Result<Vec<IconFile>, SwiftKitError> {
+    let mut files = vec![IconFile {
+        path: "favicon.ico".to_string(),
+        bytes: ico(square, &FAVICON_SIZES)?,
+    }];
+
+    for size in [16, 32] {
+        files.push(IconFile::png(
+            format!("favicon-{}x{}.png", size, size),
+            &render(square, size),
+        )?);
+    }
+
+    // iOS fills transparent pixels of home screen icons with black
+    files.push(IconFile::png(
+        "apple-touch-icon.png",
+        &flattened(square, 180, options.background),
+    )?);
+
+    let mut icons = Vec::new();
+    for size in MANIFEST_SIZES {
+        let name = format!("icon-{}.png", size);
+        files.push(IconFile::png(name.clone(), &render(square, size))?);
+        icons.push(json!({
+            "src": name,
+            "sizes": format!("{}x{}", size, size),
+            "type": "image/png",
+        }));
+
+        let maskable = format!("icon-maskable-{}.png", size);
+        files.push(IconFile::png(
+            maskable.clone(),
+            &padded(square, size, MASKABLE_SAFE_ZONE, Some(options.background)),
+        )?);
+        icons.push(json!({
+            "src": maskable,
+            "sizes": format!("{}x{}", size, size),
+            "type": "image/png",
+            "purpose": "maskable",
+        }));
+    }
+
+    let [r, g, b, _] = options.background.0;
+    let color = format!("#{:02x}{:02x}{:02x}", r, g, b);
+    files.push(IconFile::json(
+        "manifest.webmanifest",
+        &json!({
+            "name": options.app_name,
+            "short_name": options.app_name,
+            "icons": icons,
+            "theme_color": color,
+            "background_color": color,
+            "display": "standalone",
+        }),
+    )?);
+
+    Ok(files)
+}
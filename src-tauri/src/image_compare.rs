@@ -0,0 +1,75 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tokio::task;
+
+use super::error::SwiftKitError;
+use super::img_encoders::encode::open_image;
+use super::img_metrics::compare::{align, compare, flatten};
+use super::img_metrics::heatmap::heatmap;
+
+#[derive(Debug, Serialize)]
+pub struct ImageComparison {
+    pub width: u32,
+    pub height: u32,
+    // The processed image was scaled to the original's size before comparing
+    pub resized: bool,
+    pub ssim: f64,
+    // None when the images are identical
+    pub psnr: Option<f64>,
+    pub max_difference: u8,
+    pub heatmap: String,
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn image_compare(
+    original_path: String,
+    processed_path: String,
+    heatmap_path: Option<String>,
+) -> Result<ImageComparison, SwiftKitError> {
+    let original_path = PathBuf::from(original_path);
+    let processed_path = PathBuf::from(processed_path);
+    for path in [&original_path, &processed_path] {
+        if !path.is_file() {
+            return Err(SwiftKitError::validation(format!(
+                "Invalid image path: {}",
+                path.display()
+            )));
+        }
+    }
+
+    // Written next to the processed image unless a path is given
+    let heatmap_path = match heatmap_path.filter(|path| !path.is_empty()) {
+        Some(path) => PathBuf::from(path),
+        None => processed_path
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .join(
+                processed_path
+                    .file_stem()
+                    .ok_or_else(|| SwiftKitError::validation("Invalid file name"))?
+                    .to_string_lossy()
+                    .to_string()
+                    + ".diff.png",
+            ),
+    };
+
+    task::spawn_blocking(move || {
+        let original = open_image(&original_path)?;
+        let (processed, resized) = align(&original, open_image(&processed_path)?)?;
+        let (original, processed) = (flatten(&original), flatten(&processed));
+
+        let comparison = compare(&original, &processed);
+        heatmap(&original, &processed).save(&heatmap_path)?;
+
+        Ok(ImageComparison {
+            width: original.width(),
+            height: original.height(),
+            resized,
+            ssim: comparison.ssim,
+            psnr: comparison.psnr,
+            max_difference: comparison.max_difference,
+            heatmap: heatmap_path.to_string_lossy().to_string(),
+        })
+    })
+    .await?
+}
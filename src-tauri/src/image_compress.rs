@@ -1,35 +1,111 @@
-use std::path::Path;
+use image::RgbImage;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+use tokio::task;
 
+use super::error::SwiftKitError;
+use super::img_compressors::backend::CompressionBackend;
+use super::img_compressors::builtin;
 use super::img_compressors::jpeg;
 use super::img_compressors::png;
 use super::img_compressors::webp;
+use super::img_encoders::encode::{load_image, open_image};
+use super::img_metrics::compare::{flatten, ssim};
+use super::settings::service::current;
+use super::tools::registry::Tool;
+use super::tools::resolver::resolve_tool_with;
+
+#[derive(Debug, Clone, Default)]
+pub struct CompressOptions {
+    pub quality: Option<u8>,
+    pub backend: CompressionBackend,
+    // Picks the highest quality whose output fits, `quality` is then the upper bound
+    pub target_size_kb: Option<u64>,
+    // Keeps the compressed file even when it came out larger than the original
+    pub force: bool,
+    // The quality is raised until the result's SSIM reaches this, or the original is kept
+    pub min_ssim: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CompressionReport {
+    pub output: String,
+    pub original_size: u64,
+    pub compressed_size: u64,
+    // compressed_size / original_size
+    pub ratio: f64,
+    pub quality: u8,
+    // SSIM of the written file, only measured when `min_ssim` is set
+    pub ssim: Option<f64>,
+    // The compressed file was larger or below `min_ssim`, so the original bytes were kept
+    pub kept_original: bool,
+}
 
 #[tauri::command(rename_all = "snake_case")]
+#[allow(clippy::too_many_arguments)]
 pub async fn image_compress(
+    handle: AppHandle,
     img_path: String,
     quality: Option<u8>,
     output_folder: Option<String>,
-) -> Result<(), String> {
-    let input_path = Path::new(&img_path);
+    backend: Option<CompressionBackend>,
+    target_size_kb: Option<u64>,
+    force: Option<bool>,
+    min_ssim: Option<f64>,
+) -> Result<CompressionReport, SwiftKitError> {
+    let pool = handle.try_state::<SqlitePool>();
+    let settings = current(&handle).await;
+    let output_folder = output_folder
+        .filter(|folder| !folder.is_empty())
+        .or(settings.image_output_folder);
+    let options = CompressOptions {
+        quality: quality.or(settings.image_quality),
+        backend: backend.unwrap_or(settings.compression_backend),
+        target_size_kb,
+        force: force.unwrap_or(false),
+        min_ssim,
+    };
+    compress(
+        pool.as_deref(),
+        &img_path,
+        output_folder.as_deref(),
+        &options,
+    )
+    .await
+}
+
+pub async fn compress(
+    pool: Option<&SqlitePool>,
+    img_path: &str,
+    output_folder: Option<&str>,
+    options: &CompressOptions,
+) -> Result<CompressionReport, SwiftKitError> {
+    let input_path = Path::new(img_path);
     if !input_path.exists() || !input_path.is_file() {
-        return Err("Invalid image path".into());
+        return Err(SwiftKitError::validation("Invalid image path"));
     }
-    let output_folder_pathbuf = if output_folder.as_deref().unwrap_or("").is_empty() {
-        input_path.parent().unwrap_or_else(|| Path::new("."))
-    } else {
-        Path::new(output_folder.as_deref().unwrap())
+    let output_folder_pathbuf = match output_folder.filter(|folder| !folder.is_empty()) {
+        Some(folder) => Path::new(folder),
+        None => input_path.parent().unwrap_or_else(|| Path::new(".")),
     };
 
     // Ensure the output directory exists and is writable
     if !output_folder_pathbuf.exists() {
-        return Err("Output path could not be resolved.".to_string());
+        return Err(SwiftKitError::validation(
+            "Output path could not be resolved.",
+        ));
     }
 
     // Create the output file path
     let output_path = Path::new(&output_folder_pathbuf).join(
         input_path
             .file_stem()
-            .ok_or("Invalid file name")?
+            .ok_or_else(|| SwiftKitError::validation("Invalid file name"))?
             .to_string_lossy()
             .to_string()
             + "."
@@ -39,18 +115,289 @@ pub async fn image_compress(
                 .unwrap_or(""),
     );
 
-    let quality = quality.unwrap_or(75);
+    let quality = options.quality.unwrap_or(75);
+    if !(1..=100).contains(&quality) {
+        return Err(SwiftKitError::validation(
+            "Image quality must be between 1 and 100.",
+        ));
+    }
+    if options.target_size_kb == Some(0) {
+        return Err(SwiftKitError::validation(
+            "Target size must be larger than 0 KB.",
+        ));
+    }
+    if options
+        .min_ssim
+        .is_some_and(|min_ssim| !(min_ssim > 0.0 && min_ssim <= 1.0))
+    {
+        return Err(SwiftKitError::validation(
+            "Minimum SSIM must be between 0 and 1.",
+        ));
+    }
 
     let extension = input_path
         .extension()
         .and_then(|ext| ext.to_str())
         .map(|ext| ext.to_lowercase())
-        .ok_or_else(|| "Unable to determine file extension".to_string())?;
+        .ok_or_else(|| SwiftKitError::validation("Unable to determine file extension"))?;
+
+    let tool = match extension.as_str() {
+        "png" => Tool::Pngquant,
+        "jpg" | "jpeg" => Tool::Jpegoptim,
+        "webp" => Tool::Cwebp,
+        _ => return Err(SwiftKitError::validation("Unsupported file format")),
+    };
+    let binary = match options.backend {
+        CompressionBackend::Builtin => None,
+        CompressionBackend::External => Some(resolve_tool_with(pool, tool).await?),
+        CompressionBackend::Auto => resolve_tool_with(pool, tool).await.ok(),
+    };
+    let compressor = Compressor { tool, binary };
+
+    let original_size = fs::metadata(input_path)
+        .map_err(|e| SwiftKitError::io(e, input_path))?
+        .len();
+
+    // Every attempt is written to a hidden folder next to the output, so the
+    // original is only replaced once the result is known to be worth keeping
+    let staging = output_folder_pathbuf.join(format!(".swift-kit-{}", uuid::Uuid::new_v4()));
+    fs::create_dir(&staging).map_err(|e| SwiftKitError::io(e, &staging))?;
+    let attempt = best_attempt(&compressor, input_path, &staging, quality, options).await;
+    let _ = fs::remove_dir_all(&staging);
+    let Attempt {
+        bytes,
+        quality,
+        ssim,
+        meets_floor,
+    } = attempt?;
+
+    let kept_original = !meets_floor || (bytes.len() as u64 > original_size && !options.force);
+    if !kept_original {
+        fs::write(&output_path, &bytes).map_err(|e| SwiftKitError::io(e, &output_path))?;
+    } else if !is_same_file(input_path, &output_path) {
+        fs::copy(input_path, &output_path).map_err(|e| SwiftKitError::io(e, &output_path))?;
+    }
+
+    let compressed_size = if kept_original {
+        original_size
+    } else {
+        bytes.len() as u64
+    };
+    Ok(CompressionReport {
+        output: output_path.to_string_lossy().to_string(),
+        original_size,
+        compressed_size,
+        ratio: if original_size == 0 {
+            1.0
+        } else {
+            compressed_size as f64 / original_size as f64
+        },
+        quality,
+        ssim: ssim.filter(|_| !kept_original),
+        kept_original,
+    })
+}
+
+struct Attempt {
+    bytes: Vec<u8>,
+    quality: u8,
+    ssim: Option<f64>,
+    meets_floor: bool,
+}
+
+async fn best_attempt(
+    compressor: &Compressor,
+    input_path: &Path,
+    staging: &Path,
+    quality: u8,
+    options: &CompressOptions,
+) -> Result<Attempt, SwiftKitError> {
+    let (bytes, quality) = match options.target_size_kb {
+        Some(target_size_kb) => {
+            search(
+                compressor,
+                input_path,
+                staging,
+                quality,
+                target_size_kb * 1024,
+            )
+            .await?
+        }
+        None => (
+            probe(compressor, input_path, staging, quality).await?,
+            quality,
+        ),
+    };
+
+    let Some(min_ssim) = options.min_ssim else {
+        return Ok(Attempt {
+            bytes,
+            quality,
+            ssim: None,
+            meets_floor: true,
+        });
+    };
+
+    let input = input_path.to_path_buf();
+    let original =
+        Arc::new(task::spawn_blocking(move || open_image(&input).map(|img| flatten(&img))).await??);
+    let score = similarity(&original, &bytes).await?;
+    if score >= min_ssim {
+        return Ok(Attempt {
+            bytes,
+            quality,
+            ssim: Some(score),
+            meets_floor: true,
+        });
+    }
+
+    // Lowest higher quality that reaches the floor, the size target gives way to it
+    let (mut low, mut high) = (quality + 1, 100);
+    let mut raised = None;
+    while low <= high {
+        let quality = low + (high - low) / 2;
+        let bytes = probe(compressor, input_path, staging, quality).await?;
+        let score = similarity(&original, &bytes).await?;
+        if score >= min_ssim {
+            raised = Some(Attempt {
+                bytes,
+                quality,
+                ssim: Some(score),
+                meets_floor: true,
+            });
+            high = quality - 1;
+        } else {
+            low = quality + 1;
+        }
+    }
+
+    Ok(raised.unwrap_or(Attempt {
+        bytes,
+        quality,
+        ssim: Some(score),
+        meets_floor: false,
+    }))
+}
+
+async fn similarity(original: &Arc<RgbImage>, bytes: &[u8]) -> Result<f64, SwiftKitError> {
+    let original = Arc::clone(original);
+    let bytes = bytes.to_vec();
+    task::spawn_blocking(move || {
+        let processed = flatten(&load_image(&bytes)?.0);
+        if processed.dimensions() != original.dimensions() {
+            return Err(SwiftKitError::internal(
+                "The compressed image has a different size.",
+            ));
+        }
+        Ok(ssim(&original, &processed))
+    })
+    .await?
+}
+
+// An external tool when one was resolved, the built-in encoders otherwise
+struct Compressor {
+    tool: Tool,
+    binary: Option<PathBuf>,
+}
+
+impl Compressor {
+    async fn run(
+        &self,
+        input_path: &Path,
+        quality: u8,
+        output_path: &Path,
+    ) -> Result<(), SwiftKitError> {
+        match (&self.binary, self.tool) {
+            (Some(pngquant), Tool::Pngquant) => {
+                png::compress(pngquant, input_path, quality, output_path).await
+            }
+            (Some(jpegoptim), Tool::Jpegoptim) => {
+                jpeg::compress(jpegoptim, input_path, quality, output_path).await
+            }
+            (Some(cwebp), _) => webp::compress(cwebp, input_path, quality, output_path).await,
+            (None, _) => {
+                let input_path = input_path.to_path_buf();
+                let output_path = output_path.to_path_buf();
+                task::spawn_blocking(move || builtin::compress(&input_path, quality, &output_path))
+                    .await?
+            }
+        }
+    }
+}
+
+// Compresses into `staging` under the input's file name, which is what jpegoptim writes.
+// The input's bytes stand in when the compressor wrote nothing.
+async fn probe(
+    compressor: &Compressor,
+    input_path: &Path,
+    staging: &Path,
+    quality: u8,
+) -> Result<Vec<u8>, SwiftKitError> {
+    let file_name = input_path
+        .file_name()
+        .ok_or_else(|| SwiftKitError::validation("Invalid file name"))?;
+    let probe_path = staging.join(file_name);
+    // A previous attempt's file would otherwise be read back under this quality
+    if let Err(e) = fs::remove_file(&probe_path) {
+        if e.kind() != ErrorKind::NotFound {
+            return Err(SwiftKitError::io(e, &probe_path));
+        }
+    }
+    compressor.run(input_path, quality, &probe_path).await?;
+
+    match fs::read(&probe_path) {
+        Ok(bytes) => Ok(bytes),
+        // jpegoptim writes nothing for files it can't make smaller,
+        // pngquant nothing when the quality can't be reached
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            fs::read(input_path).map_err(|e| SwiftKitError::io(e, input_path))
+        }
+        Err(e) => Err(SwiftKitError::io(e, &probe_path)),
+    }
+}
+
+// Binary search for the highest quality that fits in `target_size` bytes.
+// When even the lowest quality doesn't fit, the smallest attempt is used.
+async fn search(
+    compressor: &Compressor,
+    input_path: &Path,
+    staging: &Path,
+    max_quality: u8,
+    target_size: u64,
+) -> Result<(Vec<u8>, u8), SwiftKitError> {
+    let (mut low, mut high) = (1, max_quality);
+    let mut fitting: Option<(Vec<u8>, u8)> = None;
+    let mut smallest: Option<(Vec<u8>, u8)> = None;
+
+    while low <= high {
+        let quality = low + (high - low) / 2;
+        let bytes = probe(compressor, input_path, staging, quality).await?;
+
+        if bytes.len() as u64 <= target_size {
+            fitting = Some((bytes, quality));
+            low = quality + 1;
+        } else {
+            if smallest
+                .as_ref()
+                .is_none_or(|(smallest, _)| bytes.len() < smallest.len())
+            {
+                smallest = Some((bytes, quality));
+            }
+            if quality == 1 {
+                break;
+            }
+            high = quality - 1;
+        }
+    }
+
+    fitting
+        .or(smallest)
+        .ok_or_else(|| SwiftKitError::internal("No compression attempt was made"))
+}
 
-    match extension.as_str() {
-        "png" => png::compress(input_path, quality, &output_path).await,
-        "jpg" | "jpeg" => jpeg::compress(input_path, quality, &output_path).await,
-        "webp" => webp::compress(input_path, quality, &output_path).await,
-        _ => Err("Unsupported file format".to_string()),
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    match (dunce::canonicalize(a), dunce::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
     }
 }
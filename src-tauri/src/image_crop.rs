@@ -1,88 +1,57 @@
-use image::ImageFormat;
-use serde::{Deserialize, Serialize};
-use std::fs::File;
+use std::fs;
 use std::path::Path;
+use tokio::task;
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct CropDetails {
-    height: u32,
-    width: u32,
-    x: f32,
-    y: f32,
-}
+use super::error::SwiftKitError;
+use super::img_encoders::encode::{encode_with_metadata, open_image_with_metadata, OutputFormat};
+use super::img_encoders::options::EncodeOptions;
+use super::img_metadata::mode::MetadataMode;
+use super::img_transforms::crop::{crop, CropDetails, CropOptions};
+use super::utils::resolve_output_path::resolve_output_path;
 
 #[tauri::command(rename_all = "snake_case")]
 pub async fn image_crop(
     img_path: String,
-    crop_details: CropDetails,
+    crop_details: Option<CropDetails>,
     output_folder: Option<String>,
     file_name: Option<String>,
-) -> Result<String, String> {
+    options: Option<CropOptions>,
+    metadata: Option<MetadataMode>,
+) -> Result<String, SwiftKitError> {
     let input_path = Path::new(&img_path);
     if !input_path.exists() || !input_path.is_file() {
-        return Err("Invalid image path".into());
+        return Err(SwiftKitError::validation("Invalid image path"));
     }
-
-    let img = image::open(&input_path).map_err(|e| e.to_string())?;
-
-    // Ensure crop dimensions do not exceed image dimensions
-    if crop_details.x + crop_details.width as f32 > img.width() as f32
-        || crop_details.y + crop_details.height as f32 > img.height() as f32
-    {
-        return Err("Crop dimensions exceed image dimensions".into());
+    if crop_details.is_none() && options.is_none() {
+        return Err(SwiftKitError::validation("Nothing to crop"));
     }
-
-    // Perform cropping
-    let cropped_img = img.crop_imm(
-        crop_details.x as u32,
-        crop_details.y as u32,
-        crop_details.width,
-        crop_details.height,
-    );
-
-    let output_folder = if output_folder.as_deref().unwrap_or("").is_empty() {
-        input_path
-            .parent()
-            .unwrap_or_else(|| Path::new("."))
-            .to_string_lossy()
-            .to_string()
-    } else {
-        output_folder.unwrap()
-    };
-
-    let invalid_chars = ['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
-    if let Some(ref name) = file_name {
-        if name.is_empty() || name.chars().any(|c| invalid_chars.contains(&c)) {
-            return Err("Invalid file name".into());
-        }
-    }
-
-    let output_file_name = file_name
-        .filter(|name| !name.is_empty())
-        .unwrap_or_else(|| {
-            input_path
-                .file_stem()
-                .unwrap_or_default()
-                .to_string_lossy()
-                .to_string()
-        });
-
-    let output_path = Path::new(&output_folder).join(format!(
-        "{}.{}",
-        output_file_name,
-        input_path
-            .extension()
-            .and_then(|s| s.to_str())
-            .unwrap_or("png")
-    ));
-
-    let mut output_file = File::create(&output_path).map_err(|e| e.to_string())?;
-    cropped_img
-        .write_to(
-            &mut output_file,
-            ImageFormat::from_path(&output_path).map_err(|e| e.to_string())?,
+    let options = options.unwrap_or_default();
+    options.validate()?;
+
+    let output_path = resolve_output_path(
+        input_path,
+        output_folder.as_deref().map(Path::new),
+        file_name.as_deref(),
+        None,
+    )?;
+    let output_format = OutputFormat::from_path(&output_path)
+        .ok_or_else(|| SwiftKitError::validation("Unsupported file format"))?;
+
+    let input_path = input_path.to_path_buf();
+    let bytes = task::spawn_blocking(move || {
+        let (img, source) = open_image_with_metadata(&input_path)?;
+        let cropped = crop(img, crop_details.as_ref(), &options)?;
+        encode_with_metadata(
+            &cropped,
+            output_format,
+            &EncodeOptions::default(),
+            &source,
+            metadata.unwrap_or_default(),
         )
-        .map_err(|e| e.to_string())?;
+    })
+    .await??;
+
+    fs::write(&output_path, bytes).map_err(|e| SwiftKitError::io(e, &output_path))?;
 
     Ok(output_path.to_string_lossy().to_string())
 }
@@ -0,0 +1,160 @@
+use image::ImageReader;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+use tokio::task;
+
+use super::error::SwiftKitError;
+use super::img_metadata::container::RawMetadata;
+use super::img_metadata::exif::{self, Field, Ifd};
+
+#[derive(Debug, Serialize)]
+pub struct GpsPosition {
+    pub latitude: f64,
+    pub longitude: f64,
+    // Meters, negative below sea level
+    pub altitude: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImageMetadata {
+    pub format: Option<String>,
+    // After the orientation is applied, as the image is displayed
+    pub width: u32,
+    pub height: u32,
+    pub orientation: Option<u8>,
+    pub make: Option<String>,
+    pub model: Option<String>,
+    pub lens_model: Option<String>,
+    pub software: Option<String>,
+    pub artist: Option<String>,
+    pub copyright: Option<String>,
+    pub date_taken: Option<String>,
+    // e.g. 1/250
+    pub exposure_time: Option<String>,
+    pub f_number: Option<f64>,
+    pub iso: Option<u32>,
+    // Millimeters
+    pub focal_length: Option<f64>,
+    pub gps: Option<GpsPosition>,
+    // Every EXIF field by name, unknown tags as <directory>.0x<tag>
+    pub exif: BTreeMap<String, String>,
+    pub xmp: Option<String>,
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn image_metadata(img_path: String) -> Result<ImageMetadata, SwiftKitError> {
+    let input_path = Path::new(&img_path);
+    if !input_path.exists() || !input_path.is_file() {
+        return Err(SwiftKitError::validation("Invalid image path"));
+    }
+
+    let input_path = input_path.to_path_buf();
+    task::spawn_blocking(move || {
+        let bytes = fs::read(&input_path).map_err(|e| SwiftKitError::io(e, &input_path))?;
+        read(&bytes)
+    })
+    .await?
+}
+
+fn read(bytes: &[u8]) -> Result<ImageMetadata, SwiftKitError> {
+    let reader = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(|e| SwiftKitError::io(e, "<memory>"))?;
+    let format = reader
+        .format()
+        .and_then(|format| format.extensions_str().first())
+        .map(|extension| extension.to_string());
+    let (width, height) = reader.into_dimensions()?;
+
+    let raw = RawMetadata::read(bytes);
+    let orientation = raw.orientation();
+    // 5 to 8 swap the sides
+    let (width, height) = match orientation {
+        Some(5..=8) => (height, width),
+        _ => (width, height),
+    };
+    let fields = raw.exif.as_deref().map(exif::fields).unwrap_or_default();
+
+    let find = |ifd: Ifd, tag: u16| {
+        fields
+            .iter()
+            .find(|field| field.ifd == ifd && field.tag == tag)
+    };
+    let text = |ifd: Ifd, tag: u16| {
+        find(ifd, tag)
+            .and_then(|field| field.value.as_str())
+            .filter(|text| !text.is_empty())
+            .map(str::to_string)
+    };
+    let number = |ifd: Ifd, tag: u16| find(ifd, tag).and_then(|field| field.value.as_f64());
+
+    Ok(ImageMetadata {
+        format,
+        width,
+        height,
+        orientation,
+        make: text(Ifd::Primary, 0x010F),
+        model: text(Ifd::Primary, 0x0110),
+        lens_model: text(Ifd::Exif, 0xA434),
+        software: text(Ifd::Primary, 0x0131),
+        artist: text(Ifd::Primary, 0x013B),
+        copyright: text(Ifd::Primary, 0x8298),
+        date_taken: text(Ifd::Exif, 0x9003).or_else(|| text(Ifd::Primary, 0x0132)),
+        exposure_time: find(Ifd::Exif, 0x829A).map(|field| field.value.display()),
+        f_number: number(Ifd::Exif, 0x829D),
+        iso: number(Ifd::Exif, 0x8827).map(|iso| iso as u32),
+        focal_length: number(Ifd::Exif, 0x920A),
+        gps: gps(&fields),
+        exif: fields
+            .iter()
+            .map(|field| (key(field), field.value.display()))
+            .collect(),
+        xmp: raw.xmp.map(|xmp| {
+            String::from_utf8_lossy(&xmp)
+                .trim_end_matches('\0')
+                .to_string()
+        }),
+    })
+}
+
+fn key(field: &Field) -> String {
+    match field.name() {
+        Some(name) => name.to_string(),
+        None => format!("{:?}.0x{:04X}", field.ifd, field.tag),
+    }
+}
+
+// Degrees, minutes and seconds with N/S and E/W references to signed decimal degrees
+fn gps(fields: &[Field]) -> Option<GpsPosition> {
+    let find = |tag: u16| {
+        fields
+            .iter()
+            .find(|field| field.ifd == Ifd::Gps && field.tag == tag)
+            .map(|field| &field.value)
+    };
+    let coordinate = |reference: u16, value: u16, negative: &str| {
+        let parts = find(value)?.numbers();
+        let degrees = parts.first()?
+            + parts.get(1).unwrap_or(&0.0) / 60.0
+            + parts.get(2).unwrap_or(&0.0) / 3600.0;
+        let sign = match find(reference).and_then(|value| value.as_str()) {
+            Some(reference) if reference.eq_ignore_ascii_case(negative) => -1.0,
+            _ => 1.0,
+        };
+        Some(sign * degrees)
+    };
+
+    // Reference 1 is below sea level
+    let below_sea_level = find(0x0005).and_then(|value| value.as_f64()) == Some(1.0);
+    let altitude = find(0x0006)
+        .and_then(|value| value.as_f64())
+        .map(|altitude| if below_sea_level { -altitude } else { altitude });
+    Some(GpsPosition {
+        latitude: coordinate(0x0001, 0x0002, "S")?,
+        longitude: coordinate(0x0003, 0x0004, "W")?,
+        altitude,
+    })
+}
@@ -0,0 +1,62 @@
+use serde::Serialize;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tauri::{AppHandle, Emitter};
+use tokio::task;
+
+use super::error::SwiftKitError;
+use super::img_pipeline::batch::{self, FileResult};
+use super::img_pipeline::operation::{Operation, Pipeline};
+
+#[derive(Clone, Serialize)]
+struct PipelineProgress<'a> {
+    completed: usize,
+    total: usize,
+    file: &'a FileResult,
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn image_pipeline(
+    handle: AppHandle,
+    img_paths: Vec<String>,
+    operations: Vec<Operation>,
+    output_folder: Option<String>,
+    threads: Option<usize>,
+) -> Result<Vec<FileResult>, SwiftKitError> {
+    if img_paths.is_empty() {
+        return Err(SwiftKitError::validation("No files were provided."));
+    }
+    let pipeline = Pipeline::new(operations)?;
+
+    let output_folder = output_folder.filter(|folder| !folder.is_empty());
+    if output_folder
+        .as_deref()
+        .is_some_and(|folder| !Path::new(folder).is_dir())
+    {
+        return Err(SwiftKitError::validation(
+            "Output path could not be resolved.",
+        ));
+    }
+
+    task::spawn_blocking(move || {
+        let total = img_paths.len();
+        let completed = AtomicUsize::new(0);
+        let output_folder = output_folder.as_deref().map(Path::new);
+        batch::run(
+            &img_paths,
+            threads.unwrap_or(0),
+            |path| batch::process(path, &pipeline, output_folder),
+            |file| {
+                let progress = PipelineProgress {
+                    completed: completed.fetch_add(1, Ordering::Relaxed) + 1,
+                    total,
+                    file,
+                };
+                handle
+                    .emit("image-pipeline-progress", progress)
+                    .unwrap_or_default();
+            },
+        )
+    })
+    .await?
+}
@@ -0,0 +1,97 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tauri::{AppHandle, Emitter};
+use tokio::task;
+
+use super::error::SwiftKitError;
+use super::img_encoders::encode::{encode_with_metadata, open_image_with_metadata, OutputFormat};
+use super::img_encoders::options::EncodeOptions;
+use super::img_metadata::mode::MetadataMode;
+use super::img_pipeline::batch::{self, FileResult};
+use super::img_transforms::watermark::{Watermark, WatermarkOptions};
+use super::utils::resolve_output_path::resolve_output_path;
+
+#[derive(Clone, Serialize)]
+struct WatermarkProgress<'a> {
+    completed: usize,
+    total: usize,
+    file: &'a FileResult,
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn image_watermark(
+    handle: AppHandle,
+    img_paths: Vec<String>,
+    options: WatermarkOptions,
+    output_folder: Option<String>,
+    metadata: Option<MetadataMode>,
+    threads: Option<usize>,
+) -> Result<Vec<FileResult>, SwiftKitError> {
+    if img_paths.is_empty() {
+        return Err(SwiftKitError::validation("No files were provided."));
+    }
+
+    let output_folder = output_folder.filter(|folder| !folder.is_empty());
+    if output_folder
+        .as_deref()
+        .is_some_and(|folder| !Path::new(folder).is_dir())
+    {
+        return Err(SwiftKitError::validation(
+            "Output path could not be resolved.",
+        ));
+    }
+
+    task::spawn_blocking(move || {
+        // The font is rendered or the logo decoded once for the whole batch
+        let watermark = Watermark::new(&options)?;
+        let mode = metadata.unwrap_or_default();
+        let output_folder = output_folder.as_deref().map(Path::new);
+        let total = img_paths.len();
+        let completed = AtomicUsize::new(0);
+        batch::run(
+            &img_paths,
+            threads.unwrap_or(0),
+            |path| stamp(path, &watermark, output_folder, mode),
+            |file| {
+                let progress = WatermarkProgress {
+                    completed: completed.fetch_add(1, Ordering::Relaxed) + 1,
+                    total,
+                    file,
+                };
+                handle
+                    .emit("image-watermark-progress", progress)
+                    .unwrap_or_default();
+            },
+        )
+    })
+    .await?
+}
+
+fn stamp(
+    input_path: &Path,
+    watermark: &Watermark,
+    output_folder: Option<&Path>,
+    mode: MetadataMode,
+) -> Result<PathBuf, SwiftKitError> {
+    if !input_path.is_file() {
+        return Err(SwiftKitError::validation("Invalid image path"));
+    }
+
+    let extension = input_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase())
+        .unwrap_or_default();
+    let format = OutputFormat::from_extension(&extension)
+        .ok_or_else(|| SwiftKitError::validation("Unsupported file format"))?;
+    let output_path = resolve_output_path(input_path, output_folder, None, Some(&extension))?;
+
+    let (img, metadata) = open_image_with_metadata(input_path)?;
+    let marked = watermark.apply(img);
+    let bytes = encode_with_metadata(&marked, format, &EncodeOptions::default(), &metadata, mode)?;
+    fs::write(&output_path, bytes).map_err(|e| SwiftKitError::io(e, &output_path))?;
+
+    Ok(output_path)
+}
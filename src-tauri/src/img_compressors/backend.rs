@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+// Which compressor handles a file, `Auto` prefers the external tool when it is installed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompressionBackend {
+    #[default]
+    Auto,
+    External,
+    Builtin,
+}
+
+impl CompressionBackend {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CompressionBackend::Auto => "auto",
+            CompressionBackend::External => "external",
+            CompressionBackend::Builtin => "builtin",
+        }
+    }
+}
+
+impl FromStr for CompressionBackend {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "auto" => Ok(CompressionBackend::Auto),
+            "external" => Ok(CompressionBackend::External),
+            "builtin" => Ok(CompressionBackend::Builtin),
+            _ => Err(()),
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use std::fs;
+use std::path::Path;
+
+use crate::error::SwiftKitError;
+use crate::img_encoders::encode::{open_image, OutputFormat};
+use crate::img_encoders::{jpeg, png, webp};
+
+// In-process counterpart of pngquant, jpegoptim and cwebp, so compression works
+// without them. A PNG at quality 100 is only optimized losslessly.
+pub fn compress(input_path: &Path, quality: u8, output_path: &Path) -> Result<(), SwiftKitError> {
+    let format = input_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(OutputFormat::from_extension);
+    let img = open_image(input_path)?;
+
+    let bytes = match format {
+        Some(OutputFormat::Png) if quality >= 100 => png::optimize(&img)?,
+        Some(OutputFormat::Png) => png::quantize(&img, quality)?,
+        Some(OutputFormat::Jpeg) => jpeg::encode(&img, quality, true)?,
+        Some(OutputFormat::WebP) => webp::encode(&img, Some(quality))?,
+        _ => return Err(SwiftKitError::validation("Unsupported file format")),
+    };
+
+    fs::write(output_path, bytes).map_err(|e| SwiftKitError::io(e, output_path))
+}
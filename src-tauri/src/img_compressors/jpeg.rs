@@ -1,55 +1,42 @@
-use std::fs;
-use std::path::{Path, PathBuf};
-use std::{os::windows::process::CommandExt, process::Command};
-
-pub async fn compress(input_path: &Path, quality: u8, output_path: &Path) -> Result<(), String> {
-    let is_overwriting = input_path.parent() == Some(output_path);
-
-    // Handle overwriting case
-    let temp_path: Option<PathBuf> = if is_overwriting {
-        let temp_path = input_path.with_extension("temp");
-        fs::copy(input_path, &temp_path)
-            .map_err(|e| format!("Failed to copy file to temp: {}", e))?;
-        Some(temp_path)
-    } else {
-        None
-    };
-
-    // If we are overwriting, use the temporary file for optimization
-    let file_to_compress = temp_path.as_ref().map_or(input_path, |v| v);
-
-    let mut jpegoptim_command = Command::new("jpegoptim");
+use std::path::Path;
+
+use crate::error::SwiftKitError;
+use crate::utils::hidden_command::hidden_command;
+
+// jpegoptim writes into a folder under the input's file name, `output_path` must have
+// that name. `image_compress` always points it at its staging folder, never the input.
+pub async fn compress(
+    jpegoptim: &Path,
+    input_path: &Path,
+    quality: u8,
+    output_path: &Path,
+) -> Result<(), SwiftKitError> {
+    let output_folder = output_path.parent().unwrap_or(Path::new("."));
+    let mut jpegoptim_command = hidden_command(jpegoptim);
     jpegoptim_command
-        .creation_flags(0x08000000)
         .arg(format!("--max={}", quality))
         .arg("--dest")
-        .arg(output_path.parent().unwrap_or(Path::new(".")))
+        .arg(output_folder)
         .arg("--overwrite")
-        .arg(file_to_compress);
+        .arg(input_path);
 
-    let output = jpegoptim_command
-        .output()
-        .map_err(|e| format!("Failed to execute jpegoptim: {}", e))?;
+    let output = jpegoptim_command.output().map_err(|e| {
+        SwiftKitError::tool_failed("jpegoptim", format!("Failed to execute: {}", e), None)
+    })?;
 
     if !output.status.success() {
-        return Err(format!(
-            "jpegoptim failed with error:\n\
-            Command: jpegoptim --max={} --dest {} {}\n\
-            Stderr:\n{}\n\
-            Stdout:\n{}",
-            quality,
-            output_path.parent().unwrap_or(Path::new(".")).display(),
-            input_path.display(),
-            String::from_utf8_lossy(&output.stderr),
-            String::from_utf8_lossy(&output.stdout)
+        return Err(SwiftKitError::tool_failed(
+            "jpegoptim",
+            format!(
+                "exited with {} running jpegoptim --max={} --dest {} {}",
+                output.status,
+                quality,
+                output_folder.display(),
+                input_path.display()
+            ),
+            Some(String::from_utf8_lossy(&output.stderr).to_string()),
         ));
     }
 
-    // Handle renaming the .temp file back to original if overwriting
-    if let Some(temp_path) = temp_path {
-        fs::rename(&temp_path, input_path)
-            .map_err(|e| format!("Failed to rename temp file: {}", e))?;
-    }
-
     Ok(())
 }
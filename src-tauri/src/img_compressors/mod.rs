@@ -1,3 +1,5 @@
+pub mod backend;
+pub mod builtin;
 pub mod jpeg;
 pub mod png;
 pub mod webp;
@@ -1,11 +1,21 @@
 use std::path::Path;
-use std::{os::windows::process::CommandExt, process::Command};
 
-pub async fn compress(input_path: &Path, quality: u8, output_path: &Path) -> Result<(), String> {
+use crate::error::SwiftKitError;
+use crate::utils::hidden_command::hidden_command;
+
+// pngquant exits with this when the result would be below the minimum quality
+const QUALITY_TOO_LOW: i32 = 99;
+
+// Nothing is written when `quality` can't be reached, like jpegoptim for files it can't shrink
+pub async fn compress(
+    pngquant: &Path,
+    input_path: &Path,
+    quality: u8,
+    output_path: &Path,
+) -> Result<(), SwiftKitError> {
     let quality_argument = format!("{}-{}", quality, quality);
-    let mut pngquant_command = Command::new("pngquant");
+    let mut pngquant_command = hidden_command(pngquant);
     pngquant_command
-        .creation_flags(0x08000000)
         .arg("--quality")
         .arg(&quality_argument)
         .arg("--output")
@@ -13,21 +23,25 @@ pub async fn compress(input_path: &Path, quality: u8, output_path: &Path) -> Res
         .arg("--force")
         .arg(input_path);
 
-    let output = pngquant_command
-        .output()
-        .map_err(|e| format!("Failed to execute sidecar: {}", e))?;
+    let output = pngquant_command.output().map_err(|e| {
+        SwiftKitError::tool_failed("pngquant", format!("Failed to execute: {}", e), None)
+    })?;
+
+    if output.status.code() == Some(QUALITY_TOO_LOW) {
+        return Ok(());
+    }
 
     if !output.status.success() {
-        return Err(format!(
-            "pngquant failed with error:\n\
-			Command: pngquant --quality {} --output {} --force {}\n\
-			Stderr:\n{}\n\
-			Stdout:\n{}",
-            &quality_argument,
-            output_path.display(),
-            input_path.display(),
-            String::from_utf8_lossy(&output.stderr),
-            String::from_utf8_lossy(&output.stdout)
+        return Err(SwiftKitError::tool_failed(
+            "pngquant",
+            format!(
+                "exited with {} running pngquant --quality {} --output {} --force {}",
+                output.status,
+                quality_argument,
+                output_path.display(),
+                input_path.display()
+            ),
+            Some(String::from_utf8_lossy(&output.stderr).to_string()),
         ));
     }
 
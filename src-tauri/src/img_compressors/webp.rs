@@ -1,32 +1,38 @@
 use std::path::Path;
-use std::{os::windows::process::CommandExt, process::Command};
 
-pub async fn compress(input_path: &Path, quality: u8, output_path: &Path) -> Result<(), String> {
-    let mut cwebp_command = Command::new("cwebp");
+use crate::error::SwiftKitError;
+use crate::utils::hidden_command::hidden_command;
+
+pub async fn compress(
+    cwebp: &Path,
+    input_path: &Path,
+    quality: u8,
+    output_path: &Path,
+) -> Result<(), SwiftKitError> {
+    let mut cwebp_command = hidden_command(cwebp);
     cwebp_command
-        .creation_flags(0x08000000)
         .arg(input_path)
         .arg("-o")
-        .arg(&output_path)
+        .arg(output_path)
         .arg("-q")
         .arg(quality.to_string());
 
-    let output = cwebp_command
-        .output()
-        .map_err(|e| format!("Failed to execute sidecar: {}", e))?;
+    let output = cwebp_command.output().map_err(|e| {
+        SwiftKitError::tool_failed("cwebp", format!("Failed to execute: {}", e), None)
+    })?;
 
     // Check if command failed
     if !output.status.success() {
-        return Err(format!(
-            "cwebp failed with error:\n\
-			Command: cwebp -q {} -o {} -overwrite {}\n\
-			Stderr:\n{}\n\
-			Stdout:\n{}",
-            quality,
-            output_path.display(),
-            input_path.display(),
-            String::from_utf8_lossy(&output.stderr),
-            String::from_utf8_lossy(&output.stdout)
+        return Err(SwiftKitError::tool_failed(
+            "cwebp",
+            format!(
+                "exited with {} running cwebp -q {} -o {} {}",
+                output.status,
+                quality,
+                output_path.display(),
+                input_path.display()
+            ),
+            Some(String::from_utf8_lossy(&output.stderr).to_string()),
         ));
     }
 
@@ -0,0 +1,240 @@
+use image::codecs::avif::AvifEncoder;
+use image::codecs::png::{CompressionType, FilterType, PngEncoder};
+use image::metadata::Orientation;
+use image::{DynamicImage, ImageDecoder, ImageEncoder, ImageFormat, ImageReader};
+use std::borrow::Cow;
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+
+use super::icon;
+use super::jpeg;
+use super::options::{EncodeOptions, PngCompression};
+use super::png;
+use super::webp;
+use crate::error::SwiftKitError;
+use crate::img_metadata::container::{embed, RawMetadata};
+use crate::img_metadata::mode::MetadataMode;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Png,
+    Jpeg,
+    WebP,
+    Ico,
+    Icns,
+    Bmp,
+    Avif,
+    Tiff,
+    Gif,
+    Tga,
+    Qoi,
+}
+
+impl OutputFormat {
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_lowercase().as_str() {
+            "png" => Some(OutputFormat::Png),
+            "jpg" | "jpeg" => Some(OutputFormat::Jpeg),
+            "webp" => Some(OutputFormat::WebP),
+            "ico" => Some(OutputFormat::Ico),
+            "icns" => Some(OutputFormat::Icns),
+            "bmp" => Some(OutputFormat::Bmp),
+            "avif" => Some(OutputFormat::Avif),
+            "tif" | "tiff" => Some(OutputFormat::Tiff),
+            "gif" => Some(OutputFormat::Gif),
+            "tga" => Some(OutputFormat::Tga),
+            "qoi" => Some(OutputFormat::Qoi),
+            _ => None,
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(OutputFormat::from_extension)
+    }
+
+    fn image_format(&self) -> Option<ImageFormat> {
+        let format = match self {
+            OutputFormat::Png => ImageFormat::Png,
+            OutputFormat::Jpeg => ImageFormat::Jpeg,
+            OutputFormat::WebP => ImageFormat::WebP,
+            OutputFormat::Bmp => ImageFormat::Bmp,
+            OutputFormat::Avif => ImageFormat::Avif,
+            OutputFormat::Tiff => ImageFormat::Tiff,
+            OutputFormat::Gif => ImageFormat::Gif,
+            OutputFormat::Tga => ImageFormat::Tga,
+            OutputFormat::Qoi => ImageFormat::Qoi,
+            // Written by the icon module
+            OutputFormat::Ico | OutputFormat::Icns => return None,
+        };
+        Some(format)
+    }
+}
+
+// The format comes from the file's signature, the extension is only a fallback (e.g. TGA).
+// Pixels are turned upright according to the EXIF orientation. Every `OutputFormat` can be
+// opened except AVIF and ICNS, which are output only and fail with a validation error.
+pub fn open_image(path: &Path) -> Result<DynamicImage, SwiftKitError> {
+    open_image_with_metadata(path).map(|(img, _)| img)
+}
+
+pub fn open_image_with_metadata(path: &Path) -> Result<(DynamicImage, RawMetadata), SwiftKitError> {
+    let bytes = fs::read(path).map_err(|e| SwiftKitError::io(e, path))?;
+    let mut reader = ImageReader::new(Cursor::new(bytes.as_slice()))
+        .with_guessed_format()
+        .map_err(|e| SwiftKitError::io(e, path))?;
+    if reader.format().is_none() {
+        reader.set_format(ImageFormat::from_path(path).map_err(|_| {
+            SwiftKitError::validation(format!("Unrecognized image format: {}", path.display()))
+        })?);
+    }
+    decode(reader, &bytes)
+}
+
+// Same as `open_image_with_metadata` for bytes that are already in memory
+pub fn load_image(bytes: &[u8]) -> Result<(DynamicImage, RawMetadata), SwiftKitError> {
+    let reader = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(|e| SwiftKitError::io(e, "<memory>"))?;
+    if reader.format().is_none() {
+        return Err(SwiftKitError::validation("Unrecognized image format"));
+    }
+    decode(reader, bytes)
+}
+
+fn decode(
+    reader: ImageReader<Cursor<&[u8]>>,
+    bytes: &[u8],
+) -> Result<(DynamicImage, RawMetadata), SwiftKitError> {
+    // Decoding AVIF needs the dav1d C library, which isn't bundled
+    if reader.format() == Some(ImageFormat::Avif) {
+        return Err(SwiftKitError::validation(
+            "AVIF images can be written but not opened, reading AVIF needs the dav1d library which isn't bundled.",
+        ));
+    }
+
+    let metadata = RawMetadata::read(bytes);
+    let mut decoder = reader.into_decoder()?;
+    // Our reader also covers PNG's eXIf, image's covers TIFF
+    let orientation = match metadata.orientation().and_then(Orientation::from_exif) {
+        Some(orientation) => orientation,
+        None => decoder.orientation()?,
+    };
+    let mut img = DynamicImage::from_decoder(decoder)?;
+    img.apply_orientation(orientation);
+    Ok((img, metadata))
+}
+
+pub fn encode(
+    img: &DynamicImage,
+    format: OutputFormat,
+    options: &EncodeOptions,
+) -> Result<Vec<u8>, SwiftKitError> {
+    options.validate()?;
+
+    let mut bytes = Cursor::new(Vec::new());
+    match format {
+        OutputFormat::Jpeg => {
+            return jpeg::encode(img, options.jpeg_quality(), options.jpeg_progressive)
+        }
+        OutputFormat::WebP => return webp::encode(img, options.webp_quality),
+        // Every standard size up to the source's, non-square sources are padded
+        OutputFormat::Ico => {
+            let square = icon::square_canvas(img);
+            let sizes = icon::sizes_up_to(&icon::ICO_SIZES, square.width());
+            return icon::ico(&square, &sizes);
+        }
+        OutputFormat::Icns => {
+            let square = icon::square_canvas(img);
+            let sizes = icon::sizes_up_to(&icon::ICNS_SIZES, square.width());
+            return icon::icns(&square, &sizes);
+        }
+        OutputFormat::Png => {
+            if let Some(quality) = options.png_quality {
+                return png::quantize(img, quality);
+            }
+            // Best also tries smaller colour types and every filter, which is much slower
+            if options.png_compression == PngCompression::Best {
+                return png::optimize(img);
+            }
+
+            let img = without_float_channels(img);
+            let encoder = PngEncoder::new_with_quality(
+                &mut bytes,
+                options.png_compression.into(),
+                FilterType::Adaptive,
+            );
+            encoder.write_image(
+                img.as_bytes(),
+                img.width(),
+                img.height(),
+                img.color().into(),
+            )?;
+        }
+        OutputFormat::Avif => {
+            let img = eight_bit(img);
+            let encoder = AvifEncoder::new_with_speed_quality(
+                &mut bytes,
+                options.avif_speed(),
+                options.avif_quality(),
+            );
+            encoder.write_image(
+                img.as_bytes(),
+                img.width(),
+                img.height(),
+                img.color().into(),
+            )?;
+        }
+        // The remaining encoders only take 8-bit RGB(A) reliably
+        _ => {
+            let image_format = format
+                .image_format()
+                .ok_or_else(|| SwiftKitError::validation("Unsupported output format"))?;
+            eight_bit(img).write_to(&mut bytes, image_format)?
+        }
+    }
+
+    Ok(bytes.into_inner())
+}
+
+// `encode`, then what `mode` keeps of the source's metadata is added to JPEG, PNG and WebP
+pub fn encode_with_metadata(
+    img: &DynamicImage,
+    format: OutputFormat,
+    options: &EncodeOptions,
+    metadata: &RawMetadata,
+    mode: MetadataMode,
+) -> Result<Vec<u8>, SwiftKitError> {
+    let bytes = encode(img, format, options)?;
+    Ok(embed(bytes, format, img, &metadata.for_output(mode)))
+}
+
+pub fn eight_bit(img: &DynamicImage) -> Cow<'_, DynamicImage> {
+    match img {
+        DynamicImage::ImageRgb8(_) | DynamicImage::ImageRgba8(_) => Cow::Borrowed(img),
+        _ if img.color().has_alpha() => Cow::Owned(DynamicImage::ImageRgba8(img.to_rgba8())),
+        _ => Cow::Owned(DynamicImage::ImageRgb8(img.to_rgb8())),
+    }
+}
+
+// PNG keeps 16-bit channels but has no floating point pixels
+fn without_float_channels(img: &DynamicImage) -> Cow<'_, DynamicImage> {
+    match img {
+        DynamicImage::ImageRgb32F(_) | DynamicImage::ImageRgba32F(_) => {
+            Cow::Owned(DynamicImage::ImageRgba16(img.to_rgba16()))
+        }
+        _ => Cow::Borrowed(img),
+    }
+}
+
+impl From<PngCompression> for CompressionType {
+    fn from(compression: PngCompression) -> Self {
+        match compression {
+            PngCompression::Fast => CompressionType::Fast,
+            PngCompression::Default => CompressionType::Default,
+            PngCompression::Best => CompressionType::Best,
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use image::codecs::ico::{IcoEncoder, IcoFrame};
+use image::codecs::png::PngEncoder;
+use image::imageops::{self, FilterType};
+use image::{DynamicImage, ExtendedColorType, ImageEncoder, RgbaImage};
+
+use crate::error::SwiftKitError;
+
+pub const ICO_SIZES: [u32; 7] = [16, 24, 32, 48, 64, 128, 256];
+pub const ICNS_SIZES: [u32; 7] = [16, 32, 64, 128, 256, 512, 1024];
+
+// Small icons look blurry after a large downscale, these get an unsharp mask
+const SHARPEN_UP_TO: u32 = 64;
+
+// ICNS stores PNG data under a type per size, retina types reuse the same pixels
+fn icns_types(size: u32) -> &'static [&'static [u8; 4]] {
+    match size {
+        16 => &[b"icp4"],
+        32 => &[b"icp5", b"ic11"],
+        64 => &[b"icp6", b"ic12"],
+        128 => &[b"ic07"],
+        256 => &[b"ic08", b"ic13"],
+        512 => &[b"ic09", b"ic14"],
+        1024 => &[b"ic10"],
+        _ => &[],
+    }
+}
+
+// Centers the image on a transparent square so nothing gets stretched
+pub fn square_canvas(img: &DynamicImage) -> RgbaImage {
+    let rgba = img.to_rgba8();
+    let (width, height) = rgba.dimensions();
+    if width == height {
+        return rgba;
+    }
+
+    let side = width.max(height);
+    let mut canvas = RgbaImage::new(side, side);
+    imageops::overlay(
+        &mut canvas,
+        &rgba,
+        i64::from((side - width) / 2),
+        i64::from((side - height) / 2),
+    );
+    canvas
+}
+
+pub fn render(square: &RgbaImage, size: u32) -> RgbaImage {
+    if square.width() == size {
+        return square.clone();
+    }
+
+    let resized = imageops::resize(square, size, size, FilterType::Lanczos3);
+    if size <= SHARPEN_UP_TO && size < square.width() {
+        imageops::unsharpen(&resized, 0.5, 2)
+    } else {
+        resized
+    }
+}
+
+pub fn png(frame: &RgbaImage) -> Result<Vec<u8>, SwiftKitError> {
+    let mut bytes = Vec::new();
+    PngEncoder::new(&mut bytes).write_image(
+        frame.as_raw(),
+        frame.width(),
+        frame.height(),
+        ExtendedColorType::Rgba8,
+    )?;
+    Ok(bytes)
+}
+
+pub fn ico(square: &RgbaImage, sizes: &[u32]) -> Result<Vec<u8>, SwiftKitError> {
+    if sizes.is_empty() || sizes.iter().any(|size| !(1..=256).contains(size)) {
+        return Err(SwiftKitError::validation(
+            "ICO sizes must be between 1 and 256.",
+        ));
+    }
+
+    let mut frames = Vec::with_capacity(sizes.len());
+    for &size in sizes {
+        let encoded = png(&render(square, size))?;
+        frames.push(IcoFrame::with_encoded(
+            encoded,
+            size,
+            size,
+            ExtendedColorType::Rgba8,
+        )?);
+    }
+
+    let mut bytes = Vec::new();
+    IcoEncoder::new(&mut bytes).encode_images(&frames)?;
+    Ok(bytes)
+}
+
+pub fn icns(square: &RgbaImage, sizes: &[u32]) -> Result<Vec<u8>, SwiftKitError> {
+    if sizes.is_empty() {
+        return Err(SwiftKitError::validation("No ICNS sizes were given."));
+    }
+
+    let mut entries = Vec::new();
+    for &size in sizes {
+        let types = icns_types(size);
+        if types.is_empty() {
+            return Err(SwiftKitError::validation(format!(
+                "ICNS doesn't support {}x{} icons.",
+                size, size
+            )));
+        }
+
+        let encoded = png(&render(square, size))?;
+        for ostype in types {
+            entries.extend_from_slice(*ostype);
+            entries.extend_from_slice(&block_length(encoded.len())?.to_be_bytes());
+            entries.extend_from_slice(&encoded);
+        }
+    }
+
+    let mut bytes = Vec::with_capacity(entries.len() + 8);
+    bytes.extend_from_slice(b"icns");
+    bytes.extend_from_slice(&block_length(entries.len())?.to_be_bytes());
+    bytes.extend_from_slice(&entries);
+    Ok(bytes)
+}
+
+// Lengths include the 8 byte type and length header
+fn block_length(data_length: usize) -> Result<u32, SwiftKitError> {
+    u32::try_from(data_length + 8)
+        .map_err(|_| SwiftKitError::validation("Icon is too large for ICNS."))
+}
+
+// Sizes for a source of `side` pixels, small sources get a single frame instead of upscaling
+pub fn sizes_up_to(sizes: &[u32], side: u32) -> Vec<u32> {
+    let fitting: Vec<u32> = sizes.iter().copied().filter(|&size| size <= side).collect();
+    if fitting.is_empty() {
+        sizes
+            .first()
+            .map(|&size| vec![size.min(side)])
+            .unwrap_or_default()
+    } else {
+        fitting
+    }
+}
@@ -0,0 +1,32 @@
+use image::DynamicImage;
+use jpeg_encoder::{ColorType, Encoder};
+
+use crate::error::SwiftKitError;
+
+// jpeg-encoder instead of image's encoder, which can't write progressive files
+pub fn encode(
+    img: &DynamicImage,
+    quality: u8,
+    progressive: bool,
+) -> Result<Vec<u8>, SwiftKitError> {
+    let (Ok(width), Ok(height)) = (u16::try_from(img.width()), u16::try_from(img.height())) else {
+        return Err(SwiftKitError::validation(
+            "JPEG images can't be larger than 65535 pixels per side.",
+        ));
+    };
+
+    let mut bytes = Vec::new();
+    let mut encoder = Encoder::new(&mut bytes, quality);
+    encoder.set_progressive(progressive);
+
+    let encoded = match img {
+        DynamicImage::ImageLuma8(luma) => {
+            encoder.encode(luma.as_raw(), width, height, ColorType::Luma)
+        }
+        // JPEG has no alpha channel, it is dropped like image's encoder would
+        _ => encoder.encode(&img.to_rgb8(), width, height, ColorType::Rgb),
+    };
+    encoded.map_err(|e| SwiftKitError::internal(format!("Failed to encode JPEG: {}", e)))?;
+
+    Ok(bytes)
+}
@@ -0,0 +1,6 @@
+pub mod encode;
+pub mod icon;
+pub mod jpeg;
+pub mod options;
+pub mod png;
+pub mod webp;
@@ -0,0 +1,73 @@
+use serde::Deserialize;
+
+use crate::error::SwiftKitError;
+
+const DEFAULT_JPEG_QUALITY: u8 = 90;
+const DEFAULT_AVIF_QUALITY: u8 = 80;
+const DEFAULT_AVIF_SPEED: u8 = 6;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PngCompression {
+    Fast,
+    #[default]
+    Default,
+    Best,
+}
+
+// Encoder settings, each format only reads its own fields
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct EncodeOptions {
+    pub jpeg_quality: Option<u8>,
+    pub jpeg_progressive: bool,
+    // Lossless when not set
+    pub webp_quality: Option<u8>,
+    pub avif_quality: Option<u8>,
+    // 1 is the slowest and smallest, 10 the fastest
+    pub avif_speed: Option<u8>,
+    pub png_compression: PngCompression,
+    // Lossy palette quantization when set
+    pub png_quality: Option<u8>,
+}
+
+impl EncodeOptions {
+    pub fn jpeg_quality(&self) -> u8 {
+        self.jpeg_quality.unwrap_or(DEFAULT_JPEG_QUALITY)
+    }
+
+    pub fn avif_quality(&self) -> u8 {
+        self.avif_quality.unwrap_or(DEFAULT_AVIF_QUALITY)
+    }
+
+    pub fn avif_speed(&self) -> u8 {
+        self.avif_speed.unwrap_or(DEFAULT_AVIF_SPEED)
+    }
+
+    pub fn validate(&self) -> Result<(), SwiftKitError> {
+        for (name, quality) in [
+            ("JPEG", self.jpeg_quality),
+            ("WebP", self.webp_quality),
+            ("AVIF", self.avif_quality),
+            ("PNG", self.png_quality),
+        ] {
+            if quality.is_some_and(|quality| !(1..=100).contains(&quality)) {
+                return Err(SwiftKitError::validation(format!(
+                    "{} quality must be between 1 and 100.",
+                    name
+                )));
+            }
+        }
+
+        if self
+            .avif_speed
+            .is_some_and(|speed| !(1..=10).contains(&speed))
+        {
+            return Err(SwiftKitError::validation(
+                "AVIF speed must be between 1 and 10.",
+            ));
+        }
+
+        Ok(())
+    }
+}
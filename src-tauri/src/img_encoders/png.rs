@@ -0,0 +1,305 @@
+use color_quant::NeuQuant;
+use image::{DynamicImage, Rgba, RgbaImage};
+use png::{AdaptiveFilterType, BitDepth, ColorType, Compression, Encoder, FilterType};
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::mem;
+
+use crate::error::SwiftKitError;
+
+// Scanlines for one way of storing the image
+struct Candidate {
+    color: ColorType,
+    depth: BitDepth,
+    data: Vec<u8>,
+    palette: Option<Vec<u8>>,
+    trns: Option<Vec<u8>>,
+}
+
+// `None` picks a filter per row, the others use the same filter for every row
+const FILTERS: [Option<FilterType>; 6] = [
+    None,
+    Some(FilterType::NoFilter),
+    Some(FilterType::Sub),
+    Some(FilterType::Up),
+    Some(FilterType::Avg),
+    Some(FilterType::Paeth),
+];
+
+// Lossless, in the spirit of oxipng: every colour type and bit depth that holds the
+// pixels exactly is written with every filter strategy and the smallest file wins
+pub fn optimize(img: &DynamicImage) -> Result<Vec<u8>, SwiftKitError> {
+    let (width, height) = (img.width(), img.height());
+    let candidates = candidates(img);
+    let trials: Vec<_> = candidates
+        .iter()
+        .flat_map(|candidate| FILTERS.iter().map(move |filter| (candidate, *filter)))
+        .collect();
+
+    trials
+        .par_iter()
+        .map(|(candidate, filter)| write(width, height, candidate, *filter))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .min_by_key(Vec::len)
+        .ok_or_else(|| SwiftKitError::internal("No PNG could be written"))
+}
+
+// Lossy, in the spirit of pngquant: the colours are reduced to a palette sized by
+// `quality` with Floyd-Steinberg dithering, then stored through `optimize`
+pub fn quantize(img: &DynamicImage, quality: u8) -> Result<Vec<u8>, SwiftKitError> {
+    let quality = quality.clamp(1, 100);
+    let colors = (usize::from(quality) * 256 / 100).clamp(2, 256);
+    let mut rgba = img.to_rgba8();
+    if fits_palette(&rgba, colors) {
+        return optimize(img);
+    }
+
+    // 1 samples every pixel, 30 is the fastest NeuQuant allows
+    let sample_factor = (31 - i32::from(quality) * 3 / 10).clamp(1, 30);
+    let quantizer = NeuQuant::new(sample_factor, colors, rgba.as_raw());
+    dither(&mut rgba, &quantizer);
+
+    optimize(&DynamicImage::ImageRgba8(rgba))
+}
+
+fn candidates(img: &DynamicImage) -> Vec<Candidate> {
+    if img.color().bytes_per_pixel() > img.color().channel_count() {
+        let rgba = img.to_rgba16();
+        // 16-bit files converted from 8-bit sources only repeat each byte
+        if !rgba.as_raw().iter().all(|sample| sample % 257 == 0) {
+            return vec![sixteen_bit(&rgba)];
+        }
+    }
+
+    let mut rgba = img.to_rgba8();
+    // Colour under fully transparent pixels is invisible, zeroing it compresses better
+    for pixel in rgba.pixels_mut().filter(|pixel| pixel[3] == 0) {
+        *pixel = Rgba([0, 0, 0, 0]);
+    }
+    let opaque = rgba.pixels().all(|pixel| pixel[3] == u8::MAX);
+    let gray = rgba
+        .pixels()
+        .all(|pixel| pixel[0] == pixel[1] && pixel[1] == pixel[2]);
+
+    let mut candidates = vec![true_color(&rgba, opaque, gray)];
+    candidates.extend(indexed(&rgba));
+    if opaque && gray {
+        candidates.extend(low_depth_gray(&rgba));
+    }
+    candidates
+}
+
+fn layout(opaque: bool, gray: bool) -> (ColorType, &'static [usize]) {
+    match (gray, opaque) {
+        (true, true) => (ColorType::Grayscale, &[0]),
+        (true, false) => (ColorType::GrayscaleAlpha, &[0, 3]),
+        (false, true) => (ColorType::Rgb, &[0, 1, 2]),
+        (false, false) => (ColorType::Rgba, &[0, 1, 2, 3]),
+    }
+}
+
+fn true_color(rgba: &RgbaImage, opaque: bool, gray: bool) -> Candidate {
+    let (color, channels) = layout(opaque, gray);
+    Candidate {
+        color,
+        depth: BitDepth::Eight,
+        data: rgba
+            .pixels()
+            .flat_map(|pixel| channels.iter().map(move |&channel| pixel[channel]))
+            .collect(),
+        palette: None,
+        trns: None,
+    }
+}
+
+fn sixteen_bit(rgba: &image::ImageBuffer<Rgba<u16>, Vec<u16>>) -> Candidate {
+    let opaque = rgba.pixels().all(|pixel| pixel[3] == u16::MAX);
+    let gray = rgba
+        .pixels()
+        .all(|pixel| pixel[0] == pixel[1] && pixel[1] == pixel[2]);
+    let (color, channels) = layout(opaque, gray);
+    Candidate {
+        color,
+        depth: BitDepth::Sixteen,
+        data: rgba
+            .pixels()
+            .flat_map(|pixel| {
+                channels
+                    .iter()
+                    .flat_map(move |&channel| pixel[channel].to_be_bytes())
+            })
+            .collect(),
+        palette: None,
+        trns: None,
+    }
+}
+
+fn indexed(rgba: &RgbaImage) -> Option<Candidate> {
+    let mut colors: Vec<[u8; 4]> = Vec::new();
+    let mut seen = HashSet::new();
+    for pixel in rgba.pixels() {
+        if seen.insert(pixel.0) {
+            if colors.len() == 256 {
+                return None;
+            }
+            colors.push(pixel.0);
+        }
+    }
+
+    // tRNS only has to reach the last translucent entry, so those go first
+    colors.sort_by_key(|color| color[3] == u8::MAX);
+    let lookup: HashMap<[u8; 4], u8> = colors
+        .iter()
+        .enumerate()
+        .map(|(index, color)| (*color, index as u8))
+        .collect();
+    let translucent = colors.iter().take_while(|color| color[3] < u8::MAX).count();
+
+    let depth = match colors.len() {
+        0..=2 => BitDepth::One,
+        3..=4 => BitDepth::Two,
+        5..=16 => BitDepth::Four,
+        _ => BitDepth::Eight,
+    };
+    let indices: Vec<u8> = rgba.pixels().map(|pixel| lookup[&pixel.0]).collect();
+
+    Some(Candidate {
+        color: ColorType::Indexed,
+        depth,
+        data: pack(&indices, rgba.width(), depth),
+        palette: Some(
+            colors
+                .iter()
+                .flat_map(|color| [color[0], color[1], color[2]])
+                .collect(),
+        ),
+        trns: (translucent > 0)
+            .then(|| colors[..translucent].iter().map(|color| color[3]).collect()),
+    })
+}
+
+// Grayscale below 8 bits, when every value sits on the smaller depth's steps
+fn low_depth_gray(rgba: &RgbaImage) -> Option<Candidate> {
+    let (depth, step) = [
+        (BitDepth::One, 255),
+        (BitDepth::Two, 85),
+        (BitDepth::Four, 17),
+    ]
+    .into_iter()
+    .find(|(_, step)| rgba.pixels().all(|pixel| pixel[0] % step == 0))?;
+    let samples: Vec<u8> = rgba.pixels().map(|pixel| pixel[0] / step).collect();
+
+    Some(Candidate {
+        color: ColorType::Grayscale,
+        depth,
+        data: pack(&samples, rgba.width(), depth),
+        palette: None,
+        trns: None,
+    })
+}
+
+// Packs samples below 8 bits into bytes, every row starts on a new byte
+fn pack(samples: &[u8], width: u32, depth: BitDepth) -> Vec<u8> {
+    let bits = depth as usize;
+    if bits == 8 {
+        return samples.to_vec();
+    }
+
+    let width = width as usize;
+    let row_bytes = (width * bits).div_ceil(8);
+    let mut packed = vec![0; row_bytes * samples.len().div_ceil(width)];
+    for (row, row_samples) in samples.chunks(width).enumerate() {
+        for (column, sample) in row_samples.iter().enumerate() {
+            let bit = column * bits;
+            packed[row * row_bytes + bit / 8] |= sample << (8 - bits - bit % 8);
+        }
+    }
+    packed
+}
+
+fn write(
+    width: u32,
+    height: u32,
+    candidate: &Candidate,
+    filter: Option<FilterType>,
+) -> Result<Vec<u8>, SwiftKitError> {
+    let failed =
+        |e: png::EncodingError| SwiftKitError::internal(format!("Failed to encode PNG: {}", e));
+
+    let mut bytes = Vec::new();
+    {
+        let mut encoder = Encoder::new(&mut bytes, width, height);
+        encoder.set_color(candidate.color);
+        encoder.set_depth(candidate.depth);
+        encoder.set_compression(Compression::Best);
+        match filter {
+            Some(filter) => {
+                encoder.set_filter(filter);
+                encoder.set_adaptive_filter(AdaptiveFilterType::NonAdaptive);
+            }
+            None => encoder.set_adaptive_filter(AdaptiveFilterType::Adaptive),
+        }
+        if let Some(palette) = &candidate.palette {
+            encoder.set_palette(palette.as_slice());
+        }
+        if let Some(trns) = &candidate.trns {
+            encoder.set_trns(trns.as_slice());
+        }
+
+        let mut writer = encoder.write_header().map_err(failed)?;
+        writer.write_image_data(&candidate.data).map_err(failed)?;
+        writer.finish().map_err(failed)?;
+    }
+    Ok(bytes)
+}
+
+fn fits_palette(rgba: &RgbaImage, colors: usize) -> bool {
+    let mut seen = HashSet::new();
+    for pixel in rgba.pixels() {
+        if seen.insert(pixel.0) && seen.len() > colors {
+            return false;
+        }
+    }
+    true
+}
+
+// Error diffusion on the colour channels only, dithered alpha shows up as noisy edges
+fn dither(rgba: &mut RgbaImage, quantizer: &NeuQuant) {
+    let width = rgba.width() as usize;
+    let mut current = vec![[0f32; 3]; width + 2];
+    let mut next = vec![[0f32; 3]; width + 2];
+
+    for y in 0..rgba.height() {
+        for x in 0..width {
+            let pixel = rgba.get_pixel_mut(x as u32, y);
+            let wanted: [f32; 3] = std::array::from_fn(|c| {
+                (f32::from(pixel[c]) + current[x + 1][c]).clamp(0.0, 255.0)
+            });
+            let target = [
+                wanted[0].round() as u8,
+                wanted[1].round() as u8,
+                wanted[2].round() as u8,
+                pixel[3],
+            ];
+            let mapped = quantizer
+                .lookup(quantizer.index_of(&target))
+                .unwrap_or(target);
+            let transparent = pixel[3] == 0;
+            pixel.0 = mapped;
+            if transparent {
+                continue;
+            }
+
+            for c in 0..3 {
+                let error = wanted[c] - f32::from(mapped[c]);
+                current[x + 2][c] += error * 7.0 / 16.0;
+                next[x][c] += error * 3.0 / 16.0;
+                next[x + 1][c] += error * 5.0 / 16.0;
+                next[x + 2][c] += error / 16.0;
+            }
+        }
+        mem::swap(&mut current, &mut next);
+        next.fill([0.0; 3]);
+    }
+}
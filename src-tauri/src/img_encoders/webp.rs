@@ -0,0 +1,17 @@
+use image::DynamicImage;
+use webp::Encoder;
+
+use super::encode::eight_bit;
+use crate::error::SwiftKitError;
+
+// libwebp, compiled from source by libwebp-sys. image's own WebP encoder is lossless only.
+pub fn encode(img: &DynamicImage, quality: Option<u8>) -> Result<Vec<u8>, SwiftKitError> {
+    let pixels = eight_bit(img);
+    let encoder = Encoder::from_image(&pixels).map_err(SwiftKitError::internal)?;
+
+    let encoded = match quality {
+        Some(quality) => encoder.encode(f32::from(quality)),
+        None => encoder.encode_lossless(),
+    };
+    Ok(encoded.to_vec())
+}
@@ -0,0 +1,358 @@
+use image::DynamicImage;
+use regex::Regex;
+use std::sync::LazyLock;
+
+use super::exif;
+use super::mode::MetadataMode;
+use crate::img_encoders::encode::OutputFormat;
+
+const EXIF_HEADER: &[u8] = b"Exif\0\0";
+const XMP_HEADER: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+const XMP_KEYWORD: &[u8] = b"XML:com.adobe.xmp";
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+// A JPEG segment's length field counts itself
+const MAX_SEGMENT: usize = u16::MAX as usize - 2;
+
+static XMP_GPS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?s)\s*exif:GPS\w+="[^"]*"|<exif:GPS\w+\b.*?</exif:GPS\w+>"#).unwrap()
+});
+static XMP_ORIENTATION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(tiff:Orientation\s*=\s*"|<tiff:Orientation>)\s*\d"#).unwrap());
+
+// EXIF as a TIFF structure and XMP as XML, the way the file stores them
+#[derive(Debug, Clone, Default)]
+pub struct RawMetadata {
+    pub exif: Option<Vec<u8>>,
+    pub xmp: Option<Vec<u8>>,
+}
+
+impl RawMetadata {
+    // JPEG, PNG and WebP, other formats have none as far as we're concerned
+    pub fn read(bytes: &[u8]) -> Self {
+        if bytes.starts_with(&[0xFF, 0xD8]) {
+            read_jpeg(bytes)
+        } else if bytes.starts_with(PNG_SIGNATURE) {
+            read_png(bytes)
+        } else if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WEBP") {
+            read_webp(bytes)
+        } else {
+            RawMetadata::default()
+        }
+    }
+
+    pub fn orientation(&self) -> Option<u8> {
+        self.exif.as_deref().and_then(exif::orientation)
+    }
+
+    // What `mode` keeps, with the orientation reset since decoding already applied it
+    pub fn for_output(&self, mode: MetadataMode) -> RawMetadata {
+        if mode == MetadataMode::Strip {
+            return RawMetadata::default();
+        }
+
+        let mut exif = self.exif.clone();
+        let mut xmp = self
+            .xmp
+            .as_deref()
+            .map(|xmp| String::from_utf8_lossy(xmp).to_string());
+        // A block that can't be rewritten safely is dropped rather than kept half-changed
+        if exif
+            .as_mut()
+            .is_some_and(|exif| !exif::reset_orientation(exif))
+        {
+            exif = None;
+        }
+        if let Some(text) = xmp.as_mut() {
+            *text = XMP_ORIENTATION.replace_all(text, "${1}1").to_string();
+        }
+        if mode == MetadataMode::StripGps {
+            if exif.as_mut().is_some_and(|exif| !exif::strip_gps(exif)) {
+                exif = None;
+            }
+            if let Some(text) = xmp.as_mut() {
+                *text = XMP_GPS.replace_all(text, "").to_string();
+            }
+        }
+
+        RawMetadata {
+            exif,
+            xmp: xmp.map(String::into_bytes),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.exif.is_none() && self.xmp.is_none()
+    }
+}
+
+// Adds `metadata` to a freshly encoded JPEG, PNG or WebP, other formats are returned as they are
+pub fn embed(
+    encoded: Vec<u8>,
+    format: OutputFormat,
+    img: &DynamicImage,
+    metadata: &RawMetadata,
+) -> Vec<u8> {
+    if metadata.is_empty() {
+        return encoded;
+    }
+    match format {
+        OutputFormat::Jpeg => embed_jpeg(encoded, metadata),
+        OutputFormat::Png => embed_png(encoded, metadata),
+        OutputFormat::WebP => embed_webp(encoded, img, metadata),
+        _ => encoded,
+    }
+}
+
+fn read_jpeg(bytes: &[u8]) -> RawMetadata {
+    let mut metadata = RawMetadata::default();
+    for (marker, segment) in jpeg_segments(bytes) {
+        if marker != 0xE1 {
+            continue;
+        }
+        if let Some(exif) = segment.strip_prefix(EXIF_HEADER) {
+            metadata.exif.get_or_insert_with(|| exif.to_vec());
+        } else if let Some(xmp) = segment.strip_prefix(XMP_HEADER) {
+            metadata.xmp.get_or_insert_with(|| xmp.to_vec());
+        }
+    }
+    metadata
+}
+
+// Marker and payload of every segment before the image data
+fn jpeg_segments(bytes: &[u8]) -> Vec<(u8, &[u8])> {
+    let mut segments = Vec::new();
+    let mut at = 2;
+    while at + 4 <= bytes.len() && bytes[at] == 0xFF {
+        let marker = bytes[at + 1];
+        // Padding before a marker
+        if marker == 0xFF {
+            at += 1;
+            continue;
+        }
+        // Start of scan and end of image, no metadata follows
+        if marker == 0xDA || marker == 0xD9 {
+            break;
+        }
+        let length = usize::from(u16::from_be_bytes([bytes[at + 2], bytes[at + 3]]));
+        let Some(segment) = bytes.get(at + 4..at + 2 + length) else {
+            break;
+        };
+        segments.push((marker, segment));
+        at += 2 + length;
+    }
+    segments
+}
+
+fn embed_jpeg(encoded: Vec<u8>, metadata: &RawMetadata) -> Vec<u8> {
+    // JFIF's APP0 has to stay the first segment
+    let mut insert_at = 2;
+    if encoded.get(2..4) == Some(&[0xFF, 0xE0]) {
+        insert_at = 4 + usize::from(u16::from_be_bytes([encoded[4], encoded[5]]));
+    }
+
+    let mut segments = Vec::new();
+    for (header, payload) in [
+        (EXIF_HEADER, metadata.exif.as_deref()),
+        (XMP_HEADER, metadata.xmp.as_deref()),
+    ] {
+        // Anything larger would need extended XMP or be cut off
+        let Some(payload) = payload.filter(|p| header.len() + p.len() <= MAX_SEGMENT) else {
+            continue;
+        };
+        segments.extend_from_slice(&[0xFF, 0xE1]);
+        segments.extend_from_slice(&((header.len() + payload.len() + 2) as u16).to_be_bytes());
+        segments.extend_from_slice(header);
+        segments.extend_from_slice(payload);
+    }
+
+    let mut output = Vec::with_capacity(encoded.len() + segments.len());
+    output.extend_from_slice(&encoded[..insert_at]);
+    output.extend_from_slice(&segments);
+    output.extend_from_slice(&encoded[insert_at..]);
+    output
+}
+
+fn read_png(bytes: &[u8]) -> RawMetadata {
+    let mut metadata = RawMetadata::default();
+    for (kind, data) in png_chunks(bytes) {
+        match kind {
+            b"eXIf" => {
+                metadata.exif.get_or_insert_with(|| data.to_vec());
+            }
+            b"iTXt" => {
+                // keyword, compression flag and method, language and translated keyword, text
+                let Some(rest) = data
+                    .strip_prefix(XMP_KEYWORD)
+                    .and_then(|rest| rest.strip_prefix(b"\0\0"))
+                else {
+                    continue;
+                };
+                let text = rest.get(1..).and_then(|rest| skip_nul_terminated(rest, 2));
+                if let Some(text) = text {
+                    metadata.xmp.get_or_insert_with(|| text.to_vec());
+                }
+            }
+            _ => {}
+        }
+    }
+    metadata
+}
+
+fn skip_nul_terminated(bytes: &[u8], fields: usize) -> Option<&[u8]> {
+    let mut rest = bytes;
+    for _ in 0..fields {
+        let end = rest.iter().position(|&b| b == 0)?;
+        rest = &rest[end + 1..];
+    }
+    Some(rest)
+}
+
+fn png_chunks(bytes: &[u8]) -> Vec<(&[u8], &[u8])> {
+    let mut chunks = Vec::new();
+    let mut at = PNG_SIGNATURE.len();
+    while let Some(header) = bytes.get(at..at + 8) {
+        let length = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        let kind = &header[4..8];
+        let Some(data) = bytes.get(at + 8..at + 8 + length) else {
+            break;
+        };
+        chunks.push((kind, data));
+        if kind == b"IEND" {
+            break;
+        }
+        at += 12 + length;
+    }
+    chunks
+}
+
+fn png_chunk(kind: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut chunk = Vec::with_capacity(data.len() + 12);
+    chunk.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    chunk.extend_from_slice(kind);
+    chunk.extend_from_slice(data);
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(kind);
+    hasher.update(data);
+    chunk.extend_from_slice(&hasher.finalize().to_be_bytes());
+    chunk
+}
+
+fn embed_png(encoded: Vec<u8>, metadata: &RawMetadata) -> Vec<u8> {
+    // Right after IHDR, eXIf has to come before the image data
+    let Some(insert_at) = png_chunks(&encoded)
+        .first()
+        .filter(|(kind, _)| *kind == b"IHDR")
+        .map(|(_, data)| PNG_SIGNATURE.len() + 12 + data.len())
+    else {
+        return encoded;
+    };
+
+    let mut chunks = Vec::new();
+    if let Some(exif) = &metadata.exif {
+        chunks.extend(png_chunk(b"eXIf", exif));
+    }
+    if let Some(xmp) = &metadata.xmp {
+        let mut data = XMP_KEYWORD.to_vec();
+        // Uncompressed, no language or translated keyword
+        data.extend_from_slice(b"\0\0\0\0\0");
+        data.extend_from_slice(xmp);
+        chunks.extend(png_chunk(b"iTXt", &data));
+    }
+
+    let mut output = Vec::with_capacity(encoded.len() + chunks.len());
+    output.extend_from_slice(&encoded[..insert_at]);
+    output.extend_from_slice(&chunks);
+    output.extend_from_slice(&encoded[insert_at..]);
+    output
+}
+
+fn read_webp(bytes: &[u8]) -> RawMetadata {
+    let mut metadata = RawMetadata::default();
+    for (kind, data) in webp_chunks(bytes) {
+        match kind {
+            // Some writers keep the JPEG style header
+            b"EXIF" => {
+                let exif = data.strip_prefix(EXIF_HEADER).unwrap_or(data);
+                metadata.exif.get_or_insert_with(|| exif.to_vec());
+            }
+            b"XMP " => {
+                metadata.xmp.get_or_insert_with(|| data.to_vec());
+            }
+            _ => {}
+        }
+    }
+    metadata
+}
+
+fn webp_chunks(bytes: &[u8]) -> Vec<(&[u8], &[u8])> {
+    let mut chunks = Vec::new();
+    let mut at = 12;
+    while let Some(header) = bytes.get(at..at + 8) {
+        let length = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+        let Some(data) = bytes.get(at + 8..at + 8 + length) else {
+            break;
+        };
+        chunks.push((&header[..4], data));
+        at += 8 + length + length % 2;
+    }
+    chunks
+}
+
+// Metadata needs the extended format, simple files get a VP8X header first
+fn embed_webp(encoded: Vec<u8>, img: &DynamicImage, metadata: &RawMetadata) -> Vec<u8> {
+    const ALPHA: u8 = 0x10;
+    const EXIF: u8 = 0x08;
+    const XMP: u8 = 0x04;
+
+    let chunks = webp_chunks(&encoded);
+    let mut flags = 0;
+    if metadata.exif.is_some() {
+        flags |= EXIF;
+    }
+    if metadata.xmp.is_some() {
+        flags |= XMP;
+    }
+
+    let mut body = Vec::new();
+    let mut push = |kind: &[u8], data: &[u8]| {
+        body.extend_from_slice(kind);
+        body.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        body.extend_from_slice(data);
+        if data.len() % 2 == 1 {
+            body.push(0);
+        }
+    };
+
+    match chunks.first() {
+        Some((kind, data)) if *kind == b"VP8X" && !data.is_empty() => {
+            let mut header = data.to_vec();
+            header[0] |= flags;
+            push(b"VP8X", &header);
+        }
+        _ => {
+            let mut header = vec![0; 10];
+            header[0] = flags | if img.color().has_alpha() { ALPHA } else { 0 };
+            header[4..7].copy_from_slice(&(img.width() - 1).to_le_bytes()[..3]);
+            header[7..10].copy_from_slice(&(img.height() - 1).to_le_bytes()[..3]);
+            push(b"VP8X", &header);
+        }
+    }
+    for (kind, data) in &chunks {
+        if !matches!(*kind, b"VP8X" | b"EXIF" | b"XMP ") {
+            push(kind, data);
+        }
+    }
+    if let Some(exif) = &metadata.exif {
+        push(b"EXIF", exif);
+    }
+    if let Some(xmp) = &metadata.xmp {
+        push(b"XMP ", xmp);
+    }
+
+    let mut output = Vec::with_capacity(body.len() + 12);
+    output.extend_from_slice(b"RIFF");
+    output.extend_from_slice(&(body.len() as u32 + 4).to_le_bytes());
+    output.extend_from_slice(b"WEBP");
+    output.extend_from_slice(&body);
+    output
+}
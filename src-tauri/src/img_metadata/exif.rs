@@ -0,0 +1,534 @@
+use serde::Serialize;
+
+const EXIF_POINTER: u16 = 0x8769;
+const GPS_POINTER: u16 = 0x8825;
+const ORIENTATION: u16 = 0x0112;
+// Limits how much a corrupt count can make us read
+const MAX_ENTRIES: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Ifd {
+    Primary,
+    Exif,
+    Gps,
+}
+
+#[derive(Debug, Clone)]
+pub enum Value {
+    Bytes(Vec<u8>),
+    Ascii(String),
+    Short(Vec<u16>),
+    Long(Vec<u32>),
+    Rational(Vec<(u32, u32)>),
+    SLong(Vec<i32>),
+    SRational(Vec<(i32, i32)>),
+    Undefined(Vec<u8>),
+}
+
+impl Value {
+    // First number of a numeric value, rationals are divided out
+    pub fn as_f64(&self) -> Option<f64> {
+        self.numbers().into_iter().next()
+    }
+
+    pub fn numbers(&self) -> Vec<f64> {
+        let ratio = |n: f64, d: f64| if d == 0.0 { None } else { Some(n / d) };
+        match self {
+            Value::Bytes(values) => values.iter().map(|&v| f64::from(v)).collect(),
+            Value::Short(values) => values.iter().map(|&v| f64::from(v)).collect(),
+            Value::Long(values) => values.iter().map(|&v| f64::from(v)).collect(),
+            Value::SLong(values) => values.iter().map(|&v| f64::from(v)).collect(),
+            Value::Rational(values) => values
+                .iter()
+                .filter_map(|&(n, d)| ratio(f64::from(n), f64::from(d)))
+                .collect(),
+            Value::SRational(values) => values
+                .iter()
+                .filter_map(|&(n, d)| ratio(f64::from(n), f64::from(d)))
+                .collect(),
+            Value::Ascii(_) | Value::Undefined(_) => Vec::new(),
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Ascii(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn display(&self) -> String {
+        fn join<T: ToString>(values: impl Iterator<Item = T>) -> String {
+            values.map(|v| v.to_string()).collect::<Vec<_>>().join(", ")
+        }
+        fn fraction<T: ToString + PartialEq + From<u8>>(n: T, d: T) -> String {
+            if d == T::from(1) {
+                n.to_string()
+            } else {
+                format!("{}/{}", n.to_string(), d.to_string())
+            }
+        }
+
+        match self {
+            Value::Ascii(text) => text.clone(),
+            Value::Short(values) => join(values.iter()),
+            Value::Long(values) => join(values.iter()),
+            Value::SLong(values) => join(values.iter()),
+            Value::Rational(values) => join(values.iter().map(|&(n, d)| fraction(n, d))),
+            Value::SRational(values) => join(values.iter().map(|&(n, d)| fraction(n, d))),
+            // Version tags and the like are printable, maker notes and thumbnails are not
+            Value::Bytes(bytes) | Value::Undefined(bytes) => {
+                if !bytes.is_empty() && bytes.iter().all(|b| b.is_ascii_graphic() || *b == b' ') {
+                    String::from_utf8_lossy(bytes).to_string()
+                } else if bytes.len() <= 16 {
+                    join(bytes.iter())
+                } else {
+                    format!("<{} bytes>", bytes.len())
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Field {
+    pub ifd: Ifd,
+    pub tag: u16,
+    pub value: Value,
+}
+
+impl Field {
+    pub fn name(&self) -> Option<&'static str> {
+        TAG_NAMES
+            .iter()
+            .find(|(ifd, tag, _)| *ifd == self.ifd && *tag == self.tag)
+            .map(|(_, _, name)| *name)
+    }
+}
+
+// Every field of the primary, Exif and GPS directories, malformed data gives what was readable
+pub fn fields(exif: &[u8]) -> Vec<Field> {
+    let Some(tiff) = Tiff::new(exif) else {
+        return Vec::new();
+    };
+    let Some(primary) = tiff.first_ifd() else {
+        return Vec::new();
+    };
+
+    let mut fields = Vec::new();
+    let mut directories = vec![(Ifd::Primary, primary)];
+    while let Some((ifd, offset)) = directories.pop() {
+        for entry in tiff.entries(offset) {
+            let pointer = match (ifd, entry.tag) {
+                (Ifd::Primary, EXIF_POINTER) => Some(Ifd::Exif),
+                (Ifd::Primary, GPS_POINTER) => Some(Ifd::Gps),
+                _ => None,
+            };
+            match pointer {
+                Some(child) => {
+                    if let Some(offset) = tiff.u32(entry.at + 8) {
+                        directories.push((child, offset as usize));
+                    }
+                }
+                None => {
+                    if let Some(value) = tiff.value(&entry) {
+                        fields.push(Field {
+                            ifd,
+                            tag: entry.tag,
+                            value,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    fields
+}
+
+pub fn orientation(exif: &[u8]) -> Option<u8> {
+    let tiff = Tiff::new(exif)?;
+    let entry = tiff
+        .entries(tiff.first_ifd()?)
+        .into_iter()
+        .find(|entry| entry.tag == ORIENTATION)?;
+    tiff.value(&entry)?
+        .as_f64()
+        .and_then(|value| u8::try_from(value as u32).ok())
+}
+
+// For pixels that were already turned upright, so viewers don't rotate them again.
+// False when there is an orientation that can't be rewritten, the block should then be dropped.
+pub fn reset_orientation(exif: &mut [u8]) -> bool {
+    let Some(tiff) = Tiff::new(exif) else {
+        return true;
+    };
+    let Some(entry) = tiff.first_ifd().and_then(|ifd| {
+        tiff.entries(ifd)
+            .into_iter()
+            .find(|entry| entry.tag == ORIENTATION)
+    }) else {
+        return true;
+    };
+    if entry.kind != 3 {
+        return false;
+    }
+
+    let one = if tiff.big_endian {
+        1u16.to_be_bytes()
+    } else {
+        1u16.to_le_bytes()
+    };
+    match exif.get_mut(entry.at + 8..entry.at + 10) {
+        Some(value) => {
+            value.copy_from_slice(&one);
+            true
+        }
+        None => false,
+    }
+}
+
+// Drops the GPS directory: its pointer is removed and its bytes are zeroed.
+// False when the primary directory can't be read in full, GPS data may then remain
+// somewhere we can't see and the block should be dropped.
+pub fn strip_gps(exif: &mut [u8]) -> bool {
+    let Some(tiff) = Tiff::new(exif) else {
+        return false;
+    };
+    let Some(primary) = tiff.first_ifd() else {
+        return false;
+    };
+    let Some(count) = tiff.u16(primary).map(usize::from) else {
+        return false;
+    };
+    let entries = tiff.entries(primary);
+    // The table and the next-directory offset after it have to be complete to be moved
+    let Some(table_end) = primary.checked_add(2 + count * 12 + 4) else {
+        return false;
+    };
+    if count > MAX_ENTRIES || entries.len() != count || table_end > exif.len() {
+        return false;
+    }
+    let Some(index) = entries.iter().position(|entry| entry.tag == GPS_POINTER) else {
+        return true;
+    };
+    let big_endian = tiff.big_endian;
+    let gps = tiff
+        .u32(entries[index].at + 8)
+        .map(|offset| offset as usize);
+
+    let mut wipe = Vec::new();
+    if let Some(gps) = gps {
+        let gps_entries = tiff.entries(gps);
+        for entry in &gps_entries {
+            if let Some((start, length)) = tiff.data_range(entry) {
+                if length > 4 {
+                    wipe.push((start, length));
+                }
+            }
+        }
+        wipe.push((gps, 2 + gps_entries.len() * 12 + 4));
+    }
+
+    // The entries after the pointer and the next-directory offset move up by one entry
+    let pointer_at = entries[index].at;
+    exif.copy_within(pointer_at + 12..table_end, pointer_at);
+    exif[table_end - 12..table_end].fill(0);
+    let count = (count - 1) as u16;
+    let count = if big_endian {
+        count.to_be_bytes()
+    } else {
+        count.to_le_bytes()
+    };
+    exif[primary..primary + 2].copy_from_slice(&count);
+
+    for (start, length) in wipe {
+        if let Some(bytes) = exif.get_mut(start..start + length) {
+            bytes.fill(0);
+        }
+    }
+    true
+}
+
+struct Entry {
+    // Offset of the 12 byte entry
+    at: usize,
+    tag: u16,
+    kind: u16,
+    count: usize,
+}
+
+struct Tiff<'a> {
+    data: &'a [u8],
+    big_endian: bool,
+}
+
+impl<'a> Tiff<'a> {
+    fn new(data: &'a [u8]) -> Option<Self> {
+        let big_endian = match data.get(..4)? {
+            [b'I', b'I', 42, 0] => false,
+            [b'M', b'M', 0, 42] => true,
+            _ => return None,
+        };
+        Some(Tiff { data, big_endian })
+    }
+
+    fn u16(&self, at: usize) -> Option<u16> {
+        let bytes: [u8; 2] = self.data.get(at..at + 2)?.try_into().ok()?;
+        Some(if self.big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        })
+    }
+
+    fn u32(&self, at: usize) -> Option<u32> {
+        let bytes: [u8; 4] = self.data.get(at..at + 4)?.try_into().ok()?;
+        Some(if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    }
+
+    fn first_ifd(&self) -> Option<usize> {
+        self.u32(4).map(|offset| offset as usize)
+    }
+
+    fn entries(&self, ifd: usize) -> Vec<Entry> {
+        let count = usize::from(self.u16(ifd).unwrap_or(0)).min(MAX_ENTRIES);
+        (0..count)
+            .map_while(|index| {
+                let at = ifd + 2 + index * 12;
+                Some(Entry {
+                    at,
+                    tag: self.u16(at)?,
+                    kind: self.u16(at + 2)?,
+                    count: self.u32(at + 4)? as usize,
+                })
+            })
+            .collect()
+    }
+
+    // Where the entry's value is, values of up to 4 bytes sit in the entry itself
+    fn data_range(&self, entry: &Entry) -> Option<(usize, usize)> {
+        let size = match entry.kind {
+            1 | 2 | 6 | 7 => 1,
+            3 | 8 => 2,
+            4 | 9 | 11 => 4,
+            5 | 10 | 12 => 8,
+            _ => return None,
+        };
+        let length = entry.count.checked_mul(size)?;
+        let start = if length <= 4 {
+            entry.at + 8
+        } else {
+            self.u32(entry.at + 8)? as usize
+        };
+        self.data.get(start..start.checked_add(length)?)?;
+        Some((start, length))
+    }
+
+    fn value(&self, entry: &Entry) -> Option<Value> {
+        let (start, length) = self.data_range(entry)?;
+        let bytes = &self.data[start..start + length];
+        let count = entry.count;
+        let value = match entry.kind {
+            1 => Value::Bytes(bytes.to_vec()),
+            2 => Value::Ascii(
+                String::from_utf8_lossy(bytes)
+                    .trim_end_matches('\0')
+                    .trim()
+                    .to_string(),
+            ),
+            3 => Value::Short((0..count).filter_map(|i| self.u16(start + i * 2)).collect()),
+            4 => Value::Long((0..count).filter_map(|i| self.u32(start + i * 4)).collect()),
+            5 => Value::Rational(
+                (0..count)
+                    .filter_map(|i| Some((self.u32(start + i * 8)?, self.u32(start + i * 8 + 4)?)))
+                    .collect(),
+            ),
+            7 => Value::Undefined(bytes.to_vec()),
+            9 => Value::SLong(
+                (0..count)
+                    .filter_map(|i| self.u32(start + i * 4).map(|v| v as i32))
+                    .collect(),
+            ),
+            10 => Value::SRational(
+                (0..count)
+                    .filter_map(|i| {
+                        Some((
+                            self.u32(start + i * 8)? as i32,
+                            self.u32(start + i * 8 + 4)? as i32,
+                        ))
+                    })
+                    .collect(),
+            ),
+            // Signed bytes and shorts and floats are rare enough to show as bytes
+            _ => Value::Undefined(bytes.to_vec()),
+        };
+        Some(value)
+    }
+}
+
+const TAG_NAMES: &[(Ifd, u16, &str)] = &[
+    (Ifd::Primary, 0x010E, "ImageDescription"),
+    (Ifd::Primary, 0x010F, "Make"),
+    (Ifd::Primary, 0x0110, "Model"),
+    (Ifd::Primary, 0x0112, "Orientation"),
+    (Ifd::Primary, 0x011A, "XResolution"),
+    (Ifd::Primary, 0x011B, "YResolution"),
+    (Ifd::Primary, 0x0128, "ResolutionUnit"),
+    (Ifd::Primary, 0x0131, "Software"),
+    (Ifd::Primary, 0x0132, "DateTime"),
+    (Ifd::Primary, 0x013B, "Artist"),
+    (Ifd::Primary, 0x0213, "YCbCrPositioning"),
+    (Ifd::Primary, 0x8298, "Copyright"),
+    (Ifd::Exif, 0x829A, "ExposureTime"),
+    (Ifd::Exif, 0x829D, "FNumber"),
+    (Ifd::Exif, 0x8822, "ExposureProgram"),
+    (Ifd::Exif, 0x8827, "ISOSpeedRatings"),
+    (Ifd::Exif, 0x9000, "ExifVersion"),
+    (Ifd::Exif, 0x9003, "DateTimeOriginal"),
+    (Ifd::Exif, 0x9004, "DateTimeDigitized"),
+    (Ifd::Exif, 0x9010, "OffsetTime"),
+    (Ifd::Exif, 0x9011, "OffsetTimeOriginal"),
+    (Ifd::Exif, 0x9201, "ShutterSpeedValue"),
+    (Ifd::Exif, 0x9202, "ApertureValue"),
+    (Ifd::Exif, 0x9204, "ExposureBiasValue"),
+    (Ifd::Exif, 0x9205, "MaxApertureValue"),
+    (Ifd::Exif, 0x9207, "MeteringMode"),
+    (Ifd::Exif, 0x9209, "Flash"),
+    (Ifd::Exif, 0x920A, "FocalLength"),
+    (Ifd::Exif, 0x927C, "MakerNote"),
+    (Ifd::Exif, 0x9286, "UserComment"),
+    (Ifd::Exif, 0x9290, "SubSecTime"),
+    (Ifd::Exif, 0x9291, "SubSecTimeOriginal"),
+    (Ifd::Exif, 0xA001, "ColorSpace"),
+    (Ifd::Exif, 0xA002, "PixelXDimension"),
+    (Ifd::Exif, 0xA003, "PixelYDimension"),
+    (Ifd::Exif, 0xA402, "ExposureMode"),
+    (Ifd::Exif, 0xA403, "WhiteBalance"),
+    (Ifd::Exif, 0xA405, "FocalLengthIn35mmFilm"),
+    (Ifd::Exif, 0xA406, "SceneCaptureType"),
+    (Ifd::Exif, 0xA430, "CameraOwnerName"),
+    (Ifd::Exif, 0xA431, "BodySerialNumber"),
+    (Ifd::Exif, 0xA433, "LensMake"),
+    (Ifd::Exif, 0xA434, "LensModel"),
+    (Ifd::Exif, 0xA435, "LensSerialNumber"),
+    (Ifd::Gps, 0x0000, "GPSVersionID"),
+    (Ifd::Gps, 0x0001, "GPSLatitudeRef"),
+    (Ifd::Gps, 0x0002, "GPSLatitude"),
+    (Ifd::Gps, 0x0003, "GPSLongitudeRef"),
+    (Ifd::Gps, 0x0004, "GPSLongitude"),
+    (Ifd::Gps, 0x0005, "GPSAltitudeRef"),
+    (Ifd::Gps, 0x0006, "GPSAltitude"),
+    (Ifd::Gps, 0x0007, "GPSTimeStamp"),
+    (Ifd::Gps, 0x000C, "GPSSpeedRef"),
+    (Ifd::Gps, 0x000D, "GPSSpeed"),
+    (Ifd::Gps, 0x0010, "GPSImgDirectionRef"),
+    (Ifd::Gps, 0x0011, "GPSImgDirection"),
+    (Ifd::Gps, 0x0012, "GPSMapDatum"),
+    (Ifd::Gps, 0x001D, "GPSDateStamp"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Little-endian TIFF: primary directory with Make, Orientation 6 and a GPS pointer,
+    // GPS directory with a latitude stored outside its entry
+    fn fixture() -> Vec<u8> {
+        let entry = |tag: u16, kind: u16, count: u32, value: u32| {
+            let mut bytes = Vec::new();
+            bytes.extend_from_slice(&tag.to_le_bytes());
+            bytes.extend_from_slice(&kind.to_le_bytes());
+            bytes.extend_from_slice(&count.to_le_bytes());
+            bytes.extend_from_slice(&value.to_le_bytes());
+            bytes
+        };
+
+        let mut exif = b"II\x2a\x00".to_vec();
+        exif.extend_from_slice(&8u32.to_le_bytes());
+        // Primary directory at 8, 3 entries, ends at 8 + 2 + 36 + 4 = 50
+        exif.extend_from_slice(&3u16.to_le_bytes());
+        exif.extend(entry(0x010F, 2, 4, u32::from_le_bytes(*b"ACM\0")));
+        exif.extend(entry(ORIENTATION, 3, 1, 6));
+        exif.extend(entry(GPS_POINTER, 4, 1, 50));
+        exif.extend_from_slice(&0u32.to_le_bytes());
+        // GPS directory at 50, 2 entries, ends at 50 + 2 + 24 + 4 = 80
+        exif.extend_from_slice(&2u16.to_le_bytes());
+        exif.extend(entry(0x0001, 2, 2, u32::from_le_bytes(*b"N\0\0\0")));
+        exif.extend(entry(0x0002, 5, 3, 80));
+        exif.extend_from_slice(&0u32.to_le_bytes());
+        // Latitude 51/1, 30/1, 0/1 at 80
+        for (n, d) in [(51u32, 1u32), (30, 1), (0, 1)] {
+            exif.extend_from_slice(&n.to_le_bytes());
+            exif.extend_from_slice(&d.to_le_bytes());
+        }
+        exif
+    }
+
+    fn has_gps(exif: &[u8]) -> bool {
+        fields(exif).iter().any(|field| field.ifd == Ifd::Gps)
+    }
+
+    #[test]
+    fn resets_orientation() {
+        let mut exif = fixture();
+        assert_eq!(orientation(&exif), Some(6));
+        assert!(reset_orientation(&mut exif));
+        assert_eq!(orientation(&exif), Some(1));
+    }
+
+    #[test]
+    fn strips_gps_and_keeps_the_rest() {
+        let mut exif = fixture();
+        assert!(has_gps(&exif));
+        assert!(strip_gps(&mut exif));
+        assert!(!has_gps(&exif));
+        assert!(!exif.windows(4).any(|bytes| bytes == 51u32.to_le_bytes()));
+        assert_eq!(orientation(&exif), Some(6));
+        let make = fields(&exif)
+            .into_iter()
+            .find(|field| field.tag == 0x010F)
+            .map(|field| field.value.display());
+        assert_eq!(make.as_deref(), Some("ACM"));
+    }
+
+    #[test]
+    fn truncated_blocks_never_panic_or_leak_gps() {
+        let full = fixture();
+        for length in 0..full.len() {
+            let mut exif = full[..length].to_vec();
+            reset_orientation(&mut exif);
+
+            let mut exif = full[..length].to_vec();
+            if strip_gps(&mut exif) {
+                assert!(!has_gps(&exif), "GPS kept at length {}", length);
+            }
+        }
+    }
+
+    #[test]
+    fn truncated_primary_directory_is_not_rewritten() {
+        // Cuts into the next-directory offset after the primary entries
+        let mut exif = fixture()[..48].to_vec();
+        assert!(!strip_gps(&mut exif));
+    }
+
+    #[test]
+    fn oversized_entry_count_is_not_rewritten() {
+        let mut exif = fixture();
+        exif[8..10].copy_from_slice(&2000u16.to_le_bytes());
+        assert!(!strip_gps(&mut exif));
+    }
+
+    #[test]
+    fn orientation_stored_as_long_is_not_rewritten() {
+        let mut exif = fixture();
+        // Second primary entry, its type field
+        exif[24..26].copy_from_slice(&4u16.to_le_bytes());
+        assert!(!reset_orientation(&mut exif));
+    }
+}
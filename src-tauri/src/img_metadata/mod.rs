@@ -0,0 +1,3 @@
+pub mod container;
+pub mod exif;
+pub mod mode;
@@ -0,0 +1,12 @@
+use serde::Deserialize;
+
+// What of the source's EXIF and XMP is written to JPEG, PNG and WebP outputs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataMode {
+    #[default]
+    Preserve,
+    Strip,
+    // Everything except the location
+    StripGps,
+}
@@ -0,0 +1,163 @@
+use image::imageops::FilterType;
+use image::{DynamicImage, RgbImage};
+use rayon::prelude::*;
+
+use crate::error::SwiftKitError;
+
+// Gaussian window used by the reference SSIM implementation
+const WINDOW_RADIUS: usize = 5;
+const WINDOW_SIGMA: f32 = 1.5;
+const C1: f32 = (0.01 * 255.0) * (0.01 * 255.0);
+const C2: f32 = (0.03 * 255.0) * (0.03 * 255.0);
+
+#[derive(Debug, Clone, Copy)]
+pub struct Comparison {
+    pub ssim: f64,
+    // None when the images are identical
+    pub psnr: Option<f64>,
+    pub max_difference: u8,
+}
+
+pub fn compare(original: &RgbImage, processed: &RgbImage) -> Comparison {
+    let mut squared_error = 0.0;
+    let mut max_difference = 0;
+    for (a, b) in original.as_raw().iter().zip(processed.as_raw()) {
+        let difference = a.abs_diff(*b);
+        squared_error += f64::from(difference) * f64::from(difference);
+        max_difference = max_difference.max(difference);
+    }
+
+    let mse = squared_error / original.as_raw().len().max(1) as f64;
+    Comparison {
+        ssim: ssim(original, processed),
+        psnr: (mse > 0.0).then(|| 10.0 * (255.0 * 255.0 / mse).log10()),
+        max_difference,
+    }
+}
+
+// Transparent pixels are compared as they would look on a white page
+pub fn flatten(img: &DynamicImage) -> RgbImage {
+    if !img.color().has_alpha() {
+        return img.to_rgb8();
+    }
+
+    let rgba = img.to_rgba8();
+    RgbImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+        let pixel = rgba.get_pixel(x, y);
+        let alpha = f32::from(pixel[3]) / 255.0;
+        image::Rgb(std::array::from_fn(|c| {
+            (f32::from(pixel[c]) * alpha + 255.0 * (1.0 - alpha)).round() as u8
+        }))
+    })
+}
+
+// Scales the processed image to the original's size, which only makes sense
+// when both have the same aspect ratio. Returns whether it had to be resized.
+pub fn align(
+    original: &DynamicImage,
+    processed: DynamicImage,
+) -> Result<(DynamicImage, bool), SwiftKitError> {
+    if original.width() == processed.width() && original.height() == processed.height() {
+        return Ok((processed, false));
+    }
+
+    let ratio = |img: &DynamicImage| f64::from(img.width()) / f64::from(img.height());
+    if (ratio(original) - ratio(&processed)).abs() / ratio(original) > 0.01 {
+        return Err(SwiftKitError::validation(format!(
+            "The images have different aspect ratios ({}x{} and {}x{}).",
+            original.width(),
+            original.height(),
+            processed.width(),
+            processed.height()
+        )));
+    }
+
+    let resized = processed.resize_exact(original.width(), original.height(), FilterType::Lanczos3);
+    Ok((resized, true))
+}
+
+// Mean SSIM of the luma channel, 1.0 for identical images
+pub fn ssim(original: &RgbImage, processed: &RgbImage) -> f64 {
+    let (width, height) = (original.width() as usize, original.height() as usize);
+    if width == 0 || height == 0 {
+        return 1.0;
+    }
+
+    let x = luma(original);
+    let y = luma(processed);
+    let product = |a: &[f32], b: &[f32]| a.iter().zip(b).map(|(a, b)| a * b).collect::<Vec<_>>();
+
+    let mu_x = blur(&x, width, height);
+    let mu_y = blur(&y, width, height);
+    let sigma_xx = blur(&product(&x, &x), width, height);
+    let sigma_yy = blur(&product(&y, &y), width, height);
+    let sigma_xy = blur(&product(&x, &y), width, height);
+
+    let total: f64 = (0..x.len())
+        .into_par_iter()
+        .map(|i| {
+            let (mx, my) = (mu_x[i], mu_y[i]);
+            let vx = sigma_xx[i] - mx * mx;
+            let vy = sigma_yy[i] - my * my;
+            let cov = sigma_xy[i] - mx * my;
+            let score = ((2.0 * mx * my + C1) * (2.0 * cov + C2))
+                / ((mx * mx + my * my + C1) * (vx + vy + C2));
+            f64::from(score)
+        })
+        .sum();
+    total / x.len() as f64
+}
+
+fn luma(img: &RgbImage) -> Vec<f32> {
+    img.pixels()
+        .map(|pixel| {
+            0.299 * f32::from(pixel[0]) + 0.587 * f32::from(pixel[1]) + 0.114 * f32::from(pixel[2])
+        })
+        .collect()
+}
+
+fn kernel() -> [f32; 2 * WINDOW_RADIUS + 1] {
+    let mut kernel = std::array::from_fn(|i| {
+        let offset = i as f32 - WINDOW_RADIUS as f32;
+        (-offset * offset / (2.0 * WINDOW_SIGMA * WINDOW_SIGMA)).exp()
+    });
+    let sum: f32 = kernel.iter().sum();
+    kernel.iter_mut().for_each(|weight| *weight /= sum);
+    kernel
+}
+
+// Separable Gaussian blur, edges repeat the border pixels
+fn blur(values: &[f32], width: usize, height: usize) -> Vec<f32> {
+    let kernel = kernel();
+
+    let mut horizontal = vec![0.0; values.len()];
+    horizontal
+        .par_chunks_mut(width)
+        .zip(values.par_chunks(width))
+        .for_each(|(row, source)| {
+            for (x, value) in row.iter_mut().enumerate() {
+                *value = kernel
+                    .iter()
+                    .enumerate()
+                    .map(|(i, weight)| {
+                        weight * source[(x + i).saturating_sub(WINDOW_RADIUS).min(width - 1)]
+                    })
+                    .sum();
+            }
+        });
+
+    let mut blurred = vec![0.0; values.len()];
+    blurred
+        .par_chunks_mut(width)
+        .enumerate()
+        .for_each(|(y, row)| {
+            for (i, weight) in kernel.iter().enumerate() {
+                let source_y = (y + i).saturating_sub(WINDOW_RADIUS).min(height - 1);
+                let source = &horizontal[source_y * width..(source_y + 1) * width];
+                for (value, source) in row.iter_mut().zip(source) {
+                    *value += weight * source;
+                }
+            }
+        });
+    blurred
+}
@@ -0,0 +1,97 @@
+use image::{DynamicImage, GrayImage};
+use std::f32::consts::PI;
+use std::sync::LazyLock;
+
+// pHash keeps the lowest 8x8 frequencies of a 32x32 DCT
+const DCT_SIZE: usize = 32;
+const HASH_SIZE: usize = 8;
+
+static DCT_COSINES: LazyLock<Vec<f32>> = LazyLock::new(|| {
+    (0..HASH_SIZE)
+        .flat_map(|u| {
+            (0..DCT_SIZE)
+                .map(move |x| ((2 * x + 1) as f32 * u as f32 * PI / (2 * DCT_SIZE) as f32).cos())
+        })
+        .collect()
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageHash {
+    pub dhash: u64,
+    pub phash: u64,
+}
+
+impl ImageHash {
+    pub fn new(img: &DynamicImage) -> Self {
+        ImageHash {
+            dhash: dhash(
+                &img.thumbnail_exact(HASH_SIZE as u32 + 1, HASH_SIZE as u32)
+                    .to_luma8(),
+            ),
+            phash: phash(
+                &img.thumbnail_exact(DCT_SIZE as u32, DCT_SIZE as u32)
+                    .to_luma8(),
+            ),
+        }
+    }
+
+    // Mean of both Hamming distances, 0 to 64. dHash flips bits in flat areas after a
+    // resize, pHash alone lumps together images that only share their overall layout.
+    pub fn distance(&self, other: &ImageHash) -> u32 {
+        let dhash = (self.dhash ^ other.dhash).count_ones();
+        let phash = (self.phash ^ other.phash).count_ones();
+        (dhash + phash).div_ceil(2)
+    }
+}
+
+// One bit per horizontal neighbour pair of a 9x8 thumbnail, set when brightness rises
+fn dhash(gray: &GrayImage) -> u64 {
+    let mut hash = 0;
+    for y in 0..HASH_SIZE as u32 {
+        for x in 0..HASH_SIZE as u32 {
+            hash <<= 1;
+            if gray.get_pixel(x, y)[0] < gray.get_pixel(x + 1, y)[0] {
+                hash |= 1;
+            }
+        }
+    }
+    hash
+}
+
+// One bit per low frequency, set when it's above the median
+fn phash(gray: &GrayImage) -> u64 {
+    let pixels: Vec<f32> = gray
+        .as_raw()
+        .iter()
+        .map(|&value| f32::from(value))
+        .collect();
+
+    // Separable DCT-II, only the frequencies that end up in the hash are computed
+    let mut rows = vec![0.0; DCT_SIZE * HASH_SIZE];
+    for y in 0..DCT_SIZE {
+        let row = &pixels[y * DCT_SIZE..(y + 1) * DCT_SIZE];
+        for u in 0..HASH_SIZE {
+            let cosines = &DCT_COSINES[u * DCT_SIZE..(u + 1) * DCT_SIZE];
+            rows[y * HASH_SIZE + u] = row.iter().zip(cosines).map(|(p, c)| p * c).sum();
+        }
+    }
+
+    let mut coefficients = [0.0; HASH_SIZE * HASH_SIZE];
+    for v in 0..HASH_SIZE {
+        let cosines = &DCT_COSINES[v * DCT_SIZE..(v + 1) * DCT_SIZE];
+        for u in 0..HASH_SIZE {
+            coefficients[v * HASH_SIZE + u] = (0..DCT_SIZE)
+                .map(|y| rows[y * HASH_SIZE + u] * cosines[y])
+                .sum();
+        }
+    }
+
+    // The DC term is the overall brightness, it would skew the median
+    let mut sorted = coefficients[1..].to_vec();
+    sorted.sort_by(f32::total_cmp);
+    let median = sorted[sorted.len() / 2];
+
+    coefficients
+        .iter()
+        .fold(0, |hash, &value| (hash << 1) | u64::from(value > median))
+}
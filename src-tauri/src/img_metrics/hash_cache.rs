@@ -0,0 +1,113 @@
+use sqlx::{FromRow, SqlitePool};
+use std::collections::HashMap;
+
+use super::hash::ImageHash;
+use crate::error::SwiftKitError;
+
+// A file's hash stays valid while its modification time and size are unchanged
+#[derive(Debug, Clone)]
+pub struct HashedImage {
+    pub path: String,
+    // Milliseconds since the Unix epoch
+    pub mtime: i64,
+    pub size: u64,
+    // After the EXIF orientation was applied
+    pub width: u32,
+    pub height: u32,
+    pub hash: ImageHash,
+}
+
+// SQLite integers are signed, the hashes are stored with their bits reinterpreted
+#[derive(FromRow)]
+struct HashRow {
+    path: String,
+    mtime: i64,
+    size: i64,
+    width: i64,
+    height: i64,
+    dhash: i64,
+    phash: i64,
+}
+
+impl From<HashRow> for HashedImage {
+    fn from(row: HashRow) -> Self {
+        HashedImage {
+            path: row.path,
+            mtime: row.mtime,
+            size: row.size as u64,
+            width: row.width as u32,
+            height: row.height as u32,
+            hash: ImageHash {
+                dhash: row.dhash as u64,
+                phash: row.phash as u64,
+            },
+        }
+    }
+}
+
+// Every cached path starting with `folder`, keyed by path. This is a string prefix,
+// so a sibling such as `folder2` matches too.
+pub async fn load(
+    pool: &SqlitePool,
+    folder: &str,
+) -> Result<HashMap<String, HashedImage>, SwiftKitError> {
+    let rows = sqlx::query_as::<_, HashRow>(
+        "SELECT path, mtime, size, width, height, dhash, phash FROM image_hashes
+        WHERE substr(path, 1, ?) = ?",
+    )
+    .bind(folder.chars().count() as i64)
+    .bind(folder)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| SwiftKitError::internal(format!("Failed to read image hashes: {}", e)))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.path.clone(), HashedImage::from(row)))
+        .collect())
+}
+
+pub async fn store(pool: &SqlitePool, images: &[HashedImage]) -> Result<(), SwiftKitError> {
+    let to_error =
+        |e: sqlx::Error| SwiftKitError::internal(format!("Failed to cache image hashes: {}", e));
+
+    let mut tx = pool.begin().await.map_err(to_error)?;
+    for image in images {
+        sqlx::query(
+            "INSERT INTO image_hashes (path, mtime, size, width, height, dhash, phash)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(path) DO UPDATE SET
+                mtime = excluded.mtime, size = excluded.size,
+                width = excluded.width, height = excluded.height,
+                dhash = excluded.dhash, phash = excluded.phash,
+                updated_at = CURRENT_TIMESTAMP",
+        )
+        .bind(&image.path)
+        .bind(image.mtime)
+        .bind(image.size as i64)
+        .bind(i64::from(image.width))
+        .bind(i64::from(image.height))
+        .bind(image.hash.dhash as i64)
+        .bind(image.hash.phash as i64)
+        .execute(&mut *tx)
+        .await
+        .map_err(to_error)?;
+    }
+    tx.commit().await.map_err(to_error)
+}
+
+// Drops the rows of files that were deleted or renamed since they were hashed
+pub async fn remove(pool: &SqlitePool, paths: &[String]) -> Result<(), SwiftKitError> {
+    let to_error =
+        |e: sqlx::Error| SwiftKitError::internal(format!("Failed to prune image hashes: {}", e));
+
+    let mut tx = pool.begin().await.map_err(to_error)?;
+    for path in paths {
+        sqlx::query("DELETE FROM image_hashes WHERE path = ?")
+            .bind(path)
+            .execute(&mut *tx)
+            .await
+            .map_err(to_error)?;
+    }
+    tx.commit().await.map_err(to_error)
+}
@@ -0,0 +1,32 @@
+use image::{Rgb, RgbImage};
+
+// Differences are amplified so a change of 64 already reaches the top of the scale
+const GAIN: f32 = 4.0;
+
+// black, blue, red, yellow, white
+const STOPS: [[f32; 3]; 5] = [
+    [0.0, 0.0, 0.0],
+    [0.0, 0.0, 255.0],
+    [255.0, 0.0, 0.0],
+    [255.0, 255.0, 0.0],
+    [255.0, 255.0, 255.0],
+];
+
+// Largest channel difference of every pixel, coloured from black (same) to white
+pub fn heatmap(original: &RgbImage, processed: &RgbImage) -> RgbImage {
+    RgbImage::from_fn(original.width(), original.height(), |x, y| {
+        let (a, b) = (original.get_pixel(x, y), processed.get_pixel(x, y));
+        let difference = (0..3).map(|c| a[c].abs_diff(b[c])).max().unwrap_or(0);
+        color((f32::from(difference) * GAIN / 255.0).min(1.0))
+    })
+}
+
+fn color(level: f32) -> Rgb<u8> {
+    let position = level * (STOPS.len() - 1) as f32;
+    let index = (position.floor() as usize).min(STOPS.len() - 2);
+    let t = position - index as f32;
+    let (from, to) = (STOPS[index], STOPS[index + 1]);
+    Rgb(std::array::from_fn(|c| {
+        (from[c] + (to[c] - from[c]) * t).round() as u8
+    }))
+}
@@ -0,0 +1,4 @@
+pub mod compare;
+pub mod hash;
+pub mod hash_cache;
+pub mod heatmap;
@@ -0,0 +1,93 @@
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::operation::Pipeline;
+use crate::error::SwiftKitError;
+use crate::img_encoders::encode::{encode_with_metadata, open_image_with_metadata, OutputFormat};
+use crate::utils::resolve_output_path::resolve_output_path;
+
+#[derive(Debug, Serialize)]
+pub struct FileResult {
+    pub input: String,
+    pub output: Option<String>,
+    pub error: Option<SwiftKitError>,
+}
+
+// Files are processed in parallel, results keep the order of `paths`.
+// `threads` of 0 uses one thread per core.
+pub fn run(
+    paths: &[String],
+    threads: usize,
+    process: impl Fn(&Path) -> Result<PathBuf, SwiftKitError> + Sync,
+    on_done: impl Fn(&FileResult) + Sync,
+) -> Result<Vec<FileResult>, SwiftKitError> {
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .map_err(|e| SwiftKitError::internal(e.to_string()))?;
+
+    Ok(pool.install(|| {
+        paths
+            .par_iter()
+            .map(|path| {
+                let result = match process(Path::new(path)) {
+                    Ok(output) => FileResult {
+                        input: path.clone(),
+                        output: Some(output.to_string_lossy().to_string()),
+                        error: None,
+                    },
+                    Err(e) => FileResult {
+                        input: path.clone(),
+                        output: None,
+                        error: Some(e),
+                    },
+                };
+                on_done(&result);
+                result
+            })
+            .collect()
+    }))
+}
+
+// Decodes once, applies every operation in memory and encodes once
+pub fn process(
+    input_path: &Path,
+    pipeline: &Pipeline,
+    output_folder: Option<&Path>,
+) -> Result<PathBuf, SwiftKitError> {
+    if !input_path.is_file() {
+        return Err(SwiftKitError::validation("Invalid image path"));
+    }
+
+    let input_extension = input_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase())
+        .unwrap_or_default();
+    let (format, extension) = match (pipeline.format, &pipeline.extension) {
+        (Some(format), Some(extension)) => (format, extension.clone()),
+        _ => (
+            OutputFormat::from_extension(&input_extension)
+                .ok_or_else(|| SwiftKitError::validation("Unsupported file format"))?,
+            input_extension,
+        ),
+    };
+
+    let output_path = resolve_output_path(input_path, output_folder, None, Some(&extension))?;
+
+    let (img, metadata) = open_image_with_metadata(input_path)?;
+    let img = pipeline.apply(img)?;
+    let bytes = encode_with_metadata(
+        &img,
+        format,
+        &pipeline.options,
+        &metadata,
+        pipeline.metadata,
+    )?;
+    fs::write(&output_path, bytes).map_err(|e| SwiftKitError::io(e, &output_path))?;
+
+    Ok(output_path)
+}
@@ -0,0 +1,159 @@
+use image::{DynamicImage, Rgba};
+use serde::Deserialize;
+
+use crate::error::SwiftKitError;
+use crate::img_encoders::encode::OutputFormat;
+use crate::img_encoders::options::EncodeOptions;
+use crate::img_metadata::mode::MetadataMode;
+use crate::img_transforms::crop::{auto_trim, CropDetails, DEFAULT_TRIM_TOLERANCE};
+use crate::img_transforms::resize::{resize, ResizeOptions};
+use crate::img_transforms::rotate::{rotate, RotateMode};
+use crate::img_transforms::smart_crop;
+use crate::utils::parse_color::parse_color;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Operation {
+    Resize(ResizeOptions),
+    Crop(CropDetails),
+    Rotate {
+        degrees: f32,
+        #[serde(default)]
+        mode: RotateMode,
+        background: Option<String>,
+    },
+    Flip {
+        #[serde(default)]
+        horizontal: bool,
+        #[serde(default)]
+        vertical: bool,
+    },
+    Trim {
+        tolerance: Option<u8>,
+    },
+    SmartCrop {
+        width: u32,
+        height: u32,
+    },
+    Convert {
+        to: String,
+    },
+    Compress {
+        quality: u8,
+    },
+    Metadata {
+        mode: MetadataMode,
+    },
+}
+
+// Pixel operations run in order, convert and compress only change how the result is encoded
+#[derive(Debug, Clone, Default)]
+pub struct Pipeline {
+    transforms: Vec<Operation>,
+    pub format: Option<OutputFormat>,
+    pub extension: Option<String>,
+    pub options: EncodeOptions,
+    pub metadata: MetadataMode,
+}
+
+impl Pipeline {
+    pub fn new(operations: Vec<Operation>) -> Result<Self, SwiftKitError> {
+        if operations.is_empty() {
+            return Err(SwiftKitError::validation("No operations were provided."));
+        }
+
+        let mut pipeline = Pipeline::default();
+        for operation in operations {
+            match operation {
+                Operation::Resize(ref options) => {
+                    options.validate()?;
+                    pipeline.transforms.push(operation);
+                }
+                Operation::Crop(ref details) => {
+                    if details.width <= 0.0 || details.height <= 0.0 {
+                        return Err(SwiftKitError::validation("Crop size can't be zero"));
+                    }
+                    pipeline.transforms.push(operation);
+                }
+                Operation::Rotate {
+                    degrees,
+                    ref background,
+                    ..
+                } => {
+                    if !degrees.is_finite() {
+                        return Err(SwiftKitError::validation("Invalid rotation angle"));
+                    }
+                    background.as_deref().map(parse_color).transpose()?;
+                    pipeline.transforms.push(operation);
+                }
+                Operation::SmartCrop { width, height } => {
+                    if width == 0 || height == 0 {
+                        return Err(SwiftKitError::validation("Crop size can't be zero"));
+                    }
+                    pipeline.transforms.push(operation);
+                }
+                Operation::Flip { .. } | Operation::Trim { .. } => {
+                    pipeline.transforms.push(operation);
+                }
+                Operation::Convert { to } => {
+                    let format = OutputFormat::from_extension(&to)
+                        .ok_or_else(|| SwiftKitError::validation("Unsupported output format"))?;
+                    pipeline.format = Some(format);
+                    pipeline.extension = Some(to.to_lowercase());
+                }
+                Operation::Compress { quality } => {
+                    pipeline.options.jpeg_quality = Some(quality);
+                    pipeline.options.webp_quality = Some(quality);
+                    pipeline.options.avif_quality = Some(quality);
+                    pipeline.options.png_quality = Some(quality);
+                }
+                Operation::Metadata { mode } => pipeline.metadata = mode,
+            }
+        }
+
+        pipeline.options.validate()?;
+        Ok(pipeline)
+    }
+
+    pub fn apply(&self, mut img: DynamicImage) -> Result<DynamicImage, SwiftKitError> {
+        for operation in &self.transforms {
+            img = match *operation {
+                Operation::Resize(ref options) => resize(&img, options)?,
+                Operation::Crop(ref details) => {
+                    let (x, y, width, height) = details.rect(img.width(), img.height())?;
+                    img.crop_imm(x, y, width, height)
+                }
+                Operation::Rotate {
+                    degrees,
+                    mode,
+                    ref background,
+                } => {
+                    let background = match background.as_deref() {
+                        Some(color) => parse_color(color)?,
+                        None => Rgba([0, 0, 0, 0]),
+                    };
+                    rotate(&img, degrees, mode, background)
+                }
+                Operation::Flip {
+                    horizontal,
+                    vertical,
+                } => {
+                    let img = if horizontal { img.fliph() } else { img };
+                    if vertical {
+                        img.flipv()
+                    } else {
+                        img
+                    }
+                }
+                Operation::Trim { tolerance } => {
+                    auto_trim(img, tolerance.unwrap_or(DEFAULT_TRIM_TOLERANCE))
+                }
+                Operation::SmartCrop { width, height } => smart_crop::crop(&img, width, height).1,
+                Operation::Convert { .. }
+                | Operation::Compress { .. }
+                | Operation::Metadata { .. } => img,
+            };
+        }
+        Ok(img)
+    }
+}
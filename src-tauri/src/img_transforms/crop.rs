@@ -0,0 +1,190 @@
+use image::{DynamicImage, Rgba, RgbaImage};
+use serde::Deserialize;
+
+use super::gravity::Gravity;
+use super::rotate::{rotate, RotateMode};
+use crate::error::SwiftKitError;
+use crate::utils::parse_color::parse_color;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum CropUnit {
+    #[default]
+    #[serde(rename = "px")]
+    Pixels,
+    #[serde(rename = "%")]
+    Percent,
+}
+
+// Same shape as react-image-crop's `Crop`, which sends fractional pixels
+#[derive(Debug, Clone, Deserialize)]
+pub struct CropDetails {
+    pub height: f32,
+    pub width: f32,
+    pub x: f32,
+    pub y: f32,
+    #[serde(default)]
+    pub unit: CropUnit,
+}
+
+impl CropDetails {
+    // (x, y, width, height) inside a `width` x `height` image. The edges are rounded,
+    // so a rectangle that only overshoots by float noise still fits.
+    pub fn rect(&self, width: u32, height: u32) -> Result<(u32, u32, u32, u32), SwiftKitError> {
+        let (scale_x, scale_y) = match self.unit {
+            CropUnit::Pixels => (1.0, 1.0),
+            CropUnit::Percent => (width as f32 / 100.0, height as f32 / 100.0),
+        };
+        let left = (self.x * scale_x).round();
+        let top = (self.y * scale_y).round();
+        let right = ((self.x + self.width) * scale_x).round();
+        let bottom = ((self.y + self.height) * scale_y).round();
+
+        if !(left.is_finite() && top.is_finite() && right.is_finite() && bottom.is_finite()) {
+            return Err(SwiftKitError::validation("Invalid crop rectangle"));
+        }
+        if left < 0.0 || top < 0.0 || right > width as f32 || bottom > height as f32 {
+            return Err(SwiftKitError::validation(
+                "Crop dimensions exceed image dimensions",
+            ));
+        }
+        if right <= left || bottom <= top {
+            return Err(SwiftKitError::validation("Crop size can't be zero"));
+        }
+        Ok((
+            left as u32,
+            top as u32,
+            (right - left) as u32,
+            (bottom - top) as u32,
+        ))
+    }
+}
+
+// Applied in order: the rectangle shrunk to the aspect ratio, auto trim, rotation and flips
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct CropOptions {
+    // width / height, the crop shrinks to it and is placed by `gravity`
+    pub aspect_ratio: Option<f32>,
+    pub gravity: Gravity,
+    pub auto_trim: bool,
+    // Largest channel difference from the border colour that still counts as margin
+    pub trim_tolerance: Option<u8>,
+    // Clockwise, in degrees
+    pub rotate: f32,
+    pub rotate_mode: RotateMode,
+    // Fill for the corners uncovered by rotation, transparent when not set
+    pub background: Option<String>,
+    pub flip_horizontal: bool,
+    pub flip_vertical: bool,
+}
+
+impl CropOptions {
+    pub fn validate(&self) -> Result<(), SwiftKitError> {
+        if self
+            .aspect_ratio
+            .is_some_and(|ratio| !(ratio.is_finite() && ratio > 0.0))
+        {
+            return Err(SwiftKitError::validation(
+                "Aspect ratio must be larger than 0",
+            ));
+        }
+        if !self.rotate.is_finite() {
+            return Err(SwiftKitError::validation("Invalid rotation angle"));
+        }
+        self.background.as_deref().map(parse_color).transpose()?;
+        Ok(())
+    }
+}
+
+pub const DEFAULT_TRIM_TOLERANCE: u8 = 10;
+
+pub fn crop(
+    img: DynamicImage,
+    details: Option<&CropDetails>,
+    options: &CropOptions,
+) -> Result<DynamicImage, SwiftKitError> {
+    options.validate()?;
+    let mut img = img;
+
+    let (x, y, width, height) = match details {
+        Some(details) => details.rect(img.width(), img.height())?,
+        None => (0, 0, img.width(), img.height()),
+    };
+    let (x, y, width, height) = match options.aspect_ratio {
+        Some(ratio) => {
+            let inner = fit_aspect(width, height, ratio);
+            let (offset_x, offset_y) = options.gravity.offset((width, height), inner);
+            (x + offset_x, y + offset_y, inner.0, inner.1)
+        }
+        None => (x, y, width, height),
+    };
+    if (x, y, width, height) != (0, 0, img.width(), img.height()) {
+        img = img.crop_imm(x, y, width, height);
+    }
+    if options.auto_trim {
+        img = auto_trim(
+            img,
+            options.trim_tolerance.unwrap_or(DEFAULT_TRIM_TOLERANCE),
+        );
+    }
+
+    if options.rotate != 0.0 {
+        let background = match options.background.as_deref() {
+            Some(color) => parse_color(color)?,
+            None => Rgba([0, 0, 0, 0]),
+        };
+        img = rotate(&img, options.rotate, options.rotate_mode, background);
+    }
+    if options.flip_horizontal {
+        img = img.fliph();
+    }
+    if options.flip_vertical {
+        img = img.flipv();
+    }
+    Ok(img)
+}
+
+// Largest `ratio` shaped box inside `width` x `height`
+fn fit_aspect(width: u32, height: u32, ratio: f32) -> (u32, u32) {
+    if width as f32 / height as f32 > ratio {
+        let fitted = (height as f32 * ratio).round() as u32;
+        (fitted.clamp(1, width), height)
+    } else {
+        let fitted = (width as f32 / ratio).round() as u32;
+        (width, fitted.clamp(1, height))
+    }
+}
+
+// Crops away margins of the top-left pixel's colour, or transparent ones when that
+// pixel is transparent. Images that are all margin are returned unchanged.
+pub fn auto_trim(img: DynamicImage, tolerance: u8) -> DynamicImage {
+    match trim_bounds(&img.to_rgba8(), tolerance) {
+        Some((x, y, width, height)) if (width, height) != (img.width(), img.height()) => {
+            img.crop_imm(x, y, width, height)
+        }
+        _ => img,
+    }
+}
+
+pub fn trim_bounds(rgba: &RgbaImage, tolerance: u8) -> Option<(u32, u32, u32, u32)> {
+    let (width, height) = rgba.dimensions();
+    let reference = *rgba.get_pixel_checked(0, 0)?;
+    let is_margin = |x: u32, y: u32| {
+        let pixel = rgba.get_pixel(x, y);
+        if reference[3] <= tolerance {
+            pixel[3] <= tolerance
+        } else {
+            (0..4).all(|channel| pixel[channel].abs_diff(reference[channel]) <= tolerance)
+        }
+    };
+
+    let row_is_margin = |y: u32| (0..width).all(|x| is_margin(x, y));
+    let top = (0..height).find(|&y| !row_is_margin(y))?;
+    let bottom = (top..height).rev().find(|&y| !row_is_margin(y))? + 1;
+
+    let column_is_margin = |x: u32| (top..bottom).all(|y| is_margin(x, y));
+    let left = (0..width).find(|&x| !column_is_margin(x))?;
+    let right = (left..width).rev().find(|&x| !column_is_margin(x))? + 1;
+
+    Some((left, top, right - left, bottom - top))
+}
@@ -0,0 +1,85 @@
+use ab_glyph::{point, Font as _, FontVec, Glyph, PxScale, Rect, ScaleFont};
+use image::{GrayImage, Luma};
+
+use crate::error::SwiftKitError;
+
+// TrueType or OpenType (CFF) outlines, collections use their first font
+pub struct Font(FontVec);
+
+impl Font {
+    pub fn parse(data: Vec<u8>) -> Result<Self, SwiftKitError> {
+        FontVec::try_from_vec(data)
+            .map(Font)
+            .map_err(|_| SwiftKitError::validation("Unsupported or corrupt font file"))
+    }
+
+    // Coverage of `text` at `size` pixels per em, cropped to the ink, lines are split
+    // on '\n'. The ink's area is checked against `max_pixels` before allocating.
+    pub fn render(
+        &self,
+        text: &str,
+        size: f32,
+        max_pixels: u64,
+    ) -> Result<GrayImage, SwiftKitError> {
+        let empty = || SwiftKitError::validation("The watermark text has nothing to draw.");
+        let font = self.0.as_scaled(PxScale::from(size));
+        let line_height = font.height() + font.line_gap();
+
+        let mut outlines = Vec::new();
+        for (row, line) in text.lines().enumerate() {
+            let baseline = font.ascent() + row as f32 * line_height;
+            let mut pen = 0.0;
+            let mut previous: Option<Glyph> = None;
+            for c in line.chars() {
+                let mut glyph = font.scaled_glyph(c);
+                if let Some(previous) = &previous {
+                    pen += font.kern(previous.id, glyph.id);
+                }
+                glyph.position = point(pen, baseline);
+                pen += font.h_advance(glyph.id);
+                if let Some(outline) = font.outline_glyph(glyph.clone()) {
+                    outlines.push(outline);
+                }
+                previous = Some(glyph);
+            }
+        }
+
+        let bounds = outlines
+            .iter()
+            .map(|outline| outline.px_bounds())
+            .reduce(|a, b| Rect {
+                min: point(a.min.x.min(b.min.x), a.min.y.min(b.min.y)),
+                max: point(a.max.x.max(b.max.x), a.max.y.max(b.max.y)),
+            })
+            .ok_or_else(empty)?;
+        let width = bounds.width().ceil() as u64;
+        let height = bounds.height().ceil() as u64;
+        if width * height > max_pixels {
+            return Err(SwiftKitError::validation(
+                "The watermark text is too large, use a smaller font size or shorter lines.",
+            ));
+        }
+        let (width, height) = (width as u32, height as u32);
+
+        let mut image = GrayImage::new(width, height);
+        for outline in &outlines {
+            let glyph = outline.px_bounds();
+            let left = (glyph.min.x - bounds.min.x) as u32;
+            let top = (glyph.min.y - bounds.min.y) as u32;
+            outline.draw(|x, y, coverage| {
+                let (x, y) = (left + x, top + y);
+                if x < width && y < height {
+                    // Overlapping glyphs keep the stronger coverage instead of adding up
+                    let pixel = image.get_pixel_mut(x, y);
+                    let value = (coverage.clamp(0.0, 1.0) * 255.0).round() as u8;
+                    *pixel = Luma([pixel[0].max(value)]);
+                }
+            });
+        }
+
+        if image.pixels().all(|pixel| pixel[0] == 0) {
+            return Err(empty());
+        }
+        Ok(image)
+    }
+}
@@ -0,0 +1,37 @@
+use serde::Deserialize;
+
+// Where content is anchored inside a larger area
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Gravity {
+    #[default]
+    Center,
+    North,
+    South,
+    East,
+    West,
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
+}
+
+impl Gravity {
+    // Top-left corner of an `inner` sized box placed inside `outer`
+    pub fn offset(&self, outer: (u32, u32), inner: (u32, u32)) -> (u32, u32) {
+        let free_x = outer.0.saturating_sub(inner.0);
+        let free_y = outer.1.saturating_sub(inner.1);
+        let (x, y) = match self {
+            Gravity::Center => (free_x / 2, free_y / 2),
+            Gravity::North => (free_x / 2, 0),
+            Gravity::South => (free_x / 2, free_y),
+            Gravity::East => (free_x, free_y / 2),
+            Gravity::West => (0, free_y / 2),
+            Gravity::NorthEast => (free_x, 0),
+            Gravity::NorthWest => (0, 0),
+            Gravity::SouthEast => (free_x, free_y),
+            Gravity::SouthWest => (0, free_y),
+        };
+        (x, y)
+    }
+}
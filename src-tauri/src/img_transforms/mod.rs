@@ -0,0 +1,7 @@
+pub mod crop;
+pub mod font;
+pub mod gravity;
+pub mod resize;
+pub mod rotate;
+pub mod smart_crop;
+pub mod watermark;
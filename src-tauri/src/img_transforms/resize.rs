@@ -0,0 +1,179 @@
+use image::imageops::{self, FilterType};
+use image::{DynamicImage, GenericImageView, RgbaImage};
+use serde::Deserialize;
+
+use super::gravity::Gravity;
+use crate::error::SwiftKitError;
+use crate::utils::parse_color::parse_color;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResizeMode {
+    // Exactly `width` x `height`, a missing side keeps the aspect ratio
+    #[default]
+    Exact,
+    // Fits inside the box
+    Contain,
+    // Fills the box and crops the overflow at `gravity`
+    Cover,
+    // Fits inside the box and fills the rest with `background`
+    Pad,
+    Percentage,
+    LongestEdge,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResampleFilter {
+    Nearest,
+    Bilinear,
+    Bicubic,
+    Gaussian,
+    #[default]
+    Lanczos3,
+}
+
+impl From<ResampleFilter> for FilterType {
+    fn from(filter: ResampleFilter) -> Self {
+        match filter {
+            ResampleFilter::Nearest => FilterType::Nearest,
+            ResampleFilter::Bilinear => FilterType::Triangle,
+            ResampleFilter::Bicubic => FilterType::CatmullRom,
+            ResampleFilter::Gaussian => FilterType::Gaussian,
+            ResampleFilter::Lanczos3 => FilterType::Lanczos3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ResizeOptions {
+    pub mode: ResizeMode,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub percent: Option<f32>,
+    pub longest_edge: Option<u32>,
+    pub gravity: Gravity,
+    // Transparent when not set
+    pub background: Option<String>,
+    // Images already smaller than the target keep their size
+    pub no_upscale: bool,
+    pub filter: ResampleFilter,
+}
+
+impl ResizeOptions {
+    pub fn validate(&self) -> Result<(), SwiftKitError> {
+        let width = self.width.filter(|width| *width > 0);
+        let height = self.height.filter(|height| *height > 0);
+
+        match self.mode {
+            ResizeMode::Exact if width.is_none() && height.is_none() => Err(
+                SwiftKitError::validation("At least one of width or height must be provided"),
+            ),
+            ResizeMode::Contain | ResizeMode::Cover | ResizeMode::Pad
+                if width.is_none() || height.is_none() =>
+            {
+                Err(SwiftKitError::validation(
+                    "Both width and height must be provided",
+                ))
+            }
+            ResizeMode::Percentage
+                if !self
+                    .percent
+                    .is_some_and(|percent| percent > 0.0 && percent.is_finite()) =>
+            {
+                Err(SwiftKitError::validation("Percentage must be above 0"))
+            }
+            ResizeMode::LongestEdge if self.longest_edge.unwrap_or(0) == 0 => {
+                Err(SwiftKitError::validation("Longest edge must be provided"))
+            }
+            ResizeMode::Pad => self
+                .background
+                .as_deref()
+                .map_or(Ok(()), |color| parse_color(color).map(|_| ())),
+            _ => Ok(()),
+        }
+    }
+}
+
+pub fn resize(img: &DynamicImage, options: &ResizeOptions) -> Result<DynamicImage, SwiftKitError> {
+    options.validate()?;
+
+    let (source_width, source_height) = img.dimensions();
+    let (box_width, box_height) = (options.width.unwrap_or(0), options.height.unwrap_or(0));
+    let ratio_x = f64::from(box_width) / f64::from(source_width);
+    let ratio_y = f64::from(box_height) / f64::from(source_height);
+    let filter = FilterType::from(options.filter);
+
+    let scale = match options.mode {
+        ResizeMode::Exact => return Ok(exact(img, options)),
+        ResizeMode::Contain | ResizeMode::Pad => ratio_x.min(ratio_y),
+        ResizeMode::Cover => ratio_x.max(ratio_y),
+        ResizeMode::Percentage => f64::from(options.percent.unwrap_or(100.0)) / 100.0,
+        ResizeMode::LongestEdge => {
+            f64::from(options.longest_edge.unwrap_or(0))
+                / f64::from(source_width.max(source_height))
+        }
+    };
+    let scale = if options.no_upscale {
+        scale.min(1.0)
+    } else {
+        scale
+    };
+    let scaled = |side: u32| ((f64::from(side) * scale).round() as u32).max(1);
+    let resized = img.resize_exact(scaled(source_width), scaled(source_height), filter);
+
+    match options.mode {
+        ResizeMode::Cover => {
+            let inner = (
+                box_width.min(resized.width()),
+                box_height.min(resized.height()),
+            );
+            let (x, y) = options.gravity.offset(resized.dimensions(), inner);
+            Ok(resized.crop_imm(x, y, inner.0, inner.1))
+        }
+        ResizeMode::Pad => {
+            let background = match options.background.as_deref() {
+                Some(color) => parse_color(color)?,
+                None => image::Rgba([0, 0, 0, 0]),
+            };
+            let mut canvas = RgbaImage::from_pixel(box_width, box_height, background);
+            let (x, y) = options
+                .gravity
+                .offset((box_width, box_height), resized.dimensions());
+            imageops::overlay(&mut canvas, &resized.to_rgba8(), i64::from(x), i64::from(y));
+            Ok(DynamicImage::ImageRgba8(canvas))
+        }
+        _ => Ok(resized),
+    }
+}
+
+fn exact(img: &DynamicImage, options: &ResizeOptions) -> DynamicImage {
+    let (source_width, source_height) = img.dimensions();
+    let scaled = |from: u32, to: u32, other: u32| {
+        ((f64::from(other) * f64::from(to)) / f64::from(from))
+            .round()
+            .max(1.0) as u32
+    };
+    // Sides are capped separately, an exact size may change the aspect ratio anyway
+    let capped = |side: Option<u32>, source: u32| {
+        side.filter(|side| *side > 0).map(|side| {
+            if options.no_upscale {
+                side.min(source)
+            } else {
+                side
+            }
+        })
+    };
+
+    let (width, height) = match (
+        capped(options.width, source_width),
+        capped(options.height, source_height),
+    ) {
+        (Some(width), Some(height)) => (width, height),
+        (Some(width), None) => (width, scaled(source_width, width, source_height)),
+        (None, Some(height)) => (scaled(source_height, height, source_width), height),
+        (None, None) => (source_width, source_height),
+    };
+    img.resize_exact(width, height, options.filter.into())
+}
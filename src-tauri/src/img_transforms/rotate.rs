@@ -0,0 +1,146 @@
+use image::{DynamicImage, Rgba, RgbaImage};
+use rayon::prelude::*;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RotateMode {
+    // Grows the canvas so no pixel is lost, the new corners get the background
+    #[default]
+    Expand,
+    // Keeps the largest upright rectangle that only holds image pixels, for straightening
+    CropToFit,
+}
+
+// Clockwise by `degrees`. Quarter turns are exact, other angles are resampled bilinearly.
+pub fn rotate(
+    img: &DynamicImage,
+    degrees: f32,
+    mode: RotateMode,
+    background: Rgba<u8>,
+) -> DynamicImage {
+    let degrees = degrees.rem_euclid(360.0);
+    let quarters = (degrees / 90.0).round();
+    if (degrees - quarters * 90.0).abs() < 0.01 {
+        return match quarters as u32 % 4 {
+            1 => img.rotate90(),
+            2 => img.rotate180(),
+            3 => img.rotate270(),
+            _ => img.clone(),
+        };
+    }
+
+    let radians = degrees.to_radians();
+    let rotated = expanded(&img.to_rgba8(), radians, background);
+    match mode {
+        RotateMode::Expand => DynamicImage::ImageRgba8(rotated),
+        RotateMode::CropToFit => {
+            let (width, height) = inscribed(img.width(), img.height(), radians);
+            let x = rotated.width().saturating_sub(width) / 2;
+            let y = rotated.height().saturating_sub(height) / 2;
+            DynamicImage::ImageRgba8(rotated).crop_imm(x, y, width, height)
+        }
+    }
+}
+
+fn expanded(source: &RgbaImage, radians: f32, background: Rgba<u8>) -> RgbaImage {
+    let (width, height) = (source.width() as f32, source.height() as f32);
+    let (sin, cos) = radians.sin_cos();
+    // The small bias keeps float noise from adding a whole row or column
+    let side = |length: f32| (length - 0.001).ceil().max(1.0) as u32;
+    let out_width = side(width * cos.abs() + height * sin.abs());
+    let out_height = side(width * sin.abs() + height * cos.abs());
+
+    let (center_x, center_y) = (width / 2.0, height / 2.0);
+    let (out_center_x, out_center_y) = (out_width as f32 / 2.0, out_height as f32 / 2.0);
+
+    let mut rotated = RgbaImage::new(out_width, out_height);
+    rotated
+        .par_chunks_mut(out_width as usize * 4)
+        .enumerate()
+        .for_each(|(y, row)| {
+            let dy = y as f32 + 0.5 - out_center_y;
+            for (x, pixel) in row.chunks_mut(4).enumerate() {
+                let dx = x as f32 + 0.5 - out_center_x;
+                // Inverse rotation, from the output pixel back into the source
+                let source_x = cos * dx + sin * dy + center_x - 0.5;
+                let source_y = -sin * dx + cos * dy + center_y - 0.5;
+                pixel.copy_from_slice(&sample(source, source_x, source_y, background));
+            }
+        });
+    rotated
+}
+
+// Bilinear with premultiplied alpha, so transparent neighbours don't darken the edges
+fn sample(source: &RgbaImage, x: f32, y: f32, background: Rgba<u8>) -> [u8; 4] {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let mut color = [0f32; 3];
+    let mut alpha = 0f32;
+
+    for (dx, dy, weight) in [
+        (0, 0, (1.0 - fx) * (1.0 - fy)),
+        (1, 0, fx * (1.0 - fy)),
+        (0, 1, (1.0 - fx) * fy),
+        (1, 1, fx * fy),
+    ] {
+        let (px, py) = (x0 as i64 + dx, y0 as i64 + dy);
+        let inside =
+            px >= 0 && py >= 0 && px < i64::from(source.width()) && py < i64::from(source.height());
+        let pixel = if inside {
+            *source.get_pixel(px as u32, py as u32)
+        } else {
+            background
+        };
+
+        let coverage = f32::from(pixel[3]) / 255.0 * weight;
+        for (channel, value) in color.iter_mut().enumerate() {
+            *value += f32::from(pixel[channel]) * coverage;
+        }
+        alpha += coverage;
+    }
+
+    if alpha <= 0.0 {
+        return [0, 0, 0, 0];
+    }
+    [
+        (color[0] / alpha).round() as u8,
+        (color[1] / alpha).round() as u8,
+        (color[2] / alpha).round() as u8,
+        (alpha * 255.0).round() as u8,
+    ]
+}
+
+// Largest axis-aligned rectangle inside a `width` x `height` rectangle rotated by `radians`
+fn inscribed(width: u32, height: u32, radians: f32) -> (u32, u32) {
+    let (width, height) = (width as f32, height as f32);
+    let (sin, cos) = (radians.sin().abs(), radians.cos().abs());
+    let width_is_longer = width >= height;
+    let (long, short) = if width_is_longer {
+        (width, height)
+    } else {
+        (height, width)
+    };
+
+    let (fit_width, fit_height) = if short <= 2.0 * sin * cos * long || (sin - cos).abs() < 1e-6 {
+        // Half constrained, two corners of the crop touch the longer sides
+        let half = 0.5 * short;
+        if width_is_longer {
+            (half / sin, half / cos)
+        } else {
+            (half / cos, half / sin)
+        }
+    } else {
+        let cos_2a = cos * cos - sin * sin;
+        (
+            (width * cos - height * sin) / cos_2a,
+            (height * cos - width * sin) / cos_2a,
+        )
+    };
+
+    // A pixel off every side, the resampled edge is still partly background
+    (
+        ((fit_width - 2.0).floor() as u32).max(1),
+        ((fit_height - 2.0).floor() as u32).max(1),
+    )
+}
@@ -0,0 +1,216 @@
+use image::imageops::FilterType;
+use image::{DynamicImage, RgbaImage};
+use rayon::prelude::*;
+use serde::Serialize;
+
+// In source pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Rect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+// Longer side of the downscaled copy the heuristics run on
+const ANALYSIS_SIZE: u32 = 256;
+const EDGE_WEIGHT: f32 = 1.0;
+const ENTROPY_WEIGHT: f32 = 0.6;
+const SKIN_WEIGHT: f32 = 1.8;
+// Salient content sliced by the window's edges counts against it this many times
+const CUT_PENALTY: f32 = 2.0;
+
+// Crops the most interesting `width` x `height` shaped part and scales it to that size
+pub fn crop(img: &DynamicImage, width: u32, height: u32) -> (Rect, DynamicImage) {
+    let rect = find(img, width, height);
+    let cropped = img.crop_imm(rect.x, rect.y, rect.width, rect.height);
+    let scaled = if (rect.width, rect.height) == (width, height) {
+        cropped
+    } else {
+        cropped.resize_exact(width, height, FilterType::Lanczos3)
+    };
+    (rect, scaled)
+}
+
+// The largest window with the target aspect ratio always spans one whole axis,
+// so only its position along the other axis is searched
+pub fn find(img: &DynamicImage, width: u32, height: u32) -> Rect {
+    let (img_width, img_height) = (img.width(), img.height());
+    let ratio = width as f32 / height as f32;
+    let (window_width, window_height) = if img_width as f32 / img_height as f32 > ratio {
+        (
+            ((img_height as f32 * ratio).round() as u32).clamp(1, img_width),
+            img_height,
+        )
+    } else {
+        (
+            img_width,
+            ((img_width as f32 / ratio).round() as u32).clamp(1, img_height),
+        )
+    };
+    let full = Rect {
+        x: 0,
+        y: 0,
+        width: window_width,
+        height: window_height,
+    };
+    if (window_width, window_height) == (img_width, img_height) {
+        return full;
+    }
+
+    let scale = (ANALYSIS_SIZE as f32 / img_width.max(img_height) as f32).min(1.0);
+    let analysis_width = ((img_width as f32 * scale).round() as u32).max(1);
+    let analysis_height = ((img_height as f32 * scale).round() as u32).max(1);
+    let small = img
+        .resize_exact(analysis_width, analysis_height, FilterType::Triangle)
+        .to_rgba8();
+    let saliency = saliency(&small);
+
+    let horizontal = window_width < img_width;
+    let profile: Vec<f32> = if horizontal {
+        (0..analysis_width as usize)
+            .map(|x| {
+                (0..analysis_height as usize)
+                    .map(|y| saliency[y * analysis_width as usize + x])
+                    .sum()
+            })
+            .collect()
+    } else {
+        saliency
+            .chunks(analysis_width as usize)
+            .map(|row| row.iter().sum())
+            .collect()
+    };
+
+    let (source_length, window_length) = if horizontal {
+        (img_width, window_width)
+    } else {
+        (img_height, window_height)
+    };
+    let length = profile.len();
+    let span = ((window_length as f32 * scale).round() as usize).clamp(1, length);
+    let start = (0..=length - span)
+        .max_by(|&a, &b| score(&profile, a, span).total_cmp(&score(&profile, b, span)))
+        .unwrap_or(0);
+
+    let offset = ((start as f32 / scale).round() as u32).min(source_length - window_length);
+    if horizontal {
+        Rect { x: offset, ..full }
+    } else {
+        Rect { y: offset, ..full }
+    }
+}
+
+// Content near the window's centre counts more, cuts through content count against it
+fn score(profile: &[f32], start: usize, span: usize) -> f32 {
+    let half = span as f32 / 2.0;
+    let inside: f32 = profile[start..start + span]
+        .iter()
+        .enumerate()
+        .map(|(i, value)| {
+            let distance = (i as f32 + 0.5 - half) / half;
+            value * (1.0 - 0.5 * distance * distance)
+        })
+        .sum();
+
+    let mut cut = 0.0;
+    if start > 0 {
+        cut += profile[start];
+    }
+    if start + span < profile.len() {
+        cut += profile[start + span - 1];
+    }
+    inside - CUT_PENALTY * cut
+}
+
+// Per pixel interest from edges, local entropy and skin tones, weighted by opacity
+fn saliency(rgba: &RgbaImage) -> Vec<f32> {
+    let (width, height) = (rgba.width() as usize, rgba.height() as usize);
+    let luma: Vec<f32> = rgba
+        .pixels()
+        .map(|pixel| {
+            (0.299 * f32::from(pixel[0])
+                + 0.587 * f32::from(pixel[1])
+                + 0.114 * f32::from(pixel[2]))
+                / 255.0
+        })
+        .collect();
+    let at = |x: isize, y: isize| {
+        let x = x.clamp(0, width as isize - 1) as usize;
+        let y = y.clamp(0, height as isize - 1) as usize;
+        luma[y * width + x]
+    };
+
+    let mut saliency = vec![0.0; width * height];
+    saliency
+        .par_chunks_mut(width)
+        .enumerate()
+        .for_each(|(y, row)| {
+            let y = y as isize;
+            for (x, value) in row.iter_mut().enumerate() {
+                let x = x as isize;
+                let center = at(x, y);
+                let laplacian =
+                    4.0 * center - at(x - 1, y) - at(x + 1, y) - at(x, y - 1) - at(x, y + 1);
+                let edge = (laplacian.abs() * 4.0).min(1.0);
+
+                let pixel = rgba.get_pixel(x as u32, y as u32);
+                *value = (EDGE_WEIGHT * edge
+                    + ENTROPY_WEIGHT * entropy(&at, x, y)
+                    + SKIN_WEIGHT * skin(pixel.0, center))
+                    * f32::from(pixel[3])
+                    / 255.0;
+            }
+        });
+    saliency
+}
+
+// Shannon entropy of a 16 bin luma histogram around the pixel, scaled to 0..1
+fn entropy(at: &impl Fn(isize, isize) -> f32, x: isize, y: isize) -> f32 {
+    const RADIUS: isize = 4;
+    let mut bins = [0u32; 16];
+    for dy in -RADIUS..=RADIUS {
+        for dx in -RADIUS..=RADIUS {
+            bins[((at(x + dx, y + dy) * 15.0).round() as usize).min(15)] += 1;
+        }
+    }
+
+    let total = ((2 * RADIUS + 1) * (2 * RADIUS + 1)) as f32;
+    let bits: f32 = bins
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f32 / total;
+            -p * p.log2()
+        })
+        .sum();
+    bits / 4.0
+}
+
+// How close the colour's direction is to a typical skin tone, ignoring very dark pixels
+fn skin(pixel: [u8; 4], lightness: f32) -> f32 {
+    const SKIN: [f32; 3] = [0.78, 0.57, 0.44];
+    const THRESHOLD: f32 = 0.8;
+
+    let rgb = [
+        f32::from(pixel[0]),
+        f32::from(pixel[1]),
+        f32::from(pixel[2]),
+    ];
+    let magnitude = rgb.iter().map(|c| c * c).sum::<f32>().sqrt();
+    let skin_magnitude = SKIN.iter().map(|c| c * c).sum::<f32>().sqrt();
+    if magnitude == 0.0 || !(0.2..=1.0).contains(&lightness) {
+        return 0.0;
+    }
+
+    let distance = (0..3)
+        .map(|c| (rgb[c] / magnitude - SKIN[c] / skin_magnitude).powi(2))
+        .sum::<f32>()
+        .sqrt();
+    let skinness = 1.0 - distance;
+    if skinness > THRESHOLD {
+        (skinness - THRESHOLD) / (1.0 - THRESHOLD)
+    } else {
+        0.0
+    }
+}
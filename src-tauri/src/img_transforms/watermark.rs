@@ -0,0 +1,191 @@
+use image::imageops::{self, FilterType};
+use image::{DynamicImage, Rgba, RgbaImage};
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::fs;
+use std::path::Path;
+
+use super::font::Font;
+use super::gravity::Gravity;
+use crate::error::SwiftKitError;
+use crate::img_encoders::encode::open_image;
+use crate::utils::parse_color::parse_color;
+
+const DEFAULT_OPACITY: f32 = 0.5;
+const DEFAULT_FONT_SIZE: f32 = 32.0;
+const MAX_FONT_SIZE: f32 = 1000.0;
+// 16 megapixels, 64 MB once coloured
+const MAX_TEXT_PIXELS: u64 = 16_000_000;
+const DEFAULT_LOGO_SCALE: f32 = 0.2;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Mark {
+    Text {
+        text: String,
+        // TrueType or OpenType font file
+        font_path: String,
+        // Pixels per em
+        size: Option<f32>,
+        // White when not set
+        color: Option<String>,
+    },
+    Image {
+        path: String,
+        // Width as a fraction of the watermarked image's width
+        scale: Option<f32>,
+    },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WatermarkOptions {
+    pub mark: Mark,
+    // 0 is invisible, 1 is as drawn
+    pub opacity: Option<f32>,
+    // Bottom right when not set
+    pub position: Option<Gravity>,
+    // Repeats the mark over the whole image, `margin` is then also the gap between copies
+    #[serde(default)]
+    pub tiled: bool,
+    // Pixels kept free along the image's edges
+    #[serde(default)]
+    pub margin: u32,
+}
+
+enum Stamp {
+    Ready(RgbaImage),
+    // Sized per image, since batches mix resolutions
+    Logo { image: RgbaImage, scale: f32 },
+}
+
+// Options with the font rendered or the logo decoded, ready for any number of images
+pub struct Watermark {
+    stamp: Stamp,
+    position: Gravity,
+    tiled: bool,
+    margin: u32,
+}
+
+impl Watermark {
+    pub fn new(options: &WatermarkOptions) -> Result<Self, SwiftKitError> {
+        let opacity = options.opacity.unwrap_or(DEFAULT_OPACITY);
+        if !(0.0..=1.0).contains(&opacity) {
+            return Err(SwiftKitError::validation(
+                "Opacity must be between 0 and 1.",
+            ));
+        }
+
+        let stamp = match &options.mark {
+            Mark::Text {
+                text,
+                font_path,
+                size,
+                color,
+            } => {
+                let size = size.unwrap_or(DEFAULT_FONT_SIZE);
+                if !(size > 0.0 && size <= MAX_FONT_SIZE) {
+                    return Err(SwiftKitError::validation(format!(
+                        "Font size must be between 0 and {}.",
+                        MAX_FONT_SIZE
+                    )));
+                }
+                let color = match color.as_deref() {
+                    Some(color) => parse_color(color)?,
+                    None => Rgba([255, 255, 255, 255]),
+                };
+                let font_path = Path::new(font_path);
+                let font =
+                    Font::parse(fs::read(font_path).map_err(|e| SwiftKitError::io(e, font_path))?)?;
+                let coverage = font.render(text, size, MAX_TEXT_PIXELS)?;
+
+                let alpha = f32::from(color[3]) * opacity / 255.0;
+                Stamp::Ready(RgbaImage::from_fn(
+                    coverage.width(),
+                    coverage.height(),
+                    |x, y| {
+                        let coverage = f32::from(coverage.get_pixel(x, y)[0]);
+                        Rgba([
+                            color[0],
+                            color[1],
+                            color[2],
+                            (coverage * alpha).round() as u8,
+                        ])
+                    },
+                ))
+            }
+            Mark::Image { path, scale } => {
+                let scale = scale.unwrap_or(DEFAULT_LOGO_SCALE);
+                if !(scale > 0.0 && scale <= 1.0) {
+                    return Err(SwiftKitError::validation(
+                        "Watermark scale must be between 0 and 1.",
+                    ));
+                }
+                let mut image = open_image(Path::new(path))?.to_rgba8();
+                for pixel in image.pixels_mut() {
+                    pixel[3] = (f32::from(pixel[3]) * opacity).round() as u8;
+                }
+                Stamp::Logo { image, scale }
+            }
+        };
+
+        Ok(Watermark {
+            stamp,
+            position: options.position.unwrap_or(Gravity::SouthEast),
+            tiled: options.tiled,
+            margin: options.margin,
+        })
+    }
+
+    pub fn apply(&self, img: DynamicImage) -> DynamicImage {
+        let stamp = match &self.stamp {
+            Stamp::Ready(stamp) => Cow::Borrowed(stamp),
+            Stamp::Logo { image, scale } => {
+                let width = ((img.width() as f32 * scale).round() as u32).max(1);
+                let height = ((image.height() as f32 * width as f32 / image.width() as f32).round()
+                    as u32)
+                    .max(1);
+                Cow::Owned(imageops::resize(image, width, height, FilterType::Lanczos3))
+            }
+        };
+
+        let had_alpha = img.color().has_alpha();
+        let mut canvas = img.to_rgba8();
+        let (width, height) = canvas.dimensions();
+        let margin = i64::from(self.margin);
+
+        if self.tiled {
+            // Every other row is shifted by half a step, so copies don't line up in columns
+            let step_x = i64::from(stamp.width()) + margin.max(1);
+            let step_y = i64::from(stamp.height()) + margin.max(1);
+            let mut y = margin;
+            let mut row = 0;
+            while y < i64::from(height) {
+                let mut x = margin - (row % 2) * step_x / 2;
+                while x < i64::from(width) {
+                    imageops::overlay(&mut canvas, stamp.as_ref(), x, y);
+                    x += step_x;
+                }
+                y += step_y;
+                row += 1;
+            }
+        } else {
+            let area = (
+                width.saturating_sub(2 * self.margin),
+                height.saturating_sub(2 * self.margin),
+            );
+            let (x, y) = self.position.offset(area, stamp.dimensions());
+            imageops::overlay(
+                &mut canvas,
+                stamp.as_ref(),
+                i64::from(x) + margin,
+                i64::from(y) + margin,
+            );
+        }
+
+        if had_alpha {
+            DynamicImage::ImageRgba8(canvas)
+        } else {
+            DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(canvas).to_rgb8())
+        }
+    }
+}
@@ -1,48 +1,99 @@
-use std::future::poll_fn;
-use std::task::Context;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use std::{fs, path::Path};
-use tauri::{Emitter, Listener};
+use tauri::{AppHandle, Emitter, State};
 use tauri_plugin_shell::process::{CommandEvent, TerminatedPayload};
 use tauri_plugin_shell::ShellExt;
-use tokio::sync::oneshot;
+
+use super::error::SwiftKitError;
+use super::ffmpeg::stderr_tail::StderrTail;
+use super::jobs::manager::{JobHandle, JobKind, JobManager};
+use super::settings::service::current;
+use super::tools::registry::Tool;
+use super::tools::resolver::{locate, resolve_tool};
 
 #[tauri::command(rename_all = "snake_case")]
+#[allow(clippy::too_many_arguments)]
 pub async fn interpolate(
-    handle: tauri::AppHandle,
-    video2x_path: String,
+    handle: AppHandle,
+    video2x_path: Option<String>,
     video_path: String,
-    encoder: String,
-    rife_model: String,
-    multiplier: u8,
+    encoder: Option<String>,
+    rife_model: Option<String>,
+    multiplier: Option<u8>,
     overwrite: bool,
-) -> Result<(), String> {
-    let video_path = Path::new(&video_path);
+    job_id: Option<String>,
+    jobs: State<'_, Arc<JobManager>>,
+) -> Result<(), SwiftKitError> {
+    let job = jobs.start(&handle, job_id, JobKind::Interpolate, video_path.clone())?;
+    let options = InterpolateOptions {
+        video2x_path,
+        encoder,
+        rife_model,
+        multiplier,
+        overwrite,
+    };
+    let result = run_interpolation(&handle, &job, &video_path, options).await;
+    job.finish(&result);
+    result
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterpolateOptions {
+    pub video2x_path: Option<String>,
+    pub encoder: Option<String>,
+    pub rife_model: Option<String>,
+    pub multiplier: Option<u8>,
+    pub overwrite: bool,
+}
+
+pub async fn run_interpolation(
+    handle: &AppHandle,
+    job: &JobHandle,
+    video_path: &str,
+    options: InterpolateOptions,
+) -> Result<(), SwiftKitError> {
+    let InterpolateOptions {
+        video2x_path,
+        encoder,
+        rife_model,
+        multiplier,
+        overwrite,
+    } = options;
+    let settings = current(handle).await;
+    let encoder = encoder.unwrap_or(settings.video_encoder);
+    let rife_model = rife_model.unwrap_or(settings.rife_model);
+    let multiplier = multiplier.unwrap_or(settings.interpolation_multiplier);
+    let video_path = Path::new(video_path);
     if !video_path.exists() || !video_path.is_file() {
-        return Err("Invalid directory path".into());
+        return Err(SwiftKitError::validation("Invalid directory path"));
     }
 
     if let Some(video_extension) = video_path.extension() {
         if video_extension != "mp4" && video_extension != "mkv" {
-            return Err("Unsupported video type".into());
+            return Err(SwiftKitError::validation("Unsupported video type"));
         }
     }
 
-    let video2x_path = Path::new(&video2x_path);
-    if !video2x_path.exists() || !video2x_path.is_dir() {
-        return Err("Invalid video2x installation path".into());
-    }
-
-    let video2x_binary_path = video2x_path.join("video2x.exe");
-    if !video2x_binary_path.exists() || !video2x_binary_path.is_file() {
-        return Err("Invalid video2x path, binary was not found.".into());
-    }
+    // An explicit path wins over the one stored in the settings
+    let video2x_binary_path = match video2x_path.filter(|path| !path.is_empty()) {
+        Some(path) => locate(Tool::Video2x, Some(Path::new(&path)))
+            .map(|resolved| resolved.path)
+            .ok_or_else(|| {
+                SwiftKitError::validation("Invalid video2x path, binary was not found.")
+            })?,
+        None => resolve_tool(handle, Tool::Video2x).await?,
+    };
 
-    let stem = video_path.file_stem().unwrap().to_str().unwrap();
-    let ext = video_path.extension().unwrap().to_str().unwrap();
-    let output_path = video_path
-        .parent()
-        .unwrap()
-        .join(format!("{}_rife.{}", stem, ext));
+    let stem = video_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy())
+        .unwrap_or_default();
+    let ext = video_path
+        .extension()
+        .map(|ext| ext.to_string_lossy())
+        .unwrap_or_default();
+    let output_path = video_path.with_file_name(format!("{}_rife.{}", stem, ext));
     let mut args = vec![
         "-i".to_string(),
         video_path.to_string_lossy().to_string(),
@@ -61,7 +112,7 @@ pub async fn interpolate(
             args.push("p010le".to_string());
         }
     } else {
-        return Err("Unsupported encoder type".into());
+        return Err(SwiftKitError::validation("Unsupported encoder type"));
     }
 
     args.push("-m".to_string());
@@ -79,7 +130,7 @@ pub async fn interpolate(
     {
         args.push(rife_model.to_string())
     } else {
-        return Err("Unsupported RIFE model".into());
+        return Err(SwiftKitError::validation("Unsupported RIFE model"));
     }
 
     args.push("-e".to_string());
@@ -90,41 +141,42 @@ pub async fn interpolate(
         .command(video2x_binary_path)
         .args(args.clone())
         .spawn()
-        .map_err(|e| format!("Failed to execute sidecar: {}", e))?;
-
-    let (cancel_tx, mut cancel_rx) = oneshot::channel();
-    let handle_clone = handle.clone();
-    tokio::spawn(async move {
-        handle_clone.once("cancel_interpolation", move |_| {
-            let _ = cancel_tx.send(());
-        });
-    });
+        .map_err(|e| {
+            SwiftKitError::tool_failed("video2x", format!("Failed to start: {}", e), None)
+        })?;
 
+    // video2x logs progress and errors alike, so only the exit code tells them apart
+    let mut stderr = StderrTail::default();
     loop {
         tokio::select! {
             event = rx.recv() => {
                 match event {
-                    Some(CommandEvent::Stdout(line) | CommandEvent::Stderr(line)) => {
-                        if let Ok(text) = String::from_utf8(line) {
-                            if text.to_lowercase().contains("error")
-                                || text.to_lowercase().contains("failed")
-                            {
-                                handle.emit("interpolation_stderr", text).unwrap();
-                            } else {
-                                handle.emit("interpolation_stdout", text).unwrap();
-                            }
-                        }
+                    Some(CommandEvent::Stdout(line)) => {
+                        let text = String::from_utf8_lossy(&line).trim_end().to_string();
+                        job.progress(None, Some(text.clone()));
+                        handle.emit("interpolation_stdout", text).unwrap_or_default();
+                    }
+                    Some(CommandEvent::Stderr(line)) => {
+                        let text = String::from_utf8_lossy(&line).trim_end().to_string();
+                        stderr.push(&text);
+                        job.progress(None, Some(text.clone()));
+                        handle.emit("interpolation_stdout", text).unwrap_or_default();
                     }
-                    Some(CommandEvent::Terminated(TerminatedPayload { code, signal })) => {
-                        handle.emit("interpolation_stdout", format!("code: {:?} signal {:?}", code, signal)).unwrap();
+                    // Don't let a failed run replace the source video below
+                    Some(CommandEvent::Terminated(TerminatedPayload { code, signal })) if code != Some(0) => {
+                        return Err(SwiftKitError::tool_failed(
+                            "video2x",
+                            format!("exited with code {:?}, signal {:?}", code, signal),
+                            Some(stderr.to_text()),
+                        ));
                     }
                     None => break,
                     _ => {}
                 }
             }
-            _ = poll_fn(|cx: &mut Context<'_>| Pin::new(&mut cancel_rx).poll(cx)) => {
+            _ = job.cancelled() => {
                 if let Err(e) = child.kill() {
-                    eprintln!("Failed to kill video2x process: {}", e);
+                    tracing::warn!("Failed to kill video2x process: {}", e);
                 }
                 return Ok(());
             }
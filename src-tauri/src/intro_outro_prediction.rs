@@ -1,23 +1,30 @@
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::{os::windows::process::CommandExt, process::Command};
+use tauri::AppHandle;
 
-use super::get_video_details::get_video_details;
+use super::error::SwiftKitError;
+use super::get_video_details::probe_video_details;
+use super::tools::registry::Tool;
+use super::tools::resolver::resolve_tool;
 use super::utils::file_types::IAnimeMeta;
 use super::utils::format_duration::format_duration;
+use super::utils::hidden_command::hidden_command;
 
 #[tauri::command(rename_all = "snake_case")]
-pub async fn intro_outro_prediction(episodes_folder: String) -> Result<Vec<IAnimeMeta>, String> {
+pub async fn intro_outro_prediction(
+    handle: AppHandle,
+    episodes_folder: String,
+) -> Result<Vec<IAnimeMeta>, SwiftKitError> {
     let path = Path::new(&episodes_folder);
     if !path.exists() || !path.is_dir() {
-        return Err("Invalid directory path".into());
+        return Err(SwiftKitError::validation("Invalid directory path"));
     }
 
     let mut video_files: Vec<PathBuf> = vec![];
 
     // Collect all video files (.mp4, .mkv) from the directory
-    for entry in fs::read_dir(path).map_err(|e| format!("Failed to read directory: {}", e))? {
-        let entry = entry.map_err(|e| format!("Error reading directory entry: {}", e))?;
+    for entry in fs::read_dir(path).map_err(|e| SwiftKitError::io(e, path))? {
+        let entry = entry.map_err(|e| SwiftKitError::io(e, path))?;
         let path = entry.path();
         let extension = path.extension().and_then(|ext| ext.to_str());
 
@@ -28,14 +35,16 @@ pub async fn intro_outro_prediction(episodes_folder: String) -> Result<Vec<IAnim
         }
     }
 
+    let ffprobe = resolve_tool(&handle, Tool::Ffprobe).await?;
+
     // Sort the files in ascending order based on filename
     video_files.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
     let mut result = vec![];
     for video_path in video_files {
-        let video_details = get_video_details(video_path.to_string_lossy().to_string()).await?;
+        let video_details = probe_video_details(&ffprobe, &video_path.to_string_lossy())?;
 
         // Check if there are chapters
-        let chapters = get_video_chapters(&video_path)?;
+        let chapters = get_video_chapters(&ffprobe, &video_path)?;
         let (intro_start, intro_end, outro_start, outro_end) =
             detect_intro_outro(video_details.duration_in_seconds, &chapters);
 
@@ -61,10 +70,9 @@ pub async fn intro_outro_prediction(episodes_folder: String) -> Result<Vec<IAnim
     Ok(result)
 }
 
-fn get_video_chapters(video_path: &Path) -> Result<Vec<(f64, f64)>, String> {
-    let mut ffprobe_command = Command::new("ffprobe");
+fn get_video_chapters(ffprobe: &Path, video_path: &Path) -> Result<Vec<(f64, f64)>, SwiftKitError> {
+    let mut ffprobe_command = hidden_command(ffprobe);
     ffprobe_command
-        .creation_flags(0x08000000)
         .arg("-loglevel")
         .arg("error")
         .arg("-print_format")
@@ -72,31 +80,36 @@ fn get_video_chapters(video_path: &Path) -> Result<Vec<(f64, f64)>, String> {
         .arg("-show_chapters")
         .arg(video_path);
 
-    let output = ffprobe_command
-        .output()
-        .map_err(|e| format!("Failed to execute ffprobe: {}", e))?;
+    let output = ffprobe_command.output().map_err(|e| {
+        SwiftKitError::tool_failed("ffprobe", format!("Failed to execute: {}", e), None)
+    })?;
 
     if !output.status.success() {
-        return Err("Failed to fetch chapters".into());
+        return Err(SwiftKitError::tool_failed(
+            "ffprobe",
+            "Failed to fetch chapters",
+            Some(String::from_utf8_lossy(&output.stderr).to_string()),
+        ));
     }
 
     let output_str = String::from_utf8_lossy(&output.stdout);
     let chapters: serde_json::Value = serde_json::from_str(&output_str)
-        .map_err(|e| format!("Failed to parse ffprobe output: {}", e))?;
+        .map_err(|e| SwiftKitError::parse(format!("Failed to parse ffprobe output: {}", e)))?;
 
     if let Some(chapter_list) = chapters["chapters"].as_array() {
         let chapter_times = chapter_list
             .iter()
+            // Chapters with missing or malformed times are skipped
             .filter_map(|chapter| {
-                let start_time: f64 = chapter["start_time"].as_str().unwrap().parse().unwrap();
-                let end_time: f64 = chapter["end_time"].as_str().unwrap().parse().unwrap();
+                let start_time: f64 = chapter["start_time"].as_str()?.parse().ok()?;
+                let end_time: f64 = chapter["end_time"].as_str()?.parse().ok()?;
                 Some((start_time, end_time))
             })
             .collect::<Vec<_>>();
 
         Ok(chapter_times)
     } else {
-        Err("No chapters found in ffprobe output".into())
+        Err(SwiftKitError::parse("No chapters found in ffprobe output"))
     }
 }
 
@@ -112,12 +125,12 @@ fn detect_intro_outro(
     let half_duration = video_duration / 2.0;
     for (chapter_start, chapter_end) in chapters.iter() {
         let duration = chapter_end - chapter_start;
-        if *chapter_start < half_duration && duration >= 50.0 && duration <= 180.0 {
+        if *chapter_start < half_duration && (50.0..=180.0).contains(&duration) {
             intro_start = format_duration(*chapter_start, false);
             intro_end = format_duration(*chapter_end, false);
         }
 
-        if *chapter_start > half_duration && duration >= 50.0 && duration <= 180.0 {
+        if *chapter_start > half_duration && (50.0..=180.0).contains(&duration) {
             outro_start = format_duration(*chapter_start, false);
             outro_end = format_duration(*chapter_end, false);
         }
@@ -0,0 +1,300 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::Notify;
+
+use crate::error::SwiftKitError;
+
+// Finished jobs kept around for `list_jobs` / `get_job`
+const FINISHED_JOBS_LIMIT: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+pub enum JobKind {
+    ConvertToMp4,
+    NoIntroOutro,
+    Interpolate,
+    VideoThumbnails,
+    Download,
+    YtDownload,
+}
+
+impl JobKind {
+    // Kinds that can be queued through the scheduler
+    pub const QUEUEABLE: [JobKind; 5] = [
+        JobKind::ConvertToMp4,
+        JobKind::NoIntroOutro,
+        JobKind::Interpolate,
+        JobKind::Download,
+        JobKind::YtDownload,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobKind::ConvertToMp4 => "convert_to_mp4",
+            JobKind::NoIntroOutro => "no_intro_outro",
+            JobKind::Interpolate => "interpolate",
+            JobKind::VideoThumbnails => "video_thumbnails",
+            JobKind::Download => "download",
+            JobKind::YtDownload => "yt_download",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JobInfo {
+    pub id: String,
+    pub kind: JobKind,
+    pub label: String,
+    pub status: JobStatus,
+    pub progress: Option<f64>,
+    pub message: Option<String>,
+    pub started_at: u64,
+    pub finished_at: Option<u64>,
+}
+
+// Cancellation flag shared between the manager and the task running the job
+#[derive(Clone, Default)]
+pub struct JobControl {
+    cancelled: Arc<AtomicBool>,
+    notify: Arc<Notify>,
+}
+
+impl JobControl {
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    // Resolves once the job is cancelled, meant to be raced in `tokio::select!`
+    pub async fn cancelled(&self) {
+        loop {
+            let notified = self.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+struct JobEntry {
+    info: JobInfo,
+    control: JobControl,
+}
+
+#[derive(Default)]
+pub struct JobManager {
+    jobs: Mutex<HashMap<String, JobEntry>>,
+}
+
+impl JobManager {
+    pub fn start(
+        self: &Arc<Self>,
+        handle: &AppHandle,
+        id: Option<String>,
+        kind: JobKind,
+        label: String,
+    ) -> Result<JobHandle, SwiftKitError> {
+        let id = id
+            .filter(|id| !id.is_empty())
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let control = JobControl::default();
+        let info = JobInfo {
+            id: id.clone(),
+            kind,
+            label,
+            status: JobStatus::Running,
+            progress: None,
+            message: None,
+            started_at: now_millis(),
+            finished_at: None,
+        };
+
+        {
+            let mut jobs = self
+                .jobs
+                .lock()
+                .map_err(|e| SwiftKitError::internal(e.to_string()))?;
+            if jobs
+                .get(&id)
+                .is_some_and(|job| job.info.status == JobStatus::Running)
+            {
+                return Err(SwiftKitError::validation(format!(
+                    "Job {} is already running",
+                    id
+                )));
+            }
+
+            jobs.insert(
+                id.clone(),
+                JobEntry {
+                    info: info.clone(),
+                    control: control.clone(),
+                },
+            );
+            prune_finished(&mut jobs);
+        }
+
+        handle.emit("job-progress", &info).unwrap_or_default();
+
+        Ok(JobHandle {
+            id,
+            control,
+            manager: self.clone(),
+            handle: handle.clone(),
+        })
+    }
+
+    pub fn list(&self) -> Vec<JobInfo> {
+        let mut jobs: Vec<JobInfo> = self
+            .jobs
+            .lock()
+            .map(|jobs| jobs.values().map(|job| job.info.clone()).collect())
+            .unwrap_or_default();
+        jobs.sort_by_key(|job| job.started_at);
+        jobs
+    }
+
+    pub fn get(&self, id: &str) -> Option<JobInfo> {
+        self.jobs
+            .lock()
+            .ok()
+            .and_then(|jobs| jobs.get(id).map(|job| job.info.clone()))
+    }
+
+    // Returns false when the job is unknown or no longer running
+    pub fn cancel(&self, id: &str) -> bool {
+        let Ok(jobs) = self.jobs.lock() else {
+            return false;
+        };
+
+        match jobs.get(id) {
+            Some(job) if job.info.status == JobStatus::Running => {
+                job.control.cancel();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn cancel_kind(&self, kind: JobKind) -> usize {
+        let Ok(jobs) = self.jobs.lock() else {
+            return 0;
+        };
+
+        jobs.values()
+            .filter(|job| job.info.kind == kind && job.info.status == JobStatus::Running)
+            .map(|job| job.control.cancel())
+            .count()
+    }
+
+    fn update<F: FnOnce(&mut JobInfo)>(&self, id: &str, apply: F) -> Option<JobInfo> {
+        let mut jobs = self.jobs.lock().ok()?;
+        let job = jobs.get_mut(id)?;
+        apply(&mut job.info);
+        Some(job.info.clone())
+    }
+}
+
+// Handed to the command running the job to report progress and observe cancellation
+#[derive(Clone)]
+pub struct JobHandle {
+    id: String,
+    control: JobControl,
+    manager: Arc<JobManager>,
+    handle: AppHandle,
+}
+
+impl JobHandle {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.control.is_cancelled()
+    }
+
+    pub async fn cancelled(&self) {
+        self.control.cancelled().await
+    }
+
+    pub fn progress(&self, progress: Option<f64>, message: Option<String>) {
+        let info = self.manager.update(&self.id, |info| {
+            if progress.is_some() {
+                info.progress = progress;
+            }
+            info.message = message;
+        });
+
+        if let Some(info) = info {
+            self.handle.emit("job-progress", info).unwrap_or_default();
+        }
+    }
+
+    // A cancelled job is reported as such even if the command returned Ok
+    pub fn finish<T>(&self, result: &Result<T, SwiftKitError>) {
+        let cancelled = self.is_cancelled() || result.as_ref().is_err_and(|e| e.is_cancelled());
+        let status = if cancelled {
+            JobStatus::Cancelled
+        } else if result.is_ok() {
+            JobStatus::Completed
+        } else {
+            JobStatus::Failed
+        };
+
+        let info = self.manager.update(&self.id, |info| {
+            info.status = status;
+            info.finished_at = Some(now_millis());
+            info.message = result.as_ref().err().map(|e| e.to_string());
+            if status == JobStatus::Completed {
+                info.progress = Some(100.0);
+            }
+        });
+
+        if let Some(info) = info {
+            self.handle.emit("job-finished", info).unwrap_or_default();
+        }
+    }
+}
+
+fn prune_finished(jobs: &mut HashMap<String, JobEntry>) {
+    let mut finished: Vec<(u64, String)> = jobs
+        .values()
+        .filter(|job| job.info.status != JobStatus::Running)
+        .map(|job| (job.info.finished_at.unwrap_or(0), job.info.id.clone()))
+        .collect();
+
+    if finished.len() <= FINISHED_JOBS_LIMIT {
+        return;
+    }
+
+    finished.sort();
+    for (_, id) in finished.iter().take(finished.len() - FINISHED_JOBS_LIMIT) {
+        jobs.remove(id);
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
@@ -0,0 +1,4 @@
+pub mod manager;
+pub mod params;
+pub mod scheduler;
+pub mod store;
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use super::manager::{JobHandle, JobKind};
+use crate::error::SwiftKitError;
+use crate::interpolate::InterpolateOptions;
+use crate::utils::file_types::IAnimeMeta;
+
+// Everything needed to run a queued job again after a restart
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JobParams {
+    ConvertToMp4 {
+        video_path: String,
+    },
+    NoIntroOutro {
+        folder_path: String,
+        video: IAnimeMeta,
+        use_cuda: Option<bool>,
+        overwrite: bool,
+    },
+    Interpolate {
+        video_path: String,
+        #[serde(flatten)]
+        options: InterpolateOptions,
+    },
+    Download {
+        url: String,
+        destination: String,
+        rate_limit_kb: Option<u64>,
+    },
+    YtDownload {
+        url: String,
+        output_path: Option<String>,
+        download_rate: Option<u32>,
+    },
+}
+
+impl JobParams {
+    pub fn kind(&self) -> JobKind {
+        match self {
+            JobParams::ConvertToMp4 { .. } => JobKind::ConvertToMp4,
+            JobParams::NoIntroOutro { .. } => JobKind::NoIntroOutro,
+            JobParams::Interpolate { .. } => JobKind::Interpolate,
+            JobParams::Download { .. } => JobKind::Download,
+            JobParams::YtDownload { .. } => JobKind::YtDownload,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            JobParams::ConvertToMp4 { video_path } => video_path.clone(),
+            JobParams::NoIntroOutro { video, .. } => video.filename.clone(),
+            JobParams::Interpolate { video_path, .. } => video_path.clone(),
+            JobParams::Download { url, .. } => url.clone(),
+            JobParams::YtDownload { url, .. } => url.clone(),
+        }
+    }
+
+    pub async fn run(self, handle: &AppHandle, job: &JobHandle) -> Result<(), SwiftKitError> {
+        match self {
+            JobParams::ConvertToMp4 { video_path } => {
+                crate::convert_to_mp4::convert(handle, job, &video_path).await
+            }
+            JobParams::NoIntroOutro {
+                folder_path,
+                video,
+                use_cuda,
+                overwrite,
+            } => {
+                crate::no_intro_outro::cut(handle, job, &folder_path, video, use_cuda, overwrite)
+                    .await
+            }
+            JobParams::Interpolate {
+                video_path,
+                options,
+            } => crate::interpolate::run_interpolation(handle, job, &video_path, options).await,
+            JobParams::Download {
+                url,
+                destination,
+                rate_limit_kb,
+            } => crate::download_file::download(handle, job, url, destination, rate_limit_kb)
+                .await
+                .map(|_| ()),
+            JobParams::YtDownload {
+                url,
+                output_path,
+                download_rate,
+            } => crate::yt_download::download(
+                handle,
+                job,
+                &url,
+                output_path.as_deref(),
+                download_rate,
+            )
+            .await
+            .map(|_| ()),
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::Notify;
+
+use super::manager::{JobKind, JobManager};
+use super::params::JobParams;
+use super::store::{self, QueueStatus, QueuedJob};
+use crate::db::{get_setting, set_setting};
+use crate::error::SwiftKitError;
+
+// One encode at a time keeps the GPU from thrashing, downloads are network bound
+fn default_concurrency(kind: JobKind) -> usize {
+    match kind {
+        JobKind::Download | JobKind::YtDownload => 3,
+        _ => 1,
+    }
+}
+
+fn concurrency_setting_key(kind: JobKind) -> String {
+    format!("{}_concurrency", kind.as_str())
+}
+
+// Runs queued jobs from the `jobs` table, respecting a concurrency limit per kind
+pub struct JobScheduler {
+    handle: AppHandle,
+    pool: SqlitePool,
+    manager: Arc<JobManager>,
+    limits: Mutex<HashMap<JobKind, usize>>,
+    running: Mutex<HashMap<JobKind, usize>>,
+    wake: Notify,
+}
+
+impl JobScheduler {
+    pub fn new(handle: AppHandle, pool: SqlitePool, manager: Arc<JobManager>) -> Self {
+        let limits = JobKind::QUEUEABLE
+            .into_iter()
+            .map(|kind| (kind, default_concurrency(kind)))
+            .collect();
+
+        Self {
+            handle,
+            pool,
+            manager,
+            limits: Mutex::new(limits),
+            running: Mutex::new(HashMap::new()),
+            wake: Notify::new(),
+        }
+    }
+
+    // Recovers the queue left by the previous session, then dispatches until the app exits
+    pub async fn run(self: Arc<Self>) {
+        self.load_limits().await;
+
+        match store::mark_interrupted(&self.pool).await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("Marked {} interrupted job(s) for retry", count),
+            Err(e) => tracing::error!("Failed to recover interrupted jobs: {}", e),
+        }
+
+        loop {
+            self.dispatch().await;
+            self.wake.notified().await;
+        }
+    }
+
+    pub async fn enqueue(&self, params: JobParams) -> Result<String, SwiftKitError> {
+        let id = uuid::Uuid::new_v4().to_string();
+        store::insert_job(&self.pool, &id, &params.label(), &params).await?;
+        self.changed();
+        Ok(id)
+    }
+
+    pub async fn list(&self) -> Result<Vec<QueuedJob>, SwiftKitError> {
+        store::list_jobs(&self.pool).await
+    }
+
+    // Running jobs are cancelled through the manager, queued ones never start
+    pub async fn cancel(&self, id: &str) -> Result<bool, SwiftKitError> {
+        if self.manager.cancel(id) {
+            return Ok(true);
+        }
+
+        let cancelled = store::cancel_queued(&self.pool, id).await?;
+        if cancelled {
+            self.changed();
+        }
+        Ok(cancelled)
+    }
+
+    pub async fn retry(&self, id: &str) -> Result<(), SwiftKitError> {
+        if !store::requeue(&self.pool, id).await? {
+            return Err(SwiftKitError::validation(format!(
+                "Job {} can't be retried",
+                id
+            )));
+        }
+        self.changed();
+        Ok(())
+    }
+
+    pub async fn set_limit(&self, kind: JobKind, limit: usize) -> Result<(), SwiftKitError> {
+        if !JobKind::QUEUEABLE.contains(&kind) {
+            return Err(SwiftKitError::validation(format!(
+                "Jobs of kind {} can't be queued",
+                kind.as_str()
+            )));
+        }
+        if limit == 0 {
+            return Err(SwiftKitError::validation(
+                "Concurrency limit must be at least 1",
+            ));
+        }
+
+        set_setting(
+            &self.pool,
+            &concurrency_setting_key(kind),
+            &format!("Maximum number of {} jobs running at once", kind.as_str()),
+            &limit.to_string(),
+        )
+        .await?;
+
+        if let Ok(mut limits) = self.limits.lock() {
+            limits.insert(kind, limit);
+        }
+        self.changed();
+        Ok(())
+    }
+
+    async fn load_limits(&self) {
+        for kind in JobKind::QUEUEABLE {
+            let limit = get_setting(&self.pool, &concurrency_setting_key(kind))
+                .await
+                .and_then(|value| value.trim().parse::<usize>().ok())
+                .filter(|limit| *limit > 0);
+
+            if let (Some(limit), Ok(mut limits)) = (limit, self.limits.lock()) {
+                limits.insert(kind, limit);
+            }
+        }
+    }
+
+    fn changed(&self) {
+        self.wake.notify_one();
+        self.handle
+            .emit("job-queue-changed", ())
+            .unwrap_or_default();
+    }
+
+    // Reserves a slot for `kind` if it is below its limit
+    fn try_reserve(&self, kind: JobKind) -> bool {
+        let limit = self
+            .limits
+            .lock()
+            .ok()
+            .and_then(|limits| limits.get(&kind).copied())
+            .unwrap_or_else(|| default_concurrency(kind));
+        let Ok(mut running) = self.running.lock() else {
+            return false;
+        };
+
+        let count = running.entry(kind).or_insert(0);
+        if *count >= limit {
+            return false;
+        }
+        *count += 1;
+        true
+    }
+
+    fn release(&self, kind: JobKind) {
+        if let Ok(mut running) = self.running.lock() {
+            if let Some(count) = running.get_mut(&kind) {
+                *count = count.saturating_sub(1);
+            }
+        }
+    }
+
+    async fn dispatch(self: &Arc<Self>) {
+        for kind in JobKind::QUEUEABLE {
+            while self.try_reserve(kind) {
+                let job = match store::claim_next(&self.pool, kind).await {
+                    Ok(Some(job)) => job,
+                    Ok(None) => {
+                        self.release(kind);
+                        break;
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to claim the next {} job: {}", kind.as_str(), e);
+                        self.release(kind);
+                        break;
+                    }
+                };
+
+                let scheduler = self.clone();
+                tauri::async_runtime::spawn(async move { scheduler.execute(job).await });
+            }
+        }
+    }
+
+    async fn execute(self: Arc<Self>, queued: QueuedJob) {
+        let QueuedJob {
+            id,
+            kind,
+            label,
+            params,
+            ..
+        } = queued;
+
+        let (result, cancelled) =
+            match self
+                .manager
+                .start(&self.handle, Some(id.clone()), kind, label)
+            {
+                Ok(job) => {
+                    let result = params.0.run(&self.handle, &job).await;
+                    job.finish(&result);
+                    (result, job.is_cancelled())
+                }
+                Err(e) => (Err(e), false),
+            };
+
+        let status = match &result {
+            _ if cancelled => QueueStatus::Cancelled,
+            Ok(_) => QueueStatus::Done,
+            Err(e) if e.is_cancelled() => QueueStatus::Cancelled,
+            Err(_) => QueueStatus::Failed,
+        };
+        let message = result.err().map(|e| e.to_string());
+        if let Err(e) = store::finish_job(&self.pool, &id, status, message.as_deref()).await {
+            tracing::error!("Failed to record the result of job {}: {}", id, e);
+        }
+
+        self.release(kind);
+        self.changed();
+    }
+}
@@ -0,0 +1,140 @@
+use serde::Serialize;
+use sqlx::types::Json;
+use sqlx::{FromRow, SqlitePool};
+
+use super::manager::JobKind;
+use super::params::JobParams;
+use crate::error::SwiftKitError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+pub enum QueueStatus {
+    Queued,
+    Running,
+    Done,
+    Failed,
+    Interrupted,
+    Cancelled,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct QueuedJob {
+    pub id: String,
+    pub kind: JobKind,
+    pub label: String,
+    pub params: Json<JobParams>,
+    pub status: QueueStatus,
+    pub message: Option<String>,
+    pub attempts: i64,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    pub created_at: String,
+}
+
+const JOB_COLUMNS: &str =
+    "id, kind, label, params, status, message, attempts, started_at, finished_at, created_at";
+
+pub async fn insert_job(
+    pool: &SqlitePool,
+    id: &str,
+    label: &str,
+    params: &JobParams,
+) -> Result<(), SwiftKitError> {
+    sqlx::query("INSERT INTO jobs (id, kind, label, params) VALUES (?, ?, ?, ?)")
+        .bind(id)
+        .bind(params.kind())
+        .bind(label)
+        .bind(Json(params))
+        .execute(pool)
+        .await
+        .map_err(|e| SwiftKitError::internal(format!("Failed to queue job: {}", e)))?;
+    Ok(())
+}
+
+pub async fn list_jobs(pool: &SqlitePool) -> Result<Vec<QueuedJob>, SwiftKitError> {
+    sqlx::query_as::<_, QueuedJob>(&format!(
+        "SELECT {} FROM jobs ORDER BY created_at DESC, rowid DESC",
+        JOB_COLUMNS
+    ))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| SwiftKitError::internal(format!("Failed to list jobs: {}", e)))
+}
+
+// Oldest queued job of the given kind, flipped to running in the same statement
+pub async fn claim_next(
+    pool: &SqlitePool,
+    kind: JobKind,
+) -> Result<Option<QueuedJob>, SwiftKitError> {
+    sqlx::query_as::<_, QueuedJob>(&format!(
+        "UPDATE jobs
+        SET status = 'running', attempts = attempts + 1, message = NULL,
+            started_at = CURRENT_TIMESTAMP, finished_at = NULL
+        WHERE id = (
+            SELECT id FROM jobs WHERE status = 'queued' AND kind = ?
+            ORDER BY created_at, rowid LIMIT 1
+        )
+        RETURNING {}",
+        JOB_COLUMNS
+    ))
+    .bind(kind)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| SwiftKitError::internal(format!("Failed to claim job: {}", e)))
+}
+
+pub async fn finish_job(
+    pool: &SqlitePool,
+    id: &str,
+    status: QueueStatus,
+    message: Option<&str>,
+) -> Result<(), SwiftKitError> {
+    sqlx::query(
+        "UPDATE jobs SET status = ?, message = ?, finished_at = CURRENT_TIMESTAMP WHERE id = ?",
+    )
+    .bind(status)
+    .bind(message)
+    .bind(id)
+    .execute(pool)
+    .await
+    .map_err(|e| SwiftKitError::internal(format!("Failed to update job: {}", e)))?;
+    Ok(())
+}
+
+// Only jobs that are waiting can be cancelled here, running ones go through the manager
+pub async fn cancel_queued(pool: &SqlitePool, id: &str) -> Result<bool, SwiftKitError> {
+    let result = sqlx::query(
+        "UPDATE jobs SET status = 'cancelled', finished_at = CURRENT_TIMESTAMP
+        WHERE id = ? AND status = 'queued'",
+    )
+    .bind(id)
+    .execute(pool)
+    .await
+    .map_err(|e| SwiftKitError::internal(format!("Failed to cancel job: {}", e)))?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn requeue(pool: &SqlitePool, id: &str) -> Result<bool, SwiftKitError> {
+    let result = sqlx::query(
+        "UPDATE jobs SET status = 'queued', message = NULL, started_at = NULL, finished_at = NULL
+        WHERE id = ? AND status IN ('failed', 'interrupted', 'cancelled')",
+    )
+    .bind(id)
+    .execute(pool)
+    .await
+    .map_err(|e| SwiftKitError::internal(format!("Failed to retry job: {}", e)))?;
+    Ok(result.rows_affected() > 0)
+}
+
+// Jobs still marked running were cut short by the app closing
+pub async fn mark_interrupted(pool: &SqlitePool) -> Result<u64, SwiftKitError> {
+    let result = sqlx::query(
+        "UPDATE jobs SET status = 'interrupted', message = 'Interrupted by app shutdown'
+        WHERE status = 'running'",
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| SwiftKitError::internal(format!("Failed to recover jobs: {}", e)))?;
+    Ok(result.rows_affected())
+}
@@ -1,27 +1,56 @@
 use std::sync::{Arc, Mutex};
+use tauri::Manager;
 
 mod abort_download;
 mod always_on_top;
 mod bulk_rename;
+mod cancel_job;
+mod check_tools;
+pub mod cli;
 mod convert_to_mp4;
+mod db;
+mod diagnostics;
 mod download_file;
+mod enqueue_job;
+mod error;
+mod export_diagnostics;
+mod export_icon_set;
 mod fetch_files;
+mod ffmpeg;
+mod find_similar_images;
 mod finder;
+mod generate_icons;
 mod generate_video_thumbnails;
 mod get_available_disks;
+mod get_job;
+mod get_settings;
 mod get_video_details;
 mod highlight_file;
+mod icon_set;
+mod image_compare;
 mod image_compress;
 mod image_convert;
 mod image_crop;
+mod image_metadata;
+mod image_pipeline;
 mod image_resize;
+mod image_watermark;
 mod img_compressors;
+mod img_encoders;
+mod img_metadata;
+mod img_metrics;
+mod img_pipeline;
+mod img_transforms;
 mod interpolate;
 mod intro_outro_prediction;
+mod jobs;
+mod list_job_queue;
+mod list_jobs;
 mod migrations;
 mod no_intro_outro;
 mod open_external_url;
 mod rename_files;
+mod retry_job;
 mod scrape_anime;
 mod scrape_game;
 mod scrape_movie;
@@ -29,7 +58,13 @@ mod scrapers;
 mod search_anime;
 mod search_game;
 mod search_movie;
+mod set_job_concurrency;
+mod settings;
+mod smart_crop;
+mod tools;
+mod trash_files;
 mod trash_folder;
+mod update_settings;
 mod utils;
 mod yt_download;
 
@@ -43,35 +78,58 @@ pub fn run() {
         .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(
             tauri_plugin_sql::Builder::new()
-                .add_migrations("sqlite:swiftkit.db", migrations::get_migrations())
+                .add_migrations(db::DATABASE_URL, migrations::get_migrations())
                 .build(),
         )
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_opener::init())
         .manage(app_window_state.clone())
-        .manage(Arc::new(generate_video_thumbnails::FfmpegState {
-            process: Mutex::new(None),
-            output_folder: Mutex::new(None),
-        }))
-        .manage(Arc::new(
-            Mutex::new(download_file::DownloadState::default()),
-        ))
+        .manage(Arc::new(jobs::manager::JobManager::default()))
+        .setup(|app| {
+            let app_config_dir = app.path().app_config_dir()?;
+            diagnostics::logging::init(&app_config_dir);
+
+            let pool = db::connect_lazy(&app_config_dir);
+            app.manage(pool.clone());
+            app.manage(Arc::new(settings::service::SettingsService::new(
+                pool.clone(),
+            )));
+
+            let job_manager = app
+                .state::<Arc<jobs::manager::JobManager>>()
+                .inner()
+                .clone();
+            let scheduler = Arc::new(jobs::scheduler::JobScheduler::new(
+                app.handle().clone(),
+                pool,
+                job_manager,
+            ));
+            app.manage(scheduler.clone());
+            tauri::async_runtime::spawn(scheduler.run());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             fetch_files::fetch_files,
             bulk_rename::bulk_rename,
             rename_files::rename_files,
             image_convert::image_convert,
             image_compress::image_compress,
+            image_compare::image_compare,
             image_resize::image_resize,
+            image_watermark::image_watermark,
             get_video_details::get_video_details,
             intro_outro_prediction::intro_outro_prediction,
             get_available_disks::get_available_disks,
             finder::finder,
             highlight_file::highlight_file,
+            highlight_file::highlight_files,
             always_on_top::always_on_top,
+            generate_icons::generate_icons,
+            export_icon_set::export_icon_set,
             generate_video_thumbnails::generate_video_thumbnails,
             generate_video_thumbnails::stop_video_thumbnail_generation,
             trash_folder::trash_folder,
+            trash_files::trash_files,
             no_intro_outro::no_intro_outro,
             interpolate::interpolate,
             convert_to_mp4::convert_to_mp4,
@@ -83,9 +141,24 @@ pub fn run() {
             search_game::search_game,
             open_external_url::open_external_url,
             image_crop::image_crop,
+            image_metadata::image_metadata,
+            image_pipeline::image_pipeline,
+            find_similar_images::find_similar_images,
+            smart_crop::smart_crop,
             download_file::download_file,
             abort_download::abort_download,
             yt_download::yt_download,
+            check_tools::check_tools,
+            list_jobs::list_jobs,
+            get_job::get_job,
+            cancel_job::cancel_job,
+            enqueue_job::enqueue_job,
+            list_job_queue::list_job_queue,
+            retry_job::retry_job,
+            set_job_concurrency::set_job_concurrency,
+            export_diagnostics::export_diagnostics,
+            get_settings::get_settings,
+            update_settings::update_settings,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
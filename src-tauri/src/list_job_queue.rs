@@ -0,0 +1,13 @@
+use std::sync::Arc;
+use tauri::State;
+
+use super::error::SwiftKitError;
+use super::jobs::scheduler::JobScheduler;
+use super::jobs::store::QueuedJob;
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_job_queue(
+    scheduler: State<'_, Arc<JobScheduler>>,
+) -> Result<Vec<QueuedJob>, SwiftKitError> {
+    scheduler.list().await
+}
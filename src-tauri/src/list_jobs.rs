@@ -0,0 +1,9 @@
+use std::sync::Arc;
+use tauri::State;
+
+use super::jobs::manager::{JobInfo, JobManager};
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn list_jobs(jobs: State<'_, Arc<JobManager>>) -> Vec<JobInfo> {
+    jobs.list()
+}
@@ -312,5 +312,54 @@ pub fn get_migrations() -> Vec<Migration> {
 			"#,
             kind: MigrationKind::Up,
         },
+        Migration {
+            version: 9,
+            description: "create_jobs_table",
+            sql: r#"
+			CREATE TABLE IF NOT EXISTS jobs (
+				id TEXT PRIMARY KEY,
+				kind TEXT NOT NULL CHECK(length(kind) <= 100),
+				label TEXT NOT NULL,
+				params TEXT NOT NULL,
+				status TEXT NOT NULL DEFAULT 'queued' CHECK(status IN ('queued', 'running', 'done', 'failed', 'interrupted', 'cancelled')),
+				message TEXT,
+				attempts INTEGER NOT NULL DEFAULT 0,
+				started_at TIMESTAMP,
+				finished_at TIMESTAMP,
+				created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+				updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+			);
+
+			-- Indexes for performance on commonly queried fields
+			CREATE INDEX idx_job_status ON jobs (status);
+			CREATE INDEX idx_job_kind ON jobs (kind);
+
+			-- Trigger to automatically update the `updated_at` field on updates
+			CREATE TRIGGER jobs_updated_at
+			AFTER UPDATE ON jobs
+			FOR EACH ROW
+			BEGIN
+				UPDATE jobs SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.id;
+			END;
+			"#,
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 10,
+            description: "create_image_hashes_table",
+            sql: r#"
+			CREATE TABLE IF NOT EXISTS image_hashes (
+				path TEXT PRIMARY KEY,
+				mtime INTEGER NOT NULL,
+				size INTEGER NOT NULL,
+				width INTEGER NOT NULL,
+				height INTEGER NOT NULL,
+				dhash INTEGER NOT NULL,
+				phash INTEGER NOT NULL,
+				updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+			);
+			"#,
+            kind: MigrationKind::Up,
+        },
     ]
 }
@@ -1,11 +1,13 @@
-use std::future::poll_fn;
-use std::task::Context;
+use std::sync::Arc;
 use std::{fs, path::Path};
-use tauri::{Emitter, Listener};
-use tauri_plugin_shell::process::{CommandEvent, TerminatedPayload};
-use tauri_plugin_shell::ShellExt;
-use tokio::sync::oneshot;
-
+use tauri::{AppHandle, State};
+
+use super::error::SwiftKitError;
+use super::ffmpeg::runner::FfmpegRunner;
+use super::jobs::manager::{JobHandle, JobKind, JobManager};
+use super::settings::service::current;
+use super::tools::registry::Tool;
+use super::tools::resolver::resolve_tool;
 use super::utils::check_stream_exists::check_stream_exists;
 use super::utils::file_types::IAnimeMeta;
 use super::utils::is_valid_timestamp::is_valid_timestamp;
@@ -13,23 +15,57 @@ use super::utils::parse_duration::parse_duration;
 
 #[tauri::command(rename_all = "snake_case")]
 pub async fn no_intro_outro(
-    handle: tauri::AppHandle,
+    handle: AppHandle,
     folder_path: String,
     video: IAnimeMeta,
-    use_cuda: bool,
+    use_cuda: Option<bool>,
     overwrite: bool,
-) -> Result<(), String> {
-    let folder_path = Path::new(&folder_path);
+    job_id: Option<String>,
+    jobs: State<'_, Arc<JobManager>>,
+) -> Result<(), SwiftKitError> {
+    let job = jobs.start(
+        &handle,
+        job_id,
+        JobKind::NoIntroOutro,
+        video.filename.clone(),
+    )?;
+    let result = cut(&handle, &job, &folder_path, video, use_cuda, overwrite).await;
+    job.finish(&result);
+    result
+}
+
+pub async fn cut(
+    handle: &AppHandle,
+    job: &JobHandle,
+    folder_path: &str,
+    video: IAnimeMeta,
+    use_cuda: Option<bool>,
+    overwrite: bool,
+) -> Result<(), SwiftKitError> {
+    let use_cuda = match use_cuda {
+        Some(use_cuda) => use_cuda,
+        None => current(handle).await.use_cuda,
+    };
+    let folder_path = Path::new(folder_path);
     if !folder_path.exists() || !folder_path.is_dir() {
-        return Err("Invalid directory path".into());
+        return Err(SwiftKitError::validation("Invalid directory path"));
     }
 
     let input_path = folder_path.join(&video.filename);
-    let stem = input_path.file_stem().unwrap().to_str().unwrap();
-    let ext = input_path.extension().unwrap().to_str().unwrap();
+    let (Some(stem), Some(ext)) = (
+        input_path.file_stem().map(|stem| stem.to_string_lossy()),
+        input_path.extension().map(|ext| ext.to_string_lossy()),
+    ) else {
+        return Err(SwiftKitError::validation(format!(
+            "Invalid video file name: {}",
+            video.filename
+        )));
+    };
     let output_path = folder_path.join(format!("{}_noio.{}", stem, ext));
 
+    let ffprobe = resolve_tool(handle, Tool::Ffprobe).await?;
     let result = check_stream_exists(
+        &ffprobe,
         &input_path,
         video.default_audio as usize,
         video.default_subtitle as usize,
@@ -37,23 +73,15 @@ pub async fn no_intro_outro(
     .await?;
 
     let mut segments = Vec::new();
-    let has_valid_intro =
-        is_valid_timestamp(&video.intro_start) && is_valid_timestamp(&video.intro_end);
-    let has_valid_outro =
-        is_valid_timestamp(&video.outro_start) && is_valid_timestamp(&video.outro_end);
-
-    if has_valid_intro {
-        let intro_start = parse_duration(video.intro_start.as_ref().unwrap()).unwrap();
-        let intro_end = parse_duration(video.intro_end.as_ref().unwrap()).unwrap();
+    let intro = timestamp_range(&video.intro_start, &video.intro_end)?;
+    let outro = timestamp_range(&video.outro_start, &video.outro_end)?;
 
+    if let Some((intro_start, intro_end)) = intro {
         if intro_start > 0.0 {
             segments.push((0.0, intro_start));
         }
 
-        if has_valid_outro {
-            let outro_start = parse_duration(video.outro_start.as_ref().unwrap()).unwrap();
-            let outro_end = parse_duration(video.outro_end.as_ref().unwrap()).unwrap();
-
+        if let Some((outro_start, outro_end)) = outro {
             segments.push((intro_end, outro_start));
             if outro_end < video.duration_in_seconds {
                 segments.push((outro_end, video.duration_in_seconds));
@@ -61,10 +89,7 @@ pub async fn no_intro_outro(
         } else {
             segments.push((intro_end, video.duration_in_seconds));
         }
-    } else if has_valid_outro {
-        let outro_start = parse_duration(video.outro_start.as_ref().unwrap()).unwrap();
-        let outro_end = parse_duration(video.outro_end.as_ref().unwrap()).unwrap();
-
+    } else if let Some((outro_start, outro_end)) = outro {
         segments.push((0.0, outro_start));
         if outro_end < video.duration_in_seconds {
             segments.push((outro_end, video.duration_in_seconds));
@@ -124,57 +149,17 @@ pub async fn no_intro_outro(
     ffmpeg_args.push("-y".to_string());
     ffmpeg_args.push(output_path.to_string_lossy().to_string());
 
-    let (mut rx, child) = handle
-        .shell()
-        .sidecar("ffmpeg")
-        .map_err(|e| format!("Failed to create ffmpeg sidecar: {}", e))?
-        .args(ffmpeg_args.clone())
-        .spawn()
-        .map_err(|e| format!("Failed to execute sidecar: {}", e))?;
-
-    let (cancel_tx, mut cancel_rx) = oneshot::channel();
-    let handle_clone = handle.clone();
-    tokio::spawn(async move {
-        handle_clone.once("cancel_noio", move |_| {
-            let _ = cancel_tx.send(());
-        });
-    });
-
-    loop {
-        tokio::select! {
-            event = rx.recv() => {
-                match event {
-                    Some(CommandEvent::Stdout(line) | CommandEvent::Stderr(line)) => {
-                        if let Ok(text) = String::from_utf8(line) {
-                            if text.to_lowercase().contains("error")
-                                || text.to_lowercase().contains("failed")
-                            {
-                                handle.emit("noio_stderr", text).unwrap();
-                            } else {
-                                handle.emit("noio_stdout", text).unwrap();
-                            }
-                        }
-                    }
-                    Some(CommandEvent::Terminated(TerminatedPayload { code, signal })) => {
-                        if code.unwrap_or(-1) != 0 {
-                            return Err(format!(
-                                "FFmpeg process failed with exit code: {:?}, signal: {:?}",
-                                code, signal
-                            ));
-                        }
-                        break;
-                    }
-                    None => break,
-                    _ => {}
-                }
-            }
-            _ = poll_fn(|cx: &mut Context<'_>| Pin::new(&mut cancel_rx).poll(cx)) => {
-                if let Err(e) = child.kill() {
-                    eprintln!("Failed to kill FFmpeg process: {}", e);
-                }
-                return Ok(());
-            }
+    // The output only contains the kept segments
+    let duration = segments.iter().map(|(start, end)| end - start).sum::<f64>();
+    let run = FfmpegRunner::new(handle, job)
+        .event("noio_progress")
+        .duration(Some(duration))
+        .run(ffmpeg_args);
+    if let Err(e) = run.await {
+        if e.is_cancelled() {
+            let _ = fs::remove_file(&output_path);
         }
+        return Err(e);
     }
 
     if overwrite {
@@ -183,3 +168,22 @@ pub async fn no_intro_outro(
 
     Ok(())
 }
+
+// Start and end in seconds when both timestamps are set, None when the segment is unused
+fn timestamp_range(
+    start: &Option<String>,
+    end: &Option<String>,
+) -> Result<Option<(f64, f64)>, SwiftKitError> {
+    if !is_valid_timestamp(start) || !is_valid_timestamp(end) {
+        return Ok(None);
+    }
+    let (Some(start), Some(end)) = (start, end) else {
+        return Ok(None);
+    };
+
+    let parse = |timestamp: &str| {
+        parse_duration(timestamp)
+            .ok_or_else(|| SwiftKitError::parse(format!("Invalid timestamp: {}", timestamp)))
+    };
+    Ok(Some((parse(start)?, parse(end)?)))
+}
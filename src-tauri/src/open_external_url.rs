@@ -1,12 +1,94 @@
-use std::{os::windows::process::CommandExt, process::Command};
+use serde::Serialize;
+use std::fmt;
+use tauri::AppHandle;
+use url::Url;
 
+use super::error::SwiftKitError;
+use super::settings::service::current;
+use super::utils::hidden_command::hidden_command;
+
+const ALLOWED_SCHEMES: [&str; 3] = ["http", "https", "mailto"];
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "message", rename_all = "snake_case")]
+pub enum OpenUrlError {
+    InvalidUrl(String),
+    DisallowedScheme(String),
+    LaunchFailed(String),
+}
+
+impl fmt::Display for OpenUrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpenUrlError::InvalidUrl(e) => write!(f, "Invalid URL: {}", e),
+            OpenUrlError::DisallowedScheme(scheme) => {
+                write!(f, "URL scheme is not allowed: {}", scheme)
+            }
+            OpenUrlError::LaunchFailed(e) => write!(f, "Failed to open URL: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for OpenUrlError {}
+
+impl From<OpenUrlError> for SwiftKitError {
+    fn from(e: OpenUrlError) -> Self {
+        match e {
+            OpenUrlError::LaunchFailed(_) => SwiftKitError::Io {
+                message: e.to_string(),
+                path: None,
+            },
+            _ => SwiftKitError::Validation(e.to_string()),
+        }
+    }
+}
+
+// Schemes on top of the built-in allowlist (e.g. "steam") come from the `external_url_schemes` setting
 #[tauri::command(rename_all = "snake_case")]
-pub fn open_external_url(url: String) -> Result<(), String> {
-    Command::new("powershell")
-        .creation_flags(0x08000000)
-        .arg("-Command")
-        .arg(format!("Start-Process '{}'", &url))
+pub async fn open_external_url(url: String, handle: AppHandle) -> Result<(), SwiftKitError> {
+    let settings = current(&handle).await;
+    Ok(open_url(&url, &settings.external_url_schemes)?)
+}
+
+pub fn validate_url(url: &str, extra_schemes: &[String]) -> Result<Url, OpenUrlError> {
+    let url = Url::parse(url.trim()).map_err(|e| OpenUrlError::InvalidUrl(e.to_string()))?;
+
+    // Url::parse lowercases the scheme already
+    let scheme = url.scheme();
+    let is_allowed = ALLOWED_SCHEMES.contains(&scheme)
+        || extra_schemes
+            .iter()
+            .any(|extra| extra.eq_ignore_ascii_case(scheme));
+    if !is_allowed {
+        return Err(OpenUrlError::DisallowedScheme(scheme.to_string()));
+    }
+
+    if (scheme == "http" || scheme == "https") && url.host_str().unwrap_or("").is_empty() {
+        return Err(OpenUrlError::InvalidUrl("missing host".to_string()));
+    }
+
+    Ok(url)
+}
+
+pub fn open_url(url: &str, extra_schemes: &[String]) -> Result<(), OpenUrlError> {
+    let url = validate_url(url, extra_schemes)?;
+
+    // The serialized URL is always percent-encoded and handed over as a single
+    // argument, it never goes through a shell.
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = hidden_command("rundll32");
+        command.arg("url.dll,FileProtocolHandler");
+        command
+    } else if cfg!(target_os = "macos") {
+        hidden_command("open")
+    } else {
+        hidden_command("xdg-open")
+    };
+
+    command
+        .arg(url.as_str())
         .spawn()
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| OpenUrlError::LaunchFailed(e.to_string()))?;
+
     Ok(())
 }
@@ -1,6 +1,8 @@
 use std::fs;
 use std::path::Path;
 
+use super::error::SwiftKitError;
+
 #[derive(serde::Deserialize)]
 pub struct RenameMapping {
     old: String,
@@ -12,23 +14,27 @@ pub fn rename_files(
     folder_path: String,
     rename_mapping: Vec<RenameMapping>,
     extension_filter: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), SwiftKitError> {
     let path = Path::new(&folder_path);
     if !path.exists() || !path.is_dir() {
-        return Err("Invalid folder path".into());
+        return Err(SwiftKitError::validation("Invalid folder path"));
     }
 
-    for entry in fs::read_dir(path).map_err(|e| e.to_string())? {
-        let entry = entry.map_err(|e| e.to_string())?;
-        let metadata = entry.metadata().map_err(|e| e.to_string())?;
+    for entry in fs::read_dir(path).map_err(|e| SwiftKitError::io(e, path))? {
+        let entry = entry.map_err(|e| SwiftKitError::io(e, path))?;
+        let metadata = entry
+            .metadata()
+            .map_err(|e| SwiftKitError::io(e, entry.path()))?;
         if !metadata.is_file() {
             continue;
         }
 
-        let filename = entry
-            .file_name()
-            .into_string()
-            .map_err(|e| e.to_string_lossy().into_owned())?;
+        let filename = entry.file_name().into_string().map_err(|name| {
+            SwiftKitError::validation(format!(
+                "File name is not valid UTF-8: {}",
+                name.to_string_lossy()
+            ))
+        })?;
 
         if filename.starts_with('.') {
             continue;
@@ -44,11 +50,14 @@ pub fn rename_files(
             if filename == mapping.old {
                 if mapping.new.is_empty() || mapping.new.contains('/') || mapping.new.contains('\\')
                 {
-                    return Err(format!("Invalid new filename: {}", mapping.new));
+                    return Err(SwiftKitError::validation(format!(
+                        "Invalid new filename: {}",
+                        mapping.new
+                    )));
                 }
 
                 let new_path = entry.path().with_file_name(&mapping.new);
-                fs::rename(entry.path(), new_path).map_err(|e| e.to_string())?;
+                fs::rename(entry.path(), &new_path).map_err(|e| SwiftKitError::io(e, &new_path))?;
                 break;
             }
         }
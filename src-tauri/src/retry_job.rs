@@ -0,0 +1,14 @@
+use std::sync::Arc;
+use tauri::State;
+
+use super::error::SwiftKitError;
+use super::jobs::scheduler::JobScheduler;
+
+// Puts a failed, interrupted or cancelled job back in the queue
+#[tauri::command(rename_all = "snake_case")]
+pub async fn retry_job(
+    job_id: String,
+    scheduler: State<'_, Arc<JobScheduler>>,
+) -> Result<(), SwiftKitError> {
+    scheduler.retry(&job_id).await
+}
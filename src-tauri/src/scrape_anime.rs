@@ -1,8 +1,12 @@
 use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
 use tokio::{runtime::Runtime, task};
 
+use super::error::SwiftKitError;
 use super::scrapers::mal;
 use super::scrapers::tmdb_assets::tmdb_assets;
+use super::settings::model::Settings;
+use super::settings::service::current;
 use super::utils::request_client::request_client;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -23,9 +27,13 @@ pub struct AnimeData {
 }
 
 #[tauri::command(rename_all = "snake_case")]
-pub async fn scrape_anime(url: String) -> Result<AnimeData, String> {
+pub async fn scrape_anime(handle: AppHandle, url: String) -> Result<AnimeData, SwiftKitError> {
+    scrape_with(current(&handle).await, url).await
+}
+
+pub async fn scrape_with(settings: Settings, url: String) -> Result<AnimeData, SwiftKitError> {
     task::spawn_blocking(move || {
-        Runtime::new().unwrap().block_on(async move {
+        Runtime::new()?.block_on(async move {
             let mut anime = AnimeData {
                 scraped_url: String::new(),
                 original_title: String::new(),
@@ -42,22 +50,20 @@ pub async fn scrape_anime(url: String) -> Result<AnimeData, String> {
                 trailer: None,
             };
 
-            let client = request_client().map_err(|e| e.to_string())?;
+            let client = request_client(&settings)?;
 
-            let mal_data = mal::scrape(&client, &url)
-                .await
-                .map_err(|e| e.to_string())?;
+            let mal_data = mal::scrape(&client, &url).await?;
             anime.cover = mal_data.cover;
 
             let tmdb_data = tmdb_assets(
                 &client,
                 "tv".to_string(),
                 mal_data.title.clone(),
-                mal_data.year.clone(),
+                mal_data.year,
             )
             .await;
             if let Ok(data) = tmdb_data {
-                if !data.poster.is_none() && data.poster != Some("".to_string()) {
+                if data.poster.as_deref().is_some_and(|v| !v.is_empty()) {
                     anime.poster = data.poster;
                 }
             }
@@ -77,6 +83,5 @@ pub async fn scrape_anime(url: String) -> Result<AnimeData, String> {
             Ok(anime)
         })
     })
-    .await
-    .unwrap()
+    .await?
 }
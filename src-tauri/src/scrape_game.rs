@@ -1,7 +1,11 @@
 use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
 use tokio::{runtime::Runtime, task};
 
+use super::error::SwiftKitError;
 use super::scrapers::steam;
+use super::settings::model::Settings;
+use super::settings::service::current;
 use super::utils::request_client::request_client;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -22,9 +26,13 @@ pub struct GameData {
 }
 
 #[tauri::command(rename_all = "snake_case")]
-pub async fn scrape_game(url: String) -> Result<GameData, String> {
+pub async fn scrape_game(handle: AppHandle, url: String) -> Result<GameData, SwiftKitError> {
+    scrape_with(current(&handle).await, url).await
+}
+
+pub async fn scrape_with(settings: Settings, url: String) -> Result<GameData, SwiftKitError> {
     task::spawn_blocking(move || {
-        Runtime::new().unwrap().block_on(async move {
+        Runtime::new()?.block_on(async move {
             let mut game = GameData {
                 scraped_url: String::new(),
                 title: String::new(),
@@ -41,11 +49,9 @@ pub async fn scrape_game(url: String) -> Result<GameData, String> {
                 other_images: None,
             };
 
-            let client = request_client().map_err(|e| e.to_string())?;
+            let client = request_client(&settings)?;
 
-            let steam_data = steam::scrape(&client, &url)
-                .await
-                .map_err(|e| e.to_string())?;
+            let steam_data = steam::scrape(&client, &url).await?;
 
             game.scraped_url = url;
             game.title = steam_data.title;
@@ -64,6 +70,5 @@ pub async fn scrape_game(url: String) -> Result<GameData, String> {
             Ok(game)
         })
     })
-    .await
-    .unwrap()
+    .await?
 }
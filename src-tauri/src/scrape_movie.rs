@@ -1,8 +1,12 @@
 use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
 use tokio::{runtime::Runtime, task};
 
+use super::error::SwiftKitError;
 use super::scrapers::imdb;
 use super::scrapers::tmdb_assets::tmdb_assets;
+use super::settings::model::Settings;
+use super::settings::service::current;
 use super::utils::request_client::request_client;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -28,9 +32,13 @@ pub struct MovieData {
 }
 
 #[tauri::command(rename_all = "snake_case")]
-pub async fn scrape_movie(url: String) -> Result<MovieData, String> {
+pub async fn scrape_movie(handle: AppHandle, url: String) -> Result<MovieData, SwiftKitError> {
+    scrape_with(current(&handle).await, url).await
+}
+
+pub async fn scrape_with(settings: Settings, url: String) -> Result<MovieData, SwiftKitError> {
     task::spawn_blocking(move || {
-        Runtime::new().unwrap().block_on(async move {
+        Runtime::new()?.block_on(async move {
             let mut movie = MovieData {
                 scraped_url: String::new(),
                 title: String::new(),
@@ -52,30 +60,28 @@ pub async fn scrape_movie(url: String) -> Result<MovieData, String> {
                 poster: None,
             };
 
-            let client = request_client().map_err(|e| e.to_string())?;
+            let client = request_client(&settings)?;
 
-            let imdb_data = imdb::scrape(&client, &url)
-                .await
-                .map_err(|e| e.to_string())?;
+            let imdb_data = imdb::scrape(&client, &url).await?;
 
             movie.cover = imdb_data.cover;
             let tmdb_data = tmdb_assets(
                 &client,
                 "movie".to_string(),
                 imdb_data.title.clone(),
-                imdb_data.year.clone(),
+                imdb_data.year,
             )
             .await;
             if let Ok(data) = tmdb_data {
-                if !data.cover.is_none() && data.cover != Some("".to_string()) {
+                if data.cover.as_deref().is_some_and(|v| !v.is_empty()) {
                     movie.cover = data.cover;
                 }
 
-                if !data.poster.is_none() && data.poster != Some("".to_string()) {
+                if data.poster.as_deref().is_some_and(|v| !v.is_empty()) {
                     movie.poster = data.poster;
                 }
 
-                if !data.trailer.is_none() && data.trailer != Some("".to_string()) {
+                if data.trailer.as_deref().is_some_and(|v| !v.is_empty()) {
                     movie.trailer = data.trailer;
                 }
             }
@@ -99,6 +105,5 @@ pub async fn scrape_movie(url: String) -> Result<MovieData, String> {
             Ok(movie)
         })
     })
-    .await
-    .unwrap()
+    .await?
 }
@@ -1,8 +1,9 @@
 use reqwest::Client;
-use scraper::{Html, Selector};
+use scraper::Html;
 use serde::{Deserialize, Deserializer, Serialize};
-use std::error::Error;
 
+use super::parse_selector;
+use crate::error::SwiftKitError;
 use crate::utils::{common_headers::common_headers, decode_html_entities::decode_html_entities};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -48,51 +49,50 @@ pub struct IMDBData {
     pub other_images: Option<String>,
 }
 
-pub async fn scrape(client: &Client, url: &str) -> Result<IMDBData, Box<dyn Error>> {
-    let res = client.get(url).headers(common_headers()).send().await?;
+pub async fn scrape(client: &Client, url: &str) -> Result<IMDBData, SwiftKitError> {
+    let res = client
+        .get(url)
+        .headers(common_headers())
+        .send()
+        .await?
+        .error_for_status()?;
     let body = res.text().await?;
     let document = Html::parse_document(&body);
-    let selector = Selector::parse("script[type='application/ld+json']").unwrap();
+    let selector = parse_selector("script[type='application/ld+json']")?;
     let json_data = document
         .select(&selector)
         .next()
-        .ok_or("No ld+json script found")?
+        .ok_or_else(|| SwiftKitError::parse("No ld+json script found"))?
         .text()
         .collect::<Vec<_>>()
         .join("");
-    let mut imdb_data = match serde_json::from_str::<IMDBData>(&json_data) {
-        Ok(data) => data,
-        Err(e) => {
-            eprintln!("Error parsing JSON: {}", e);
-            return Err("Error parsing JSON".into());
-        }
-    };
+    let mut imdb_data = serde_json::from_str::<IMDBData>(&json_data)
+        .map_err(|e| SwiftKitError::parse(format!("Failed to parse IMDb data: {}", e)))?;
 
     if let Some(date) = &imdb_data.release_date {
         imdb_data.year = date.split('-').next().and_then(|y| y.parse().ok());
     }
 
     // Country
-    let country_selector = Selector::parse("li[data-testid='title-details-origin'] a").unwrap();
+    let country_selector = parse_selector("li[data-testid='title-details-origin'] a")?;
     if let Some(country) = document.select(&country_selector).next() {
         imdb_data.country = Some(country.text().collect::<Vec<_>>().concat());
     }
 
     // Language
-    let language_selector = Selector::parse(
+    let language_selector = parse_selector(
         "li[data-testid='title-details-languages'] .ipc-metadata-list-item__content-container",
-    )
-    .unwrap();
+    )?;
     if let Some(language) = document.select(&language_selector).next() {
         imdb_data.language = Some(language.text().next().unwrap_or_default().to_string());
     }
 
     // Other images
-    let other_images_selector = Selector::parse("section[data-testid='Photos'] img").unwrap();
+    let other_images_selector = parse_selector("section[data-testid='Photos'] img")?;
     let images = document
         .select(&other_images_selector)
         .filter_map(|img| img.value().attr("src"))
-        .map(|url| parse_url(url))
+        .map(parse_url)
         .collect::<Vec<String>>()
         .join(", ");
 
@@ -1,7 +1,8 @@
 use reqwest::Client;
-use scraper::{ElementRef, Html, Selector};
-use std::error::Error;
+use scraper::{ElementRef, Html};
 
+use super::parse_selector;
+use crate::error::SwiftKitError;
 use crate::utils::{common_headers::common_headers, decode_html_entities::decode_html_entities};
 
 #[derive(Debug)]
@@ -19,20 +20,27 @@ pub struct MALData {
     pub trailer: Option<String>,
 }
 
-pub async fn scrape(client: &Client, url: &str) -> Result<MALData, Box<dyn Error>> {
-    let res = client.get(url).headers(common_headers()).send().await?;
+pub async fn scrape(client: &Client, url: &str) -> Result<MALData, SwiftKitError> {
+    let res = client
+        .get(url)
+        .headers(common_headers())
+        .send()
+        .await?
+        .error_for_status()?;
     let body = res.text().await?;
     let document = Html::parse_document(&body);
 
     let description = document
-        .select(&Selector::parse(".js-scrollfix-bottom-rel [itemprop='description']").unwrap())
+        .select(&parse_selector(
+            ".js-scrollfix-bottom-rel [itemprop='description']",
+        )?)
         .next()
         .map(|el| el.text().collect::<String>())
         .as_deref()
         .map(decode_html_entities);
 
     let cover = document
-        .select(&Selector::parse("img[itemprop='image']").unwrap())
+        .select(&parse_selector("img[itemprop='image']")?)
         .next()
         .and_then(|el| el.value().attr("data-src").map(|s| s.to_string()))
         .unwrap_or_default()
@@ -43,7 +51,7 @@ pub async fn scrape(client: &Client, url: &str) -> Result<MALData, Box<dyn Error
     );
 
     let original_title = document
-        .select(&Selector::parse(".title-name").unwrap())
+        .select(&parse_selector(".title-name")?)
         .next()
         .map(|el| el.text().collect::<String>())
         .as_deref()
@@ -67,7 +75,9 @@ pub async fn scrape(client: &Client, url: &str) -> Result<MALData, Box<dyn Error
     );
 
     let trailer_raw = document
-        .select(&Selector::parse("a.iframe.js-fancybox-video.video-unit.promotion").unwrap())
+        .select(&parse_selector(
+            "a.iframe.js-fancybox-video.video-unit.promotion",
+        )?)
         .next()
         .and_then(|el| el.value().attr("href").map(|s| s.to_string()));
     let trailer: Option<String> = trailer_raw
@@ -94,7 +104,7 @@ pub async fn scrape(client: &Client, url: &str) -> Result<MALData, Box<dyn Error
 }
 
 fn get_from_border(document: &Html, label: &str) -> Option<String> {
-    let selector = Selector::parse("div.spaceit_pad").unwrap();
+    let selector = parse_selector("div.spaceit_pad").ok()?;
     let div = document
         .select(&selector)
         .find(|div| div.text().any(|t| t.contains(label)))?;
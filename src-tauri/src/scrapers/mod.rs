@@ -1,4 +1,13 @@
+use scraper::Selector;
+
+use crate::error::SwiftKitError;
+
 pub mod imdb;
 pub mod mal;
 pub mod steam;
 pub mod tmdb_assets;
+
+pub fn parse_selector(css: &str) -> Result<Selector, SwiftKitError> {
+    Selector::parse(css)
+        .map_err(|e| SwiftKitError::parse(format!("Invalid selector {}: {}", css, e)))
+}
@@ -1,7 +1,8 @@
 use reqwest::Client;
-use scraper::{Html, Selector};
-use std::error::Error;
+use scraper::Html;
 
+use super::parse_selector;
+use crate::error::SwiftKitError;
 use crate::utils::{common_headers::common_headers, decode_html_entities::decode_html_entities};
 
 #[derive(Debug)]
@@ -20,13 +21,18 @@ pub struct SteamData {
     pub other_images: Option<String>,
 }
 
-pub async fn scrape(client: &Client, url: &str) -> Result<SteamData, Box<dyn Error>> {
-    let res = client.get(url).headers(common_headers()).send().await?;
+pub async fn scrape(client: &Client, url: &str) -> Result<SteamData, SwiftKitError> {
+    let res = client
+        .get(url)
+        .headers(common_headers())
+        .send()
+        .await?
+        .error_for_status()?;
     let body = res.text().await?;
     let document = Html::parse_document(&body);
 
     // TITLE
-    let selector = Selector::parse("#appHubAppName").unwrap();
+    let selector = parse_selector("#appHubAppName")?;
     let title = document
         .select(&selector)
         .next()
@@ -36,7 +42,7 @@ pub async fn scrape(client: &Client, url: &str) -> Result<SteamData, Box<dyn Err
         .unwrap_or_default();
 
     // GENRE
-    let selector = Selector::parse(".popular_tags_ctn a").unwrap();
+    let selector = parse_selector(".popular_tags_ctn a")?;
     let mut tags = vec![];
     for element in document.select(&selector) {
         let text = element
@@ -48,22 +54,18 @@ pub async fn scrape(client: &Client, url: &str) -> Result<SteamData, Box<dyn Err
     let genre = Some(tags.join(", "));
 
     // DESCRIPTION
-    let selector = Selector::parse("meta[property='og:description']").unwrap();
+    let selector = parse_selector("meta[property='og:description']")?;
     let description: Option<String> = Some(
         document
             .select(&selector)
             .next()
-            .unwrap()
-            .value()
-            .attr("content")
-            .as_deref()
+            .and_then(|el| el.value().attr("content"))
             .map(decode_html_entities)
-            .unwrap_or_default()
-            .to_string(),
+            .unwrap_or_default(),
     );
 
     // ABOUT
-    let selector = Selector::parse("#game_area_description").unwrap();
+    let selector = parse_selector("#game_area_description")?;
     let about: Option<String> = document
         .select(&selector)
         .next()
@@ -72,7 +74,7 @@ pub async fn scrape(client: &Client, url: &str) -> Result<SteamData, Box<dyn Err
         .map(decode_html_entities);
 
     // RELEASE DATE
-    let selector = Selector::parse(".date").unwrap();
+    let selector = parse_selector(".date")?;
     let release_date = document
         .select(&selector)
         .next()
@@ -90,7 +92,7 @@ pub async fn scrape(client: &Client, url: &str) -> Result<SteamData, Box<dyn Err
     };
 
     // DEVELOPERS
-    let selector = Selector::parse("#developers_list a").unwrap();
+    let selector = parse_selector("#developers_list a")?;
     let mut developers = vec![];
     for element in document.select(&selector) {
         let text = element
@@ -102,8 +104,8 @@ pub async fn scrape(client: &Client, url: &str) -> Result<SteamData, Box<dyn Err
     let developers = Some(developers.join(", "));
 
     // PUBLISHERS
-    let dev_row_selector = Selector::parse(".dev_row").unwrap();
-    let a_tag_selector = Selector::parse("a").unwrap();
+    let dev_row_selector = parse_selector(".dev_row")?;
+    let a_tag_selector = parse_selector("a")?;
     let mut publishers = vec![];
     if let Some(second_dev_row) = document.select(&dev_row_selector).nth(1) {
         for a_tag in second_dev_row.select(&a_tag_selector) {
@@ -121,23 +123,23 @@ pub async fn scrape(client: &Client, url: &str) -> Result<SteamData, Box<dyn Err
     let url = url.trim_end_matches('/');
     let url_parts: Vec<&str> = url.split('/').collect();
     let app_id: u32 = match url_parts.len() {
-        len if len == 6 => url_parts[url_parts.len() - 2].parse().unwrap_or(0),
-        len if len == 5 => url_parts[url_parts.len() - 1].parse().unwrap_or(0),
+        6 => url_parts[url_parts.len() - 2].parse().unwrap_or(0),
+        5 => url_parts[url_parts.len() - 1].parse().unwrap_or(0),
         _ => 0,
     };
 
     let cover: Option<String> = format!(
         "https://cdn.cloudflare.steamstatic.com/steam/apps/{}/hero_capsule.jpg",
-        app_id.to_string()
+        app_id
     )
     .into();
     let poster: Option<String> = format!(
         "https://cdn.cloudflare.steamstatic.com/steam/apps/{}/library_hero.jpg",
-        app_id.to_string()
+        app_id
     )
     .into();
 
-    let selector = Selector::parse(".highlight_player_item").unwrap();
+    let selector = parse_selector(".highlight_player_item")?;
     let trailer_id_string = document
         .select(&selector)
         .next()
@@ -156,11 +158,9 @@ pub async fn scrape(client: &Client, url: &str) -> Result<SteamData, Box<dyn Err
             id
         )
         .into();
-    } else {
-        Some("");
     }
 
-    let selector = Selector::parse(".highlight_screenshot_link").unwrap();
+    let selector = parse_selector(".highlight_screenshot_link")?;
     let hrefs: Vec<String> = document
         .select(&selector)
         .filter_map(|a| a.value().attr("href").map(|href| href.to_string()))
@@ -1,8 +1,9 @@
 use reqwest::Client;
-use scraper::{Html, Selector};
+use scraper::Html;
 use serde::{Deserialize, Serialize};
-use std::error::Error as StdError;
 
+use super::parse_selector;
+use crate::error::SwiftKitError;
 use crate::utils::common_headers::common_headers;
 
 pub async fn tmdb_assets(
@@ -10,7 +11,7 @@ pub async fn tmdb_assets(
     content_type: String,
     title: String,
     year: Option<u16>,
-) -> Result<TMDBAssetData, Box<dyn StdError + Send + Sync>> {
+) -> Result<TMDBAssetData, SwiftKitError> {
     let mut data = TMDBAssetData {
         cover: None,
         poster: None,
@@ -33,11 +34,12 @@ pub async fn tmdb_assets(
         .get(&search_url)
         .headers(common_headers())
         .send()
-        .await?;
+        .await?
+        .error_for_status()?;
     let body = res.text().await?;
     let document = Html::parse_document(&body);
 
-    let selector = Selector::parse(".white_column a").unwrap();
+    let selector = parse_selector(".white_column a")?;
     if let Some(first_item) = document.select(&selector).next() {
         let mut href = first_item.value().attr("href").unwrap_or("");
         let fallback_href = &format!("https://www.themoviedb.org{}", href);
@@ -45,7 +47,7 @@ pub async fn tmdb_assets(
             href = fallback_href;
         }
 
-        data = fetch_tmdb_assets(&client, href.to_string()).await.unwrap()
+        data = fetch_tmdb_assets(client, href.to_string()).await?;
     }
 
     Ok(data)
@@ -61,7 +63,7 @@ pub struct TMDBAssetData {
 pub async fn fetch_tmdb_assets(
     client: &Client,
     poster_href: String,
-) -> Result<TMDBAssetData, Box<dyn StdError + Send + Sync>> {
+) -> Result<TMDBAssetData, SwiftKitError> {
     let mut data = TMDBAssetData {
         cover: None,
         poster: None,
@@ -71,22 +73,23 @@ pub async fn fetch_tmdb_assets(
         .get(&poster_href)
         .headers(common_headers())
         .send()
-        .await?;
+        .await?
+        .error_for_status()?;
     let body = res.text().await?;
     let document = Html::parse_document(&body);
 
-    let selector = Selector::parse("meta[property='og:image']").unwrap();
+    let selector = parse_selector("meta[property='og:image']")?;
     let mut images = vec![];
     for element in document.select(&selector) {
         images.push(element.value().attr("content").unwrap_or(""));
     }
 
-    data.cover = Some(images.get(0).cloned().unwrap_or_default().to_string());
+    data.cover = Some(images.first().cloned().unwrap_or_default().to_string());
     data.cover = get_original_image_sizes(data.cover);
     data.poster = Some(images.get(1).cloned().unwrap_or_default().to_string());
     data.poster = get_original_image_sizes(data.poster);
 
-    let selector = Selector::parse("a[data-title='Play Trailer']").unwrap();
+    let selector = parse_selector("a[data-title='Play Trailer']")?;
     if let Some(video_element) = document.select(&selector).next() {
         if let Some(src) = video_element.attr("data-id") {
             let yt_embed_id = src.to_string();
@@ -98,8 +101,5 @@ pub async fn fetch_tmdb_assets(
 }
 
 fn get_original_image_sizes(poster: Option<String>) -> Option<String> {
-    match poster {
-        Some(url) => Some(url.replace("/w780/", "/original/")),
-        None => None,
-    }
+    poster.map(|url| url.replace("/w780/", "/original/"))
 }
@@ -1,7 +1,11 @@
-use scraper::{Html, Selector};
+use scraper::Html;
 use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
 use tokio::{runtime::Runtime, task};
 
+use super::error::SwiftKitError;
+use super::scrapers::parse_selector;
+use super::settings::service::current;
 use super::utils::request_client::request_client;
 use crate::utils::common_headers::common_headers;
 
@@ -13,26 +17,30 @@ pub struct AnimeSearchResult {
 }
 
 #[tauri::command(rename_all = "snake_case")]
-pub async fn search_anime(query: String) -> Result<Vec<AnimeSearchResult>, String> {
+pub async fn search_anime(
+    handle: AppHandle,
+    query: String,
+) -> Result<Vec<AnimeSearchResult>, SwiftKitError> {
+    let settings = current(&handle).await;
     task::spawn_blocking(move || {
-        Runtime::new().unwrap().block_on(async move {
+        Runtime::new()?.block_on(async move {
             let mut results = Vec::new();
             let search_url = format!("https://myanimelist.net/anime.php?q={}&cat=anime", query);
-            let client = request_client().map_err(|e| e.to_string())?;
+            let client = request_client(&settings)?;
 
             let res = client
                 .get(&search_url)
                 .headers(common_headers())
                 .send()
-                .await
-                .map_err(|e| e.to_string())?;
-            let body = res.text().await.map_err(|e| e.to_string())?;
+                .await?
+                .error_for_status()?;
+            let body = res.text().await?;
             let document = Html::parse_document(&body);
 
-            let tr_selector = Selector::parse("tr").unwrap();
-            let img_selector = Selector::parse("a[href*='/anime/'] > img").unwrap();
-            let title_selector = Selector::parse("a[href*='/anime/'][id^='sinfo']").unwrap();
-            let hover_selector = Selector::parse("div[id^='sinfo'] a[href*='/anime/']").unwrap();
+            let tr_selector = parse_selector("tr")?;
+            let img_selector = parse_selector("a[href*='/anime/'] > img")?;
+            let title_selector = parse_selector("a[href*='/anime/'][id^='sinfo']")?;
+            let hover_selector = parse_selector("div[id^='sinfo'] a[href*='/anime/']")?;
 
             for row in document.select(&tr_selector) {
                 if let Some(link_el) = row.select(&title_selector).next() {
@@ -52,28 +60,16 @@ pub async fn search_anime(query: String) -> Result<Vec<AnimeSearchResult>, Strin
                                 .attr("data-src")
                                 .or_else(|| img.value().attr("src"))
                         })
-                        .map(String::from)
-                        .unwrap()
-                        .to_string()
-                        .replace("/r/50x70", "")
-                        .replace("webp", "jpg");
+                        .map(|src| src.replace("/r/50x70", "").replace("webp", "jpg"));
                     let cover = cover_raw
-                        .split('?')
-                        .next()
-                        .unwrap_or(&cover_raw)
-                        .to_string();
+                        .map(|cover| cover.split('?').next().unwrap_or(&cover).to_string());
 
-                    results.push(AnimeSearchResult {
-                        href,
-                        title,
-                        cover: Some(cover),
-                    });
+                    results.push(AnimeSearchResult { href, title, cover });
                 }
             }
 
             Ok(results)
         })
     })
-    .await
-    .unwrap()
+    .await?
 }
@@ -1,8 +1,11 @@
-use scraper::{Html, Selector};
+use scraper::Html;
 use serde::{Deserialize, Serialize};
-use tauri::Url;
+use tauri::{AppHandle, Url};
 use tokio::{runtime::Runtime, task};
 
+use super::error::SwiftKitError;
+use super::scrapers::parse_selector;
+use super::settings::service::current;
 use super::utils::request_client::request_client;
 use crate::utils::common_headers::common_headers;
 
@@ -14,24 +17,28 @@ pub struct GameSearchResult {
 }
 
 #[tauri::command(rename_all = "snake_case")]
-pub async fn search_game(query: String) -> Result<Vec<GameSearchResult>, String> {
+pub async fn search_game(
+    handle: AppHandle,
+    query: String,
+) -> Result<Vec<GameSearchResult>, SwiftKitError> {
+    let settings = current(&handle).await;
     task::spawn_blocking(move || {
-        Runtime::new().unwrap().block_on(async move {
+        Runtime::new()?.block_on(async move {
             let mut results = Vec::new();
             let search_url = format!("https://store.steampowered.com/search/?term={}&category1=998&os=win&supportedlang=english&ndl=1", query);
-            let client = request_client().map_err(|e| e.to_string())?;
+            let client = request_client(&settings)?;
 
             let res = client
                 .get(&search_url)
                 .headers(common_headers())
                 .send()
-                .await
-                .map_err(|e| e.to_string())?;
-            let body = res.text().await.map_err(|e| e.to_string())?;
+                .await?
+                .error_for_status()?;
+            let body = res.text().await?;
             let document = Html::parse_document(&body);
-			let selector = Selector::parse("#search_results a").unwrap();
-			let title_selector = Selector::parse(".title").unwrap();
-			let cover_selector = Selector::parse("img").unwrap();
+			let selector = parse_selector("#search_results a")?;
+			let title_selector = parse_selector(".title")?;
+			let cover_selector = parse_selector("img")?;
             for row in document.select(&selector) {
 				let href_raw = row.value().attr("href").unwrap_or_default().to_string();
 				let href = href_raw.split('?').next().unwrap_or(&href_raw).to_string();
@@ -63,6 +70,5 @@ pub async fn search_game(query: String) -> Result<Vec<GameSearchResult>, String>
             Ok(results)
         })
     })
-    .await
-    .unwrap()
+    .await?
 }
@@ -1,7 +1,11 @@
-use scraper::{Html, Selector};
+use scraper::Html;
 use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
 use tokio::{runtime::Runtime, task};
 
+use super::error::SwiftKitError;
+use super::scrapers::parse_selector;
+use super::settings::service::current;
 use super::utils::common_headers::common_headers;
 use super::utils::request_client::request_client;
 
@@ -13,27 +17,30 @@ pub struct MovieSearchResult {
 }
 
 #[tauri::command(rename_all = "snake_case")]
-pub async fn search_movie(query: String) -> Result<Vec<MovieSearchResult>, String> {
+pub async fn search_movie(
+    handle: AppHandle,
+    query: String,
+) -> Result<Vec<MovieSearchResult>, SwiftKitError> {
+    let settings = current(&handle).await;
     task::spawn_blocking(move || {
-        Runtime::new().unwrap().block_on(async move {
+        Runtime::new()?.block_on(async move {
             let mut results = Vec::new();
             let search_url = format!("https://www.imdb.com/find/?q={}", query);
-            let client = request_client().map_err(|e| e.to_string())?;
+            let client = request_client(&settings)?;
 
             let res = client
                 .get(&search_url)
                 .headers(common_headers())
                 .send()
-                .await
-                .map_err(|e| e.to_string())?;
-            let body = res.text().await.map_err(|e| e.to_string())?;
+                .await?
+                .error_for_status()?;
+            let body = res.text().await?;
             let document = Html::parse_document(&body);
 
-            let selector =
-                Selector::parse("section[data-testid='find-results-section-title'] li").unwrap();
+            let selector = parse_selector("section[data-testid='find-results-section-title'] li")?;
 
             for element in document.select(&selector) {
-                if let Some(anchor) = element.select(&Selector::parse("a").unwrap()).next() {
+                if let Some(anchor) = element.select(&parse_selector("a")?).next() {
                     let title = anchor
                         .text()
                         .collect::<Vec<_>>()
@@ -45,7 +52,7 @@ pub async fn search_movie(query: String) -> Result<Vec<MovieSearchResult>, Strin
                     let cleaned_href = full_href.split('?').next().unwrap_or(&full_href);
 
                     let cover = element
-                        .select(&Selector::parse("img").unwrap())
+                        .select(&parse_selector("img")?)
                         .next()
                         .and_then(|img| img.value().attr("src").map(|s| s.to_string()));
                     results.push(MovieSearchResult {
@@ -59,6 +66,5 @@ pub async fn search_movie(query: String) -> Result<Vec<MovieSearchResult>, Strin
             Ok(results)
         })
     })
-    .await
-    .unwrap()
+    .await?
 }
@@ -0,0 +1,15 @@
+use std::sync::Arc;
+use tauri::State;
+
+use super::error::SwiftKitError;
+use super::jobs::manager::JobKind;
+use super::jobs::scheduler::JobScheduler;
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_job_concurrency(
+    kind: JobKind,
+    limit: usize,
+    scheduler: State<'_, Arc<JobScheduler>>,
+) -> Result<(), SwiftKitError> {
+    scheduler.set_limit(kind, limit).await
+}
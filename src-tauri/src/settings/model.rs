@@ -0,0 +1,307 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::error::SwiftKitError;
+use crate::img_compressors::backend::CompressionBackend;
+use crate::tools::registry::Tool;
+
+pub const VIDEO_ENCODERS: [&str; 3] = ["h264_nvenc", "hevc_nvenc", "libx264"];
+pub const RIFE_MODELS: [&str; 4] = ["rife-anime", "rife-v4.26", "rife-v4.25-lite", "rife-v4.6"];
+// Never opened through `open_external_url`, even when listed as an extra scheme
+const BLOCKED_URL_SCHEMES: [&str; 4] = ["file", "javascript", "data", "vbscript"];
+
+// Typed view over the `settings` table, every field is stored as its own row named after it.
+// Tool paths keep the `<tool>_binary_path` names the resolver already reads.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    pub ffmpeg_binary_path: Option<String>,
+    pub ffprobe_binary_path: Option<String>,
+    pub yt_dlp_binary_path: Option<String>,
+    pub jpegoptim_binary_path: Option<String>,
+    pub pngquant_binary_path: Option<String>,
+    pub cwebp_binary_path: Option<String>,
+    pub video2x_binary_path: Option<String>,
+    pub download_folder: Option<String>,
+    pub image_output_folder: Option<String>,
+    pub image_quality: Option<u8>,
+    pub compression_backend: CompressionBackend,
+    pub video_encoder: String,
+    pub rife_model: String,
+    pub interpolation_multiplier: u8,
+    pub use_cuda: bool,
+    pub download_rate_limit_kb: Option<u64>,
+    pub yt_download_rate_kb: Option<u32>,
+    pub proxy: Option<String>,
+    pub scraper_language: String,
+    // Opened by `open_external_url` on top of http, https and mailto, e.g. "steam"
+    pub external_url_schemes: Vec<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            ffmpeg_binary_path: None,
+            ffprobe_binary_path: None,
+            yt_dlp_binary_path: None,
+            jpegoptim_binary_path: None,
+            pngquant_binary_path: None,
+            cwebp_binary_path: None,
+            video2x_binary_path: None,
+            download_folder: None,
+            image_output_folder: None,
+            image_quality: None,
+            compression_backend: CompressionBackend::default(),
+            video_encoder: "h264_nvenc".to_string(),
+            rife_model: "rife-v4.6".to_string(),
+            interpolation_multiplier: 2,
+            use_cuda: true,
+            download_rate_limit_kb: None,
+            yt_download_rate_kb: None,
+            proxy: None,
+            scraper_language: "en-US".to_string(),
+            external_url_schemes: Vec::new(),
+        }
+    }
+}
+
+impl Settings {
+    // Unparsable rows fall back to their default instead of failing the whole load
+    pub fn from_rows(rows: &HashMap<String, String>) -> Self {
+        let defaults = Settings::default();
+        let text = |name: &str| {
+            rows.get(name)
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+
+        Self {
+            ffmpeg_binary_path: text(Tool::Ffmpeg.setting_key()),
+            ffprobe_binary_path: text(Tool::Ffprobe.setting_key()),
+            yt_dlp_binary_path: text(Tool::YtDlp.setting_key()),
+            jpegoptim_binary_path: text(Tool::Jpegoptim.setting_key()),
+            pngquant_binary_path: text(Tool::Pngquant.setting_key()),
+            cwebp_binary_path: text(Tool::Cwebp.setting_key()),
+            video2x_binary_path: text(Tool::Video2x.setting_key()),
+            download_folder: text("download_folder"),
+            image_output_folder: text("image_output_folder"),
+            image_quality: parse(text("image_quality")),
+            compression_backend: parse(text("compression_backend")).unwrap_or_default(),
+            video_encoder: text("video_encoder")
+                .filter(|encoder| VIDEO_ENCODERS.contains(&encoder.as_str()))
+                .unwrap_or(defaults.video_encoder),
+            rife_model: text("rife_model")
+                .filter(|model| RIFE_MODELS.contains(&model.as_str()))
+                .unwrap_or(defaults.rife_model),
+            interpolation_multiplier: parse(text("interpolation_multiplier"))
+                .unwrap_or(defaults.interpolation_multiplier),
+            use_cuda: parse(text("use_cuda")).unwrap_or(defaults.use_cuda),
+            download_rate_limit_kb: parse(text("download_rate_limit_kb")),
+            yt_download_rate_kb: parse(text("yt_download_rate_kb")),
+            proxy: text("proxy"),
+            scraper_language: text("scraper_language").unwrap_or(defaults.scraper_language),
+            external_url_schemes: text("external_url_schemes")
+                .map(|schemes| {
+                    schemes
+                        .split(',')
+                        .map(|scheme| scheme.trim().to_lowercase())
+                        .filter(|scheme| !scheme.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    // `None` is stored as an empty value, the same way the frontend clears a setting
+    pub fn to_rows(&self) -> Vec<(&'static str, String)> {
+        let text = |value: &Option<String>| value.clone().unwrap_or_default();
+        let number = |value: Option<u64>| value.map(|value| value.to_string()).unwrap_or_default();
+
+        vec![
+            (Tool::Ffmpeg.setting_key(), text(&self.ffmpeg_binary_path)),
+            (Tool::Ffprobe.setting_key(), text(&self.ffprobe_binary_path)),
+            (Tool::YtDlp.setting_key(), text(&self.yt_dlp_binary_path)),
+            (
+                Tool::Jpegoptim.setting_key(),
+                text(&self.jpegoptim_binary_path),
+            ),
+            (
+                Tool::Pngquant.setting_key(),
+                text(&self.pngquant_binary_path),
+            ),
+            (Tool::Cwebp.setting_key(), text(&self.cwebp_binary_path)),
+            (Tool::Video2x.setting_key(), text(&self.video2x_binary_path)),
+            ("download_folder", text(&self.download_folder)),
+            ("image_output_folder", text(&self.image_output_folder)),
+            ("image_quality", number(self.image_quality.map(u64::from))),
+            (
+                "compression_backend",
+                self.compression_backend.as_str().to_string(),
+            ),
+            ("video_encoder", self.video_encoder.clone()),
+            ("rife_model", self.rife_model.clone()),
+            (
+                "interpolation_multiplier",
+                self.interpolation_multiplier.to_string(),
+            ),
+            ("use_cuda", self.use_cuda.to_string()),
+            (
+                "download_rate_limit_kb",
+                number(self.download_rate_limit_kb),
+            ),
+            (
+                "yt_download_rate_kb",
+                number(self.yt_download_rate_kb.map(u64::from)),
+            ),
+            ("proxy", text(&self.proxy)),
+            ("scraper_language", self.scraper_language.clone()),
+            ("external_url_schemes", self.external_url_schemes.join(",")),
+        ]
+    }
+
+    pub fn tool_path(&self, tool: Tool) -> Option<&str> {
+        match tool {
+            Tool::Ffmpeg => self.ffmpeg_binary_path.as_deref(),
+            Tool::Ffprobe => self.ffprobe_binary_path.as_deref(),
+            Tool::YtDlp => self.yt_dlp_binary_path.as_deref(),
+            Tool::Jpegoptim => self.jpegoptim_binary_path.as_deref(),
+            Tool::Pngquant => self.pngquant_binary_path.as_deref(),
+            Tool::Cwebp => self.cwebp_binary_path.as_deref(),
+            Tool::Video2x => self.video2x_binary_path.as_deref(),
+        }
+    }
+
+    // Blank strings coming from form inputs mean "not set"
+    pub fn normalize(&mut self) {
+        for value in [
+            &mut self.ffmpeg_binary_path,
+            &mut self.ffprobe_binary_path,
+            &mut self.yt_dlp_binary_path,
+            &mut self.jpegoptim_binary_path,
+            &mut self.pngquant_binary_path,
+            &mut self.cwebp_binary_path,
+            &mut self.video2x_binary_path,
+            &mut self.download_folder,
+            &mut self.image_output_folder,
+            &mut self.proxy,
+        ] {
+            *value = value
+                .take()
+                .map(|text| text.trim().to_string())
+                .filter(|text| !text.is_empty());
+        }
+        self.scraper_language = self.scraper_language.trim().to_string();
+
+        let mut schemes = Vec::new();
+        for scheme in &self.external_url_schemes {
+            let scheme = scheme.trim().trim_end_matches(':').to_lowercase();
+            if !scheme.is_empty() && !schemes.contains(&scheme) {
+                schemes.push(scheme);
+            }
+        }
+        self.external_url_schemes = schemes;
+    }
+
+    pub fn validate(&self) -> Result<(), SwiftKitError> {
+        for tool in Tool::ALL {
+            if let Some(path) = self.tool_path(tool) {
+                if !Path::new(path).exists() {
+                    return Err(SwiftKitError::validation(format!(
+                        "{} path does not exist: {}",
+                        tool.binary_name(),
+                        path
+                    )));
+                }
+            }
+        }
+
+        for (name, folder) in [
+            ("Download folder", &self.download_folder),
+            ("Image output folder", &self.image_output_folder),
+        ] {
+            if let Some(folder) = folder.as_deref() {
+                if !Path::new(folder).is_dir() {
+                    return Err(SwiftKitError::validation(format!(
+                        "{} is not a directory: {}",
+                        name, folder
+                    )));
+                }
+            }
+        }
+
+        if self
+            .image_quality
+            .is_some_and(|quality| !(1..=100).contains(&quality))
+        {
+            return Err(SwiftKitError::validation(
+                "Image quality must be between 1 and 100.",
+            ));
+        }
+
+        if !VIDEO_ENCODERS.contains(&self.video_encoder.as_str()) {
+            return Err(SwiftKitError::validation(format!(
+                "Unsupported encoder: {}",
+                self.video_encoder
+            )));
+        }
+
+        if !RIFE_MODELS.contains(&self.rife_model.as_str()) {
+            return Err(SwiftKitError::validation(format!(
+                "Unsupported RIFE model: {}",
+                self.rife_model
+            )));
+        }
+
+        if !(2..=16).contains(&self.interpolation_multiplier) {
+            return Err(SwiftKitError::validation(
+                "Interpolation multiplier must be between 2 and 16.",
+            ));
+        }
+
+        if let Some(proxy) = self.proxy.as_deref() {
+            reqwest::Proxy::all(proxy)
+                .map_err(|e| SwiftKitError::validation(format!("Invalid proxy: {}", e)))?;
+        }
+
+        let is_language_tag = !self.scraper_language.is_empty()
+            && self
+                .scraper_language
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-');
+        if !is_language_tag {
+            return Err(SwiftKitError::validation(format!(
+                "Invalid scraper language: {}",
+                self.scraper_language
+            )));
+        }
+
+        // RFC 3986: a letter followed by letters, digits, "+", "-" or "."
+        for scheme in &self.external_url_schemes {
+            let is_scheme = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+            if !is_scheme {
+                return Err(SwiftKitError::validation(format!(
+                    "Invalid URL scheme: {}",
+                    scheme
+                )));
+            }
+            if BLOCKED_URL_SCHEMES.contains(&scheme.as_str()) {
+                return Err(SwiftKitError::validation(format!(
+                    "URL scheme can't be allowed: {}",
+                    scheme
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn parse<T: FromStr>(value: Option<String>) -> Option<T> {
+    value.and_then(|value| value.parse().ok())
+}
@@ -0,0 +1,94 @@
+use serde_json::Value;
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+use tokio::sync::RwLock;
+
+use super::model::Settings;
+use crate::db::set_setting;
+use crate::error::SwiftKitError;
+
+// Caches the typed settings so commands don't hit the database on every call.
+// Writes go through `update`, which refreshes the cache from the table it just saved.
+pub struct SettingsService {
+    pool: SqlitePool,
+    cache: RwLock<Option<Settings>>,
+}
+
+impl SettingsService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            pool,
+            cache: RwLock::new(None),
+        }
+    }
+
+    pub async fn get(&self) -> Result<Settings, SwiftKitError> {
+        if let Some(settings) = self.cache.read().await.as_ref() {
+            return Ok(settings.clone());
+        }
+
+        let mut cache = self.cache.write().await;
+        let settings = load(&self.pool).await?;
+        *cache = Some(settings.clone());
+        Ok(settings)
+    }
+
+    // `changes` holds only the fields to update, the rest keep their current value
+    pub async fn update(&self, changes: Value) -> Result<Settings, SwiftKitError> {
+        let Value::Object(changes) = changes else {
+            return Err(SwiftKitError::validation("Settings must be an object."));
+        };
+
+        // The table is re-read so rows written outside the service aren't overwritten with
+        // stale values, a failed save leaves the cache empty for the next `get` to reload
+        let mut cache = self.cache.write().await;
+        *cache = None;
+        let current = load(&self.pool).await?;
+
+        let mut merged = serde_json::to_value(&current)?;
+        if let Value::Object(fields) = &mut merged {
+            fields.extend(changes);
+        }
+        let mut settings: Settings = serde_json::from_value(merged)
+            .map_err(|e| SwiftKitError::validation(format!("Invalid settings: {}", e)))?;
+        settings.normalize();
+        settings.validate()?;
+
+        let previous = current.to_rows();
+        for (row, (name, value)) in settings.to_rows().into_iter().enumerate() {
+            if previous[row].1 != value {
+                set_setting(&self.pool, name, "", &value).await?;
+            }
+        }
+
+        *cache = Some(settings.clone());
+        Ok(settings)
+    }
+}
+
+// Reads the settings straight from the table, for code running without the app
+pub async fn load(pool: &SqlitePool) -> Result<Settings, SwiftKitError> {
+    let rows: HashMap<String, String> =
+        sqlx::query_as::<_, (String, Option<String>)>("SELECT name, value FROM settings")
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .filter_map(|(name, value)| value.map(|value| (name, value)))
+            .collect();
+
+    Ok(Settings::from_rows(&rows))
+}
+
+// Settings for a command, defaults are used when the table can't be read yet
+pub async fn current(handle: &AppHandle) -> Settings {
+    let Some(service) = handle.try_state::<Arc<SettingsService>>() else {
+        return Settings::default();
+    };
+
+    service.get().await.unwrap_or_else(|e| {
+        tracing::warn!("Falling back to default settings: {}", e);
+        Settings::default()
+    })
+}
@@ -0,0 +1,68 @@
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use tokio::task;
+
+use super::error::SwiftKitError;
+use super::img_encoders::encode::{encode_with_metadata, open_image_with_metadata, OutputFormat};
+use super::img_encoders::options::EncodeOptions;
+use super::img_metadata::mode::MetadataMode;
+use super::img_transforms::smart_crop::{crop, Rect};
+use super::utils::resolve_output_path::resolve_output_path;
+
+#[derive(Debug, Serialize)]
+pub struct SmartCrop {
+    pub output: String,
+    // The part of the source that was kept, before scaling to the requested size
+    pub rect: Rect,
+}
+
+// One image per call, batches go through `image_pipeline` with a `smart_crop` operation
+#[tauri::command(rename_all = "snake_case")]
+pub async fn smart_crop(
+    img_path: String,
+    width: u32,
+    height: u32,
+    output_folder: Option<String>,
+    file_name: Option<String>,
+    metadata: Option<MetadataMode>,
+) -> Result<SmartCrop, SwiftKitError> {
+    let input_path = Path::new(&img_path);
+    if !input_path.exists() || !input_path.is_file() {
+        return Err(SwiftKitError::validation("Invalid image path"));
+    }
+    if width == 0 || height == 0 {
+        return Err(SwiftKitError::validation("Crop size can't be zero"));
+    }
+
+    let output_path = resolve_output_path(
+        input_path,
+        output_folder.as_deref().map(Path::new),
+        file_name.as_deref(),
+        None,
+    )?;
+    let output_format = OutputFormat::from_path(&output_path)
+        .ok_or_else(|| SwiftKitError::validation("Unsupported file format"))?;
+
+    let input_path = input_path.to_path_buf();
+    let (rect, bytes) = task::spawn_blocking(move || {
+        let (img, source) = open_image_with_metadata(&input_path)?;
+        let (rect, cropped) = crop(&img, width, height);
+        encode_with_metadata(
+            &cropped,
+            output_format,
+            &EncodeOptions::default(),
+            &source,
+            metadata.unwrap_or_default(),
+        )
+        .map(|bytes| (rect, bytes))
+    })
+    .await??;
+
+    fs::write(&output_path, bytes).map_err(|e| SwiftKitError::io(e, &output_path))?;
+
+    Ok(SmartCrop {
+        output: output_path.to_string_lossy().to_string(),
+        rect,
+    })
+}
@@ -0,0 +1,3 @@
+pub mod probe;
+pub mod registry;
+pub mod resolver;
@@ -0,0 +1,162 @@
+use serde::Serialize;
+use std::path::Path;
+
+use super::registry::Tool;
+use super::resolver::{locate, ToolSource};
+use crate::utils::hidden_command::hidden_command;
+
+const FFMPEG_ENCODERS: [&str; 5] = ["h264_nvenc", "hevc_nvenc", "libx264", "libx265", "aac"];
+const FFMPEG_HWACCELS: [&str; 1] = ["cuda"];
+const FFMPEG_FILTERS: [&str; 1] = ["subtitles"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolState {
+    Ready,
+    Missing,
+    Outdated,
+    Broken,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ToolStatus {
+    pub tool: Tool,
+    pub state: ToolState,
+    pub path: Option<String>,
+    pub source: Option<ToolSource>,
+    pub version: Option<String>,
+    pub minimum_version: String,
+    pub features: Vec<String>,
+    pub message: Option<String>,
+}
+
+pub fn probe_tool(tool: Tool, override_path: Option<&Path>) -> ToolStatus {
+    let mut status = ToolStatus {
+        tool,
+        state: ToolState::Missing,
+        path: None,
+        source: None,
+        version: None,
+        minimum_version: tool.minimum_version().to_string(),
+        features: Vec::new(),
+        message: None,
+    };
+
+    let Some(resolved) = locate(tool, override_path) else {
+        status.message = Some(format!("{} was not found", tool.binary_name()));
+        return status;
+    };
+
+    if let Some(path) = override_path.filter(|_| resolved.source != ToolSource::Settings) {
+        status.message = Some(format!(
+            "Configured path {} does not contain {}, falling back to {}",
+            path.display(),
+            tool.binary_name(),
+            resolved.path.display()
+        ));
+    }
+    status.path = Some(resolved.path.to_string_lossy().to_string());
+    status.source = Some(resolved.source);
+
+    let version_output = match run_tool(&resolved.path, tool.version_args()) {
+        Ok(output) => output,
+        Err(e) => {
+            status.state = ToolState::Broken;
+            status.message = Some(e);
+            return status;
+        }
+    };
+
+    status.version = parse_version(&version_output);
+    status.state = match status.version.as_deref() {
+        Some(version) if is_older(version, tool.minimum_version()) => ToolState::Outdated,
+        // Nightly and git builds don't print a release number, assume they are recent
+        _ => ToolState::Ready,
+    };
+
+    if tool == Tool::Ffmpeg {
+        status.features = ffmpeg_features(&resolved.path);
+    }
+
+    status
+}
+
+fn run_tool(path: &Path, args: &[&str]) -> Result<String, String> {
+    let output = hidden_command(path)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to execute {}: {}", path.display(), e))?;
+
+    // Some tools print their version to stderr or exit with 1 on --version
+    let mut text = String::from_utf8_lossy(&output.stdout).to_string();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    if !output.status.success() && text.trim().is_empty() {
+        return Err(format!(
+            "{} exited with status {}",
+            path.display(),
+            output.status
+        ));
+    }
+
+    Ok(text)
+}
+
+// Picks the first dotted number on the first line, e.g. "ffmpeg version 6.1.1-essentials"
+// or "jpegoptim v1.5.5 x86_64-pc-linux-gnu"
+pub fn parse_version(output: &str) -> Option<String> {
+    let first_line = output.lines().find(|line| !line.trim().is_empty())?;
+    first_line.split_whitespace().find_map(|token| {
+        let token = token.trim_start_matches(['v', 'V']);
+        let version: String = token
+            .chars()
+            .take_while(|c| c.is_ascii_digit() || *c == '.')
+            .collect();
+        let version = version.trim_end_matches('.');
+
+        (version.contains('.') && version.starts_with(|c: char| c.is_ascii_digit()))
+            .then(|| version.to_string())
+    })
+}
+
+pub fn is_older(version: &str, minimum: &str) -> bool {
+    let parts = |v: &str| -> Vec<u64> { v.split('.').map(|p| p.parse().unwrap_or(0)).collect() };
+    let (version, minimum) = (parts(version), parts(minimum));
+
+    // Missing components count as zero so "5.1" and "5.1.0" are equal
+    for i in 0..version.len().max(minimum.len()) {
+        let (a, b) = (
+            version.get(i).copied().unwrap_or(0),
+            minimum.get(i).copied().unwrap_or(0),
+        );
+        if a != b {
+            return a < b;
+        }
+    }
+    false
+}
+
+fn ffmpeg_features(ffmpeg: &Path) -> Vec<String> {
+    let mut features = Vec::new();
+    let listings: [(&str, &str, &[&str]); 3] = [
+        ("-encoders", "encoder", &FFMPEG_ENCODERS),
+        ("-hwaccels", "hwaccel", &FFMPEG_HWACCELS),
+        ("-filters", "filter", &FFMPEG_FILTERS),
+    ];
+
+    for (flag, kind, wanted) in listings {
+        let Ok(listing) = run_tool(ffmpeg, &["-hide_banner", flag]) else {
+            continue;
+        };
+
+        for name in wanted {
+            let available = listing
+                .lines()
+                .any(|line| line.split_whitespace().any(|token| token == *name));
+            if available {
+                features.push(format!("{}:{}", kind, name));
+            }
+        }
+    }
+
+    features
+}
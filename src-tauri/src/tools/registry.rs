@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Tool {
+    Ffmpeg,
+    Ffprobe,
+    YtDlp,
+    Jpegoptim,
+    Pngquant,
+    Cwebp,
+    Video2x,
+}
+
+impl Tool {
+    pub const ALL: [Tool; 7] = [
+        Tool::Ffmpeg,
+        Tool::Ffprobe,
+        Tool::YtDlp,
+        Tool::Jpegoptim,
+        Tool::Pngquant,
+        Tool::Cwebp,
+        Tool::Video2x,
+    ];
+
+    // Executable name without the platform suffix
+    pub fn binary_name(&self) -> &'static str {
+        match self {
+            Tool::Ffmpeg => "ffmpeg",
+            Tool::Ffprobe => "ffprobe",
+            Tool::YtDlp => "yt-dlp",
+            Tool::Jpegoptim => "jpegoptim",
+            Tool::Pngquant => "pngquant",
+            Tool::Cwebp => "cwebp",
+            Tool::Video2x => "video2x",
+        }
+    }
+
+    // Row in the settings table holding a user supplied path, either the binary or its folder
+    pub fn setting_key(&self) -> &'static str {
+        match self {
+            Tool::Ffmpeg => "ffmpeg_binary_path",
+            Tool::Ffprobe => "ffprobe_binary_path",
+            Tool::YtDlp => "yt_dlp_binary_path",
+            Tool::Jpegoptim => "jpegoptim_binary_path",
+            Tool::Pngquant => "pngquant_binary_path",
+            Tool::Cwebp => "cwebp_binary_path",
+            Tool::Video2x => "video2x_binary_path",
+        }
+    }
+
+    // Whether the binary is shipped through `bundle.externalBin`
+    pub fn is_bundled(&self) -> bool {
+        matches!(
+            self,
+            Tool::Ffmpeg | Tool::Ffprobe | Tool::Jpegoptim | Tool::Pngquant | Tool::Cwebp
+        )
+    }
+
+    pub fn version_args(&self) -> &'static [&'static str] {
+        match self {
+            Tool::Ffmpeg | Tool::Ffprobe => &["-hide_banner", "-version"],
+            Tool::Cwebp => &["-version"],
+            _ => &["--version"],
+        }
+    }
+
+    // Oldest release supporting every flag the app passes to the tool
+    pub fn minimum_version(&self) -> &'static str {
+        match self {
+            // -fps_mode landed in 5.1
+            Tool::Ffmpeg | Tool::Ffprobe => "5.1",
+            Tool::YtDlp => "2023.01.06",
+            Tool::Jpegoptim => "1.4.0",
+            Tool::Pngquant => "2.12.0",
+            Tool::Cwebp => "1.0.0",
+            Tool::Video2x => "6.0.0",
+        }
+    }
+}
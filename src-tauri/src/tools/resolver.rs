@@ -0,0 +1,97 @@
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::env;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+use super::registry::Tool;
+use crate::db::get_setting;
+use crate::error::SwiftKitError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolSource {
+    Settings,
+    Sidecar,
+    Path,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolvedTool {
+    pub tool: Tool,
+    pub path: PathBuf,
+    pub source: ToolSource,
+}
+
+// Resolution order: settings override, bundled sidecar, then PATH
+pub fn locate(tool: Tool, override_path: Option<&Path>) -> Option<ResolvedTool> {
+    let found = |path: PathBuf, source: ToolSource| ResolvedTool { tool, path, source };
+
+    if let Some(path) = override_path.and_then(|path| binary_in(path, tool)) {
+        return Some(found(path, ToolSource::Settings));
+    }
+
+    if tool.is_bundled() {
+        if let Some(path) = sidecar_path(tool) {
+            return Some(found(path, ToolSource::Sidecar));
+        }
+    }
+
+    find_in_path(tool.binary_name()).map(|path| found(path, ToolSource::Path))
+}
+
+// Settings override stored for the tool, if any
+pub async fn configured_path(pool: &SqlitePool, tool: Tool) -> Option<PathBuf> {
+    get_setting(pool, tool.setting_key())
+        .await
+        .map(PathBuf::from)
+}
+
+// Without a settings database only sidecars and PATH are searched
+pub async fn resolve_tool_with(
+    pool: Option<&SqlitePool>,
+    tool: Tool,
+) -> Result<PathBuf, SwiftKitError> {
+    let override_path = match pool {
+        Some(pool) => configured_path(pool, tool).await,
+        None => None,
+    };
+    locate(tool, override_path.as_deref())
+        .map(|resolved| resolved.path)
+        .ok_or_else(|| SwiftKitError::ToolMissing {
+            tool: tool.binary_name().to_string(),
+        })
+}
+
+pub async fn resolve_tool(handle: &AppHandle, tool: Tool) -> Result<PathBuf, SwiftKitError> {
+    let pool = handle.try_state::<SqlitePool>();
+    resolve_tool_with(pool.as_deref(), tool).await
+}
+
+fn executable_name(name: &str) -> String {
+    format!("{}{}", name, env::consts::EXE_SUFFIX)
+}
+
+// An override may point at the binary itself or at the folder containing it
+fn binary_in(path: &Path, tool: Tool) -> Option<PathBuf> {
+    if path.is_dir() {
+        let binary = path.join(executable_name(tool.binary_name()));
+        return binary.is_file().then_some(binary);
+    }
+
+    path.is_file().then(|| path.to_path_buf())
+}
+
+// Tauri copies external binaries next to the app executable without the target triple
+fn sidecar_path(tool: Tool) -> Option<PathBuf> {
+    let exe = env::current_exe().ok()?;
+    let sidecar = exe.parent()?.join(executable_name(tool.binary_name()));
+    sidecar.is_file().then_some(sidecar)
+}
+
+fn find_in_path(name: &str) -> Option<PathBuf> {
+    let file_name = executable_name(name);
+    env::split_paths(&env::var_os("PATH")?)
+        .map(|dir| dir.join(&file_name))
+        .find(|candidate| candidate.is_file())
+}
@@ -0,0 +1,23 @@
+use std::path::Path;
+use trash::delete_all;
+
+use super::error::SwiftKitError;
+
+// Sends files to the system trash, e.g. the lower quality copies found by `find_similar_images`
+#[tauri::command(rename_all = "snake_case")]
+pub async fn trash_files(file_paths: Vec<String>) -> Result<usize, SwiftKitError> {
+    if file_paths.is_empty() {
+        return Err(SwiftKitError::validation("No files were provided."));
+    }
+
+    // Nothing is trashed unless every path is a regular file
+    if let Some(path) = file_paths.iter().find(|path| !Path::new(path).is_file()) {
+        return Err(SwiftKitError::validation(format!(
+            "Invalid file path: {}",
+            path
+        )));
+    }
+
+    delete_all(&file_paths).map_err(|e| SwiftKitError::io(e, &file_paths[0]))?;
+    Ok(file_paths.len())
+}
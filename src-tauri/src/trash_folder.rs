@@ -3,22 +3,28 @@ use tauri::path::BaseDirectory;
 use tauri::{AppHandle, Manager};
 use trash::delete;
 
+use super::error::SwiftKitError;
+
 #[tauri::command(rename_all = "snake_case")]
-pub async fn trash_folder(folder_path: String, handle: AppHandle) -> Result<String, String> {
+pub async fn trash_folder(folder_path: String, handle: AppHandle) -> Result<String, SwiftKitError> {
     let allowed_folder = handle
         .path()
-        .resolve("".to_string(), BaseDirectory::AppConfig)
-        .map_err(|e| format!("Failed to resolve App Config directory: {}", e))?;
+        .resolve("", BaseDirectory::AppConfig)
+        .map_err(|e| {
+            SwiftKitError::internal(format!("Failed to resolve App Config directory: {}", e))
+        })?;
 
     let input_path = Path::new(&folder_path);
     if !input_path.exists() || !input_path.is_dir() {
-        return Err("Invalid folder path".into());
+        return Err(SwiftKitError::validation("Invalid folder path"));
     }
 
     if !input_path.starts_with(&allowed_folder) {
-        return Err("Folder is outside the allowed directory".into());
+        return Err(SwiftKitError::validation(
+            "Folder is outside the allowed directory",
+        ));
     }
 
-    delete(&input_path).map_err(|e| e.to_string())?;
+    delete(input_path).map_err(|e| SwiftKitError::io(e, input_path))?;
     Ok("Delete complete.".to_string())
 }
@@ -0,0 +1,16 @@
+use serde_json::Value;
+use std::sync::Arc;
+use tauri::State;
+
+use super::error::SwiftKitError;
+use super::settings::model::Settings;
+use super::settings::service::SettingsService;
+
+// Only the given fields change, an empty string or null clears an optional one
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_settings(
+    changes: Value,
+    settings: State<'_, Arc<SettingsService>>,
+) -> Result<Settings, SwiftKitError> {
+    settings.update(changes).await
+}
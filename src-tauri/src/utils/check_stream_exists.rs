@@ -1,5 +1,7 @@
 use std::path::Path;
-use std::{os::windows::process::CommandExt, process::Command};
+
+use super::hidden_command::hidden_command;
+use crate::error::SwiftKitError;
 
 #[derive(Debug)]
 pub struct StreamIndexExistence {
@@ -8,13 +10,13 @@ pub struct StreamIndexExistence {
 }
 
 pub async fn check_stream_exists(
+    ffprobe: &Path,
     input_path: &Path,
     audio_index: usize,
     subtitle_index: usize,
-) -> Result<StreamIndexExistence, String> {
-    let mut ffprobe_command = Command::new("ffprobe");
+) -> Result<StreamIndexExistence, SwiftKitError> {
+    let mut ffprobe_command = hidden_command(ffprobe);
     ffprobe_command
-        .creation_flags(0x08000000)
         .arg("-v")
         .arg("quiet")
         .arg("-print_format")
@@ -22,24 +24,25 @@ pub async fn check_stream_exists(
         .arg("-show_streams")
         .arg(input_path);
 
-    let output = ffprobe_command
-        .output()
-        .map_err(|e| format!("Failed to execute ffprobe: {}", e))?;
+    let output = ffprobe_command.output().map_err(|e| {
+        SwiftKitError::tool_failed("ffprobe", format!("Failed to execute: {}", e), None)
+    })?;
 
     if !output.status.success() {
-        return Err(format!(
-            "ffprobe failed: {}",
-            String::from_utf8_lossy(&output.stderr)
+        return Err(SwiftKitError::tool_failed(
+            "ffprobe",
+            format!("exited with {}", output.status),
+            Some(String::from_utf8_lossy(&output.stderr).to_string()),
         ));
     }
 
     let json_output: serde_json::Value = serde_json::from_slice(&output.stdout)
-        .map_err(|e| format!("Failed to parse ffprobe output: {}", e))?;
+        .map_err(|e| SwiftKitError::parse(format!("Failed to parse ffprobe output: {}", e)))?;
 
     let streams = json_output
         .get("streams")
         .and_then(|s| s.as_array())
-        .ok_or_else(|| "No streams found in media file".to_string())?;
+        .ok_or_else(|| SwiftKitError::parse("No streams found in media file"))?;
 
     let mut audio_stream_index_exists = false;
     let mut subtitle_stream_index_exists = false;
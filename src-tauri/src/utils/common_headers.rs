@@ -1,5 +1,6 @@
-use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, ACCEPT_LANGUAGE, CONNECTION, COOKIE};
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, CONNECTION, COOKIE};
 
+// Accept-Language is left to the client so it follows the scraper language setting
 pub fn common_headers() -> HeaderMap {
     let mut headers = HeaderMap::new();
     headers.insert(
@@ -12,7 +13,6 @@ pub fn common_headers() -> HeaderMap {
             "text/html,application/xhtml+xml,application/xml;q=0.9,image/webp,*/*;q=0.8",
         ),
     );
-    headers.insert(ACCEPT_LANGUAGE, HeaderValue::from_static("en-US,en;q=0.5"));
     headers.insert(CONNECTION, HeaderValue::from_static("keep-alive"));
 
     headers
@@ -1,7 +1,7 @@
-use std::process::Command;
+use super::hidden_command::hidden_command;
 
 pub fn get_default_browser() -> Result<String, Box<dyn std::error::Error>> {
-    let output = Command::new("reg")
+    let output = hidden_command("reg")
         .arg("query")
         .arg(r"HKEY_CURRENT_USER\Software\Microsoft\Windows\Shell\Associations\UrlAssociations\http\UserChoice")
         .arg("/v")
@@ -4,6 +4,10 @@ pub mod decode_html_entities;
 pub mod file_types;
 pub mod format_duration;
 pub mod get_default_browser;
+pub mod hidden_command;
 pub mod is_valid_timestamp;
+pub mod parse_color;
 pub mod parse_duration;
 pub mod request_client;
+pub mod resolve_output_path;
+pub mod validate_file_name;
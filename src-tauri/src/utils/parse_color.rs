@@ -0,0 +1,32 @@
+use image::Rgba;
+
+use crate::error::SwiftKitError;
+
+// Accepts #rgb, #rrggbb and #rrggbbaa
+pub fn parse_color(color: &str) -> Result<Rgba<u8>, SwiftKitError> {
+    let hex = color.trim().trim_start_matches('#');
+    let invalid = || SwiftKitError::validation(format!("Invalid color: {}", color));
+
+    let expanded: String = match hex.len() {
+        3 => hex.chars().flat_map(|c| [c, c]).collect(),
+        6 | 8 => hex.to_string(),
+        _ => return Err(invalid()),
+    };
+    let channel = |index: usize| {
+        expanded
+            .get(index..index + 2)
+            .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+    };
+
+    match (channel(0), channel(2), channel(4)) {
+        (Some(r), Some(g), Some(b)) => {
+            let a = if expanded.len() == 8 {
+                channel(6).ok_or_else(invalid)?
+            } else {
+                255
+            };
+            Ok(Rgba([r, g, b, a]))
+        }
+        _ => Err(invalid()),
+    }
+}
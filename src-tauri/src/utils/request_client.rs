@@ -1,9 +1,25 @@
-use reqwest::{Client, Error};
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT_LANGUAGE};
+use reqwest::{Client, Proxy};
 
-pub fn request_client() -> Result<Client, Error> {
-    let client = Client::builder()
-	.user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36")
-	.build();
+use crate::error::SwiftKitError;
+use crate::settings::model::Settings;
 
-    client
+// Proxy and scraper language come from the settings, other headers are set per request
+pub fn request_client(settings: &Settings) -> Result<Client, SwiftKitError> {
+    let mut headers = HeaderMap::new();
+    let language = format!("{},*;q=0.5", settings.scraper_language);
+    headers.insert(
+        ACCEPT_LANGUAGE,
+        HeaderValue::from_str(&language)
+            .map_err(|_| SwiftKitError::validation("Invalid scraper language"))?,
+    );
+
+    let mut builder = Client::builder()
+        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36")
+        .default_headers(headers);
+    if let Some(proxy) = settings.proxy.as_deref() {
+        builder = builder.proxy(Proxy::all(proxy)?);
+    }
+
+    Ok(builder.build()?)
 }
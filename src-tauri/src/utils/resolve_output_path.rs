@@ -0,0 +1,39 @@
+use std::path::{Path, PathBuf};
+
+use super::validate_file_name::validate_file_name;
+use crate::error::SwiftKitError;
+
+// Where a command writes an image's result: `output_folder` or the input's folder,
+// named `file_name` or after the input, with `extension` or the input's extension
+pub fn resolve_output_path(
+    input_path: &Path,
+    output_folder: Option<&Path>,
+    file_name: Option<&str>,
+    extension: Option<&str>,
+) -> Result<PathBuf, SwiftKitError> {
+    let output_folder = match output_folder.filter(|folder| !folder.as_os_str().is_empty()) {
+        Some(folder) => folder,
+        None => input_path.parent().unwrap_or_else(|| Path::new(".")),
+    };
+
+    let output_file_name = match file_name {
+        Some(name) => {
+            validate_file_name(name)?;
+            name.to_string()
+        }
+        None => input_path
+            .file_stem()
+            .ok_or_else(|| SwiftKitError::validation("Invalid file name"))?
+            .to_string_lossy()
+            .to_string(),
+    };
+
+    let extension = match extension {
+        Some(extension) => extension,
+        None => input_path
+            .extension()
+            .and_then(|s| s.to_str())
+            .unwrap_or(""),
+    };
+    Ok(output_folder.join(format!("{}.{}", output_file_name, extension)))
+}
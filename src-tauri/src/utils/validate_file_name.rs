@@ -0,0 +1,11 @@
+use crate::error::SwiftKitError;
+
+const INVALID_FILE_NAME_CHARS: [char; 9] = ['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+// A name for a file inside a chosen folder, separators would let it write elsewhere
+pub fn validate_file_name(name: &str) -> Result<(), SwiftKitError> {
+    if name.is_empty() || name.chars().any(|c| INVALID_FILE_NAME_CHARS.contains(&c)) {
+        return Err(SwiftKitError::validation("Invalid file name"));
+    }
+    Ok(())
+}
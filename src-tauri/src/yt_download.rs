@@ -1,16 +1,21 @@
 use serde::Serialize;
-use std::io::{BufReader, Read};
-use std::os::windows::process::CommandExt;
+use std::future::Future;
 use std::path::Path;
-use std::process::{Command, Stdio};
-use tauri::Emitter;
-use tokio::{sync::mpsc, task};
-
+use std::process::Stdio;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, State};
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::process::Command;
+
+use super::error::SwiftKitError;
+use super::jobs::manager::{JobHandle, JobKind, JobManager};
+use super::settings::service::current;
+use super::tools::registry::Tool;
+use super::tools::resolver::resolve_tool;
 use super::utils::get_default_browser::get_default_browser;
+use super::utils::hidden_command::hidden_command;
 
-const WINDOWS_CREATE_NO_WINDOW: u32 = 0x08000000;
 const BUFFER_SIZE: usize = 1024;
-const CHANNEL_BUFFER_SIZE: usize = 32;
 
 #[derive(Serialize)]
 pub struct DownloadResponse {
@@ -27,141 +32,192 @@ pub struct ProgressUpdate {
 #[tauri::command(rename_all = "snake_case")]
 pub async fn yt_download(
     url: String,
-    output_path: String,
+    output_path: Option<String>,
     download_rate: Option<u32>,
-    handle: tauri::AppHandle,
-) -> Result<DownloadResponse, String> {
-    let output_path = Path::new(&output_path);
+    job_id: Option<String>,
+    handle: AppHandle,
+    jobs: State<'_, Arc<JobManager>>,
+) -> Result<DownloadResponse, SwiftKitError> {
+    let job = jobs.start(&handle, job_id, JobKind::YtDownload, url.clone())?;
+    let result = download(&handle, &job, &url, output_path.as_deref(), download_rate).await;
+    job.finish(&result);
+    result
+}
+
+pub async fn download(
+    handle: &AppHandle,
+    job: &JobHandle,
+    url: &str,
+    output_path: Option<&str>,
+    download_rate: Option<u32>,
+) -> Result<DownloadResponse, SwiftKitError> {
+    let settings = current(handle).await;
+    let output_path = match output_path.filter(|path| !path.is_empty()) {
+        Some(path) => path.to_string(),
+        None => settings.download_folder.clone().ok_or_else(|| {
+            SwiftKitError::validation("No output folder was given and no download folder is set.")
+        })?,
+    };
+    let yt_dlp = resolve_tool(handle, Tool::YtDlp).await?;
+    let ffmpeg = resolve_tool(handle, Tool::Ffmpeg).await?;
+
+    let (handle, progress_job) = (handle.clone(), job.clone());
+    let on_output = move |output: String| {
+        if let Some(line) = output.lines().rev().find(|line| !line.trim().is_empty()) {
+            progress_job.progress(None, Some(line.trim().to_string()));
+        }
+
+        let progress = ProgressUpdate {
+            output,
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        };
+        handle
+            .emit("yt-download-progress", Some(progress))
+            .unwrap_or_default();
+    };
+
+    let options = YtDlpOptions {
+        url,
+        output_path: &output_path,
+        download_rate: download_rate.or(settings.yt_download_rate_kb),
+        proxy: settings.proxy.as_deref(),
+    };
+    run_yt_dlp(&yt_dlp, &ffmpeg, options, job.cancelled(), on_output).await
+}
+
+pub struct YtDlpOptions<'a> {
+    pub url: &'a str,
+    pub output_path: &'a str,
+    pub download_rate: Option<u32>,
+    pub proxy: Option<&'a str>,
+}
+
+// Runs yt-dlp until it exits or `cancelled` resolves, every chunk of output goes to `on_output`
+pub async fn run_yt_dlp<F>(
+    yt_dlp: &Path,
+    ffmpeg: &Path,
+    options: YtDlpOptions<'_>,
+    cancelled: impl Future<Output = ()>,
+    on_output: F,
+) -> Result<DownloadResponse, SwiftKitError>
+where
+    F: Fn(String) + Clone + Send + 'static,
+{
+    let YtDlpOptions {
+        url,
+        output_path,
+        download_rate,
+        proxy,
+    } = options;
+    let output_path = Path::new(output_path);
     if !output_path.is_dir() {
-        return Err(format!("Invalid output path: {}", output_path.display()));
+        return Err(SwiftKitError::validation(format!(
+            "Invalid output path: {}",
+            output_path.display()
+        )));
+    }
+
+    let mut command = Command::from(hidden_command(yt_dlp));
+
+    // Configure command
+    command
+        .arg("-f")
+        .arg("bv*+ba/b")
+        .arg("-o")
+        .arg(
+            output_path
+                .join("%(title)s.%(ext)s")
+                .to_str()
+                .ok_or_else(|| SwiftKitError::validation("Invalid path encoding"))?,
+        )
+        .arg("--continue")
+        .arg("--no-warnings")
+        .arg("--ffmpeg-location")
+        .arg(ffmpeg)
+        .arg(url)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    // Add rate limit if specified
+    if let Some(rate) = download_rate {
+        if rate > 0 {
+            command.arg("--limit-rate").arg(format!("{}K", rate));
+        }
+    }
+
+    if let Some(proxy) = proxy {
+        command.arg("--proxy").arg(proxy);
     }
-    let output_path = output_path.to_path_buf();
-
-    let result = task::spawn_blocking(move || -> Result<DownloadResponse, String> {
-        let binding = Command::new("ffmpeg");
-        let ffmpeg_path = binding.get_program();
-        let mut command = Command::new("yt-dlp");
-
-        // Configure command
-        command
-            .arg("-f")
-            .arg("bv*+ba/b")
-            .arg("-o")
-            .arg(
-                output_path
-                    .join("%(title)s.%(ext)s")
-                    .to_str()
-                    .ok_or("Invalid path encoding".to_string())?,
-            )
-            .arg("--continue")
-            .arg("--no-warnings")
-            .arg("--ffmpeg-location")
-            .arg(ffmpeg_path)
-            .arg(&url)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .creation_flags(WINDOWS_CREATE_NO_WINDOW);
-
-        // Add rate limit if specified
-        if let Some(rate) = download_rate {
-            if rate > 0 {
-                command.arg("--limit-rate").arg(format!("{}K", rate));
-            }
+
+    // Add browser cookies if available
+    if let Ok(browser) = get_default_browser() {
+        if browser != "not_found" {
+            command.arg("--cookies-from-browser").arg(browser);
         }
+    }
+
+    let mut child = command.spawn().map_err(|e| {
+        SwiftKitError::tool_failed("yt-dlp", format!("Failed to start: {}", e), None)
+    })?;
+
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| SwiftKitError::internal("Failed to capture stdout"))?;
 
-        // Add browser cookies if available
-        if let Ok(browser) = get_default_browser() {
-            if browser != "not_found" {
-                command.arg("--cookies-from-browser").arg(browser);
-            }
+    let stderr = child
+        .stderr
+        .take()
+        .ok_or_else(|| SwiftKitError::internal("Failed to capture stderr"))?;
+
+    let stdout_task = tokio::spawn(forward_output(stdout, on_output.clone()));
+    let stderr_task = tokio::spawn(forward_output(stderr, on_output));
+
+    let status = tokio::select! {
+        status = child.wait() => status?,
+        _ = cancelled => {
+            child.kill().await?;
+            return Err(SwiftKitError::Cancelled("Download cancelled".to_string()));
         }
+    };
+
+    stdout_task.await?;
+    let stderr_output = stderr_task.await?;
+
+    if status.success() {
+        Ok(DownloadResponse {
+            success: true,
+            message: "Download completed successfully".to_string(),
+        })
+    } else {
+        Err(SwiftKitError::tool_failed(
+            "yt-dlp",
+            format!("Download failed with {}", status),
+            Some(stderr_output),
+        ))
+    }
+}
 
-        let mut child = command.spawn().map_err(|e| e.to_string())?;
-
-        let stdout = child
-            .stdout
-            .take()
-            .ok_or("Failed to capture stdout".to_string())?;
-
-        let stderr = child
-            .stderr
-            .take()
-            .ok_or("Failed to capture stderr".to_string())?;
-
-        let (tx, _rx) = mpsc::channel(CHANNEL_BUFFER_SIZE);
-        let handle_clone_stdout = handle.clone();
-        let handle_clone_stderr = handle.clone();
-
-        // Handle stdout
-        let tx_stdout = tx.clone();
-        task::spawn(async move {
-            let mut reader = BufReader::new(stdout);
-            let mut buffer = vec![0u8; BUFFER_SIZE];
-
-            while let Ok(read) = reader.read(&mut buffer) {
-                if read == 0 {
-                    break;
-                }
-                if let Ok(output) = std::str::from_utf8(&buffer[0..read]) {
-                    let progress = ProgressUpdate {
-                        output: output.to_string(),
-                        timestamp: std::time::SystemTime::now()
-                            .duration_since(std::time::UNIX_EPOCH)
-                            .unwrap_or_default()
-                            .as_secs(),
-                    };
-
-                    if tx_stdout.send(progress.clone()).await.is_ok() {
-                        handle_clone_stdout
-                            .emit("yt-download-progress", Some(progress))
-                            .unwrap_or_default();
-                    }
-                }
-            }
-        });
-
-        // Handle stderr
-        let tx_stderr = tx;
-        task::spawn(async move {
-            let mut reader = BufReader::new(stderr);
-            let mut buffer = vec![0u8; BUFFER_SIZE];
-
-            while let Ok(read) = reader.read(&mut buffer) {
-                if read == 0 {
-                    break;
-                }
-                if let Ok(output) = std::str::from_utf8(&buffer[0..read]) {
-                    let progress = ProgressUpdate {
-                        output: output.to_string(),
-                        timestamp: std::time::SystemTime::now()
-                            .duration_since(std::time::UNIX_EPOCH)
-                            .unwrap_or_default()
-                            .as_secs(),
-                    };
-
-                    if tx_stderr.send(progress.clone()).await.is_ok() {
-                        handle_clone_stderr
-                            .emit("yt-download-progress", Some(progress))
-                            .unwrap_or_default();
-                    }
-                }
-            }
-        });
-
-        let output = child.wait_with_output().map_err(|e| e.to_string())?;
-
-        if output.status.success() {
-            Ok(DownloadResponse {
-                success: true,
-                message: "Download completed successfully".to_string(),
-            })
-        } else {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            Err(format!("Download failed: {}", stderr))
+// Hands a pipe's output to `on_output` as it arrives and returns everything that was read
+async fn forward_output<R: AsyncRead + Unpin, F: Fn(String)>(
+    mut reader: R,
+    on_output: F,
+) -> String {
+    let mut collected = String::new();
+    let mut buffer = vec![0u8; BUFFER_SIZE];
+
+    while let Ok(read) = reader.read(&mut buffer).await {
+        if read == 0 {
+            break;
         }
-    })
-    .await
-    .map_err(|e| e.to_string())?;
+        let output = String::from_utf8_lossy(&buffer[0..read]).to_string();
+        collected.push_str(&output);
+        on_output(output);
+    }
 
-    result
+    collected
 }
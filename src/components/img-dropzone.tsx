@@ -54,7 +54,19 @@ const IMGDropzone = ({
 			filters: [
 				{
 					name: '',
-					extensions: ['jpg', 'png', 'webp', 'ico', 'bmp'],
+					extensions: [
+						'jpg',
+						'jpeg',
+						'png',
+						'webp',
+						'ico',
+						'bmp',
+						'tif',
+						'tiff',
+						'gif',
+						'tga',
+						'qoi',
+					],
 				},
 			],
 		});
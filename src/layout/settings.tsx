@@ -25,12 +25,21 @@ import {
 } from '@/components/form';
 import { Button } from '@/components/button';
 import { Input } from '@/components/input';
+import {
+	Select,
+	SelectContent,
+	SelectItem,
+	SelectTrigger,
+	SelectValue,
+} from '@/components/select';
 
-import { Setting } from '@/lib/models/setting';
 import api from '@/lib/api';
+import { errorMessage } from '@/lib/utils';
 
 const settingsSchema = z.object({
 	video2x_binary_path: z.string().optional(),
+	compression_backend: z.enum(['auto', 'external', 'builtin']),
+	external_url_schemes: z.string().optional(),
 });
 
 export function Settings() {
@@ -38,18 +47,23 @@ export function Settings() {
 		resolver: zodResolver(settingsSchema),
 		defaultValues: {
 			video2x_binary_path: '',
+			compression_backend: 'auto' as const,
+			external_url_schemes: '',
 		},
 	});
 
 	useEffect(() => {
 		(async () => {
-			const { setValue } = form;
-			const video2x_binary_path = await Setting.get(
+			const settings = await api.get_settings();
+			form.setValue(
 				'video2x_binary_path',
+				settings.video2x_binary_path ?? '',
+			);
+			form.setValue('compression_backend', settings.compression_backend);
+			form.setValue(
+				'external_url_schemes',
+				settings.external_url_schemes.join(', '),
 			);
-			if (video2x_binary_path) {
-				setValue('video2x_binary_path', video2x_binary_path.value);
-			}
 		})();
 	}, [form]);
 
@@ -69,11 +83,34 @@ export function Settings() {
 			}
 		}
 
-		await Setting.save(
-			'video2x_binary_path',
-			data.video2x_binary_path ?? '',
-		);
-		toast.success('Settings are saved.');
+		try {
+			await api.update_settings({
+				video2x_binary_path: data.video2x_binary_path || null,
+				compression_backend: data.compression_backend,
+				external_url_schemes: (data.external_url_schemes ?? '')
+					.split(',')
+					.map(scheme => scheme.trim())
+					.filter(Boolean),
+			});
+			toast.success('Settings are saved.');
+		} catch (e) {
+			toast.error(errorMessage(e));
+		}
+	}
+
+	async function exportDiagnostics() {
+		try {
+			const path = await api.export_diagnostics();
+			toast.success('Diagnostics exported.', {
+				description: path,
+				action: {
+					label: 'Show',
+					onClick: () => api.highlight_file(path),
+				},
+			});
+		} catch (e) {
+			toast.error(errorMessage(e));
+		}
 	}
 
 	return (
@@ -122,7 +159,66 @@ export function Settings() {
 							)}
 						/>
 
+						<FormField
+							control={form.control}
+							name="compression_backend"
+							render={({ field }) => (
+								<FormItem className="grid gap-1 flex-grow">
+									<FormLabel>Image Compression</FormLabel>
+									<Select
+										onValueChange={field.onChange}
+										value={field.value}
+									>
+										<FormControl>
+											<SelectTrigger>
+												<SelectValue />
+											</SelectTrigger>
+										</FormControl>
+										<SelectContent>
+											<SelectItem value="auto">Auto</SelectItem>
+											<SelectItem value="external">
+												pngquant, jpegoptim and cwebp
+											</SelectItem>
+											<SelectItem value="builtin">
+												Built-in
+											</SelectItem>
+										</SelectContent>
+									</Select>
+									<FormMessage />
+									<FormDescription>
+										Auto uses the external tools when they are
+										installed and the built-in encoders otherwise.
+									</FormDescription>
+								</FormItem>
+							)}
+						/>
+
+						<FormField
+							control={form.control}
+							name="external_url_schemes"
+							render={({ field }) => (
+								<FormItem className="grid gap-1 flex-grow">
+									<FormLabel>Extra URL Schemes</FormLabel>
+									<FormControl>
+										<Input placeholder="eg. steam, spotify" {...field} />
+									</FormControl>
+									<FormMessage />
+									<FormDescription>
+										Links with these schemes can be opened on top of
+										http, https and mailto.
+									</FormDescription>
+								</FormItem>
+							)}
+						/>
+
 						<DialogFooter>
+							<Button
+								type="button"
+								variant="outline"
+								onClick={exportDiagnostics}
+							>
+								Export diagnostics
+							</Button>
 							<Button type="submit">Save changes</Button>
 						</DialogFooter>
 					</form>
@@ -1,7 +1,6 @@
 import { invoke } from '@tauri-apps/api/core';
 import type { Crop } from 'react-image-crop';
 
-import { Setting } from '@/lib/models/setting';
 import type { Movie } from '@/lib/models/movie';
 import type { Anime } from '@/lib/models/anime';
 import type { Game } from '@/lib/models/game';
@@ -41,6 +40,354 @@ export type IVideoIO = {
 	outro_end: string;
 } & IVideoMeta;
 
+export type IDiskInfo = {
+	mount_point: string;
+	file_system: string;
+	label?: string;
+	total_bytes: number;
+	free_bytes: number;
+	is_removable: boolean;
+};
+
+export type IToolStatus = {
+	tool:
+		| 'ffmpeg'
+		| 'ffprobe'
+		| 'yt_dlp'
+		| 'jpegoptim'
+		| 'pngquant'
+		| 'cwebp'
+		| 'video2x';
+	state: 'ready' | 'missing' | 'outdated' | 'broken';
+	path?: string;
+	source?: 'settings' | 'sidecar' | 'path';
+	version?: string;
+	minimum_version: string;
+	features: string[];
+	message?: string;
+};
+
+export type IJobInfo = {
+	id: string;
+	kind:
+		| 'convert_to_mp4'
+		| 'no_intro_outro'
+		| 'interpolate'
+		| 'video_thumbnails'
+		| 'download'
+		| 'yt_download';
+	label: string;
+	status: 'running' | 'completed' | 'failed' | 'cancelled';
+	progress?: number;
+	message?: string;
+	started_at: number;
+	finished_at?: number;
+};
+
+export type IJobParams =
+	| { kind: 'convert_to_mp4'; video_path: string }
+	| {
+			kind: 'no_intro_outro';
+			folder_path: string;
+			video: IVideoIO;
+			use_cuda?: boolean;
+			overwrite: boolean;
+	  }
+	| {
+			kind: 'interpolate';
+			video_path: string;
+			video2x_path?: string;
+			encoder?: string;
+			rife_model?: string;
+			multiplier?: number;
+			overwrite: boolean;
+	  }
+	| {
+			kind: 'download';
+			url: string;
+			destination: string;
+			rate_limit_kb?: number;
+	  }
+	| {
+			kind: 'yt_download';
+			url: string;
+			output_path?: string;
+			download_rate?: number;
+	  };
+
+export type IQueuedJob = {
+	id: string;
+	kind: IJobParams['kind'];
+	label: string;
+	params: IJobParams;
+	status:
+		| 'queued'
+		| 'running'
+		| 'done'
+		| 'failed'
+		| 'interrupted'
+		| 'cancelled';
+	message?: string;
+	attempts: number;
+	started_at?: string;
+	finished_at?: string;
+	created_at: string;
+};
+
+export type IFfmpegProgress = {
+	job_id: string;
+	frame?: number | null;
+	fps?: number | null;
+	speed?: number | null;
+	out_time: number;
+	duration?: number | null;
+	percent?: number | null;
+	eta?: number | null;
+	done: boolean;
+};
+
+export type ISettings = {
+	ffmpeg_binary_path: string | null;
+	ffprobe_binary_path: string | null;
+	yt_dlp_binary_path: string | null;
+	jpegoptim_binary_path: string | null;
+	pngquant_binary_path: string | null;
+	cwebp_binary_path: string | null;
+	video2x_binary_path: string | null;
+	download_folder: string | null;
+	image_output_folder: string | null;
+	image_quality: number | null;
+	compression_backend: ICompressionBackend;
+	video_encoder: 'h264_nvenc' | 'hevc_nvenc' | 'libx264';
+	rife_model: 'rife-anime' | 'rife-v4.26' | 'rife-v4.25-lite' | 'rife-v4.6';
+	interpolation_multiplier: number;
+	use_cuda: boolean;
+	download_rate_limit_kb: number | null;
+	yt_download_rate_kb: number | null;
+	proxy: string | null;
+	scraper_language: string;
+	external_url_schemes: string[];
+};
+
+export type ICompressionBackend = 'auto' | 'external' | 'builtin';
+
+export type ICompressionReport = {
+	output: string;
+	original_size: number;
+	compressed_size: number;
+	ratio: number;
+	quality: number;
+	ssim: number | null;
+	kept_original: boolean;
+};
+
+export type IImageComparison = {
+	width: number;
+	height: number;
+	resized: boolean;
+	ssim: number;
+	psnr: number | null;
+	max_difference: number;
+	heatmap: string;
+};
+
+export type IEncodeOptions = {
+	jpeg_quality?: number;
+	jpeg_progressive?: boolean;
+	webp_quality?: number;
+	avif_quality?: number;
+	avif_speed?: number;
+	png_compression?: 'fast' | 'default' | 'best';
+	png_quality?: number;
+};
+
+export type IGeneratedIcons = {
+	ico: string | null;
+	icns: string | null;
+};
+
+export type IIconPlatform = 'android' | 'ios' | 'pwa';
+
+export type IIconSetResult = {
+	output: string;
+	files: string[];
+};
+
+export type IGravity =
+	| 'center'
+	| 'north'
+	| 'south'
+	| 'east'
+	| 'west'
+	| 'north_east'
+	| 'north_west'
+	| 'south_east'
+	| 'south_west';
+
+export type IResizeOptions = {
+	mode?: 'exact' | 'contain' | 'cover' | 'pad' | 'percentage' | 'longest_edge';
+	width?: number;
+	height?: number;
+	percent?: number;
+	longest_edge?: number;
+	gravity?: IGravity;
+	background?: string;
+	no_upscale?: boolean;
+	filter?: 'nearest' | 'bilinear' | 'bicubic' | 'gaussian' | 'lanczos3';
+};
+
+export type IRotateMode = 'expand' | 'crop_to_fit';
+
+export type ICropOptions = {
+	auto_trim?: boolean;
+	trim_tolerance?: number;
+	aspect_ratio?: number;
+	gravity?: IGravity;
+	rotate?: number;
+	rotate_mode?: IRotateMode;
+	background?: string;
+	flip_horizontal?: boolean;
+	flip_vertical?: boolean;
+};
+
+export type IMetadataMode = 'preserve' | 'strip' | 'strip_gps';
+
+export type IGpsPosition = {
+	latitude: number;
+	longitude: number;
+	altitude: number | null;
+};
+
+export type IImageMetadata = {
+	format: string | null;
+	width: number;
+	height: number;
+	orientation: number | null;
+	make: string | null;
+	model: string | null;
+	lens_model: string | null;
+	software: string | null;
+	artist: string | null;
+	copyright: string | null;
+	date_taken: string | null;
+	exposure_time: string | null;
+	f_number: number | null;
+	iso: number | null;
+	focal_length: number | null;
+	gps: IGpsPosition | null;
+	exif: Record<string, string>;
+	xmp: string | null;
+};
+
+export type IRect = {
+	x: number;
+	y: number;
+	width: number;
+	height: number;
+};
+
+export type ISmartCrop = {
+	output: string;
+	rect: IRect;
+};
+
+export type IImageOperation =
+	| ({ type: 'resize' } & IResizeOptions)
+	| {
+			type: 'crop';
+			x: number;
+			y: number;
+			width: number;
+			height: number;
+			unit?: 'px' | '%';
+	  }
+	| {
+			type: 'rotate';
+			degrees: number;
+			mode?: IRotateMode;
+			background?: string;
+	  }
+	| { type: 'flip'; horizontal?: boolean; vertical?: boolean }
+	| { type: 'trim'; tolerance?: number }
+	| { type: 'smart_crop'; width: number; height: number }
+	| { type: 'convert'; to: string }
+	| { type: 'compress'; quality: number }
+	| { type: 'metadata'; mode: IMetadataMode };
+
+export type IPipelineFileResult = {
+	input: string;
+	output: string | null;
+	error: ISwiftKitError | null;
+};
+
+export type IPipelineProgress = {
+	completed: number;
+	total: number;
+	file: IPipelineFileResult;
+};
+
+export type IWatermarkMark =
+	| {
+			type: 'text';
+			text: string;
+			font_path: string;
+			size?: number;
+			color?: string;
+	  }
+	| { type: 'image'; path: string; scale?: number };
+
+export type IWatermarkOptions = {
+	mark: IWatermarkMark;
+	opacity?: number;
+	position?: IGravity;
+	tiled?: boolean;
+	margin?: number;
+};
+
+export type ISimilarImage = {
+	path: string;
+	width: number;
+	height: number;
+	size: number;
+	distance: number;
+};
+
+export type ISimilarGroup = {
+	// Best copy first, the rest are candidates for the trash
+	images: ISimilarImage[];
+};
+
+export type ISimilarImages = {
+	scanned: number;
+	skipped: string[];
+	groups: ISimilarGroup[];
+};
+
+export type ISimilarImagesProgress = {
+	completed: number;
+	total: number;
+};
+
+export type ISwiftKitError = {
+	code:
+		| 'io'
+		| 'tool_missing'
+		| 'tool_failed'
+		| 'parse'
+		| 'network'
+		| 'validation'
+		| 'cancelled'
+		| 'internal';
+	message: string;
+	details?: Record<string, unknown> | null;
+};
+
+export const isSwiftKitError = (error: unknown): error is ISwiftKitError =>
+	typeof error === 'object' &&
+	error !== null &&
+	'code' in error &&
+	'message' in error;
+
 export type MediaQueryR = {
 	title: string;
 	href: string;
@@ -99,7 +446,7 @@ class API {
 		});
 	}
 
-	async get_available_disks(): Promise<string[]> {
+	async get_available_disks(): Promise<IDiskInfo[]> {
 		return invoke('get_available_disks');
 	}
 
@@ -110,21 +457,63 @@ class API {
 		});
 	}
 
-	async highlight_file(file_path: string): Promise<string[]> {
+	async highlight_file(file_path: string): Promise<void> {
 		return invoke('highlight_file', {
 			file_path,
 		});
 	}
 
+	async highlight_files(file_paths: string[]): Promise<void> {
+		return invoke('highlight_files', {
+			file_paths,
+		});
+	}
+
 	async image_convert(
 		img_path: string,
 		to: string,
 		output_folder?: string,
+		options?: IEncodeOptions,
+		metadata?: IMetadataMode,
 	): Promise<string> {
 		return invoke('image_convert', {
 			img_path,
 			to,
 			output_folder,
+			options,
+			metadata,
+		});
+	}
+
+	async generate_icons(
+		img_path: string,
+		sizes?: number[],
+		output_folder?: string,
+		file_name?: string,
+	): Promise<IGeneratedIcons> {
+		return invoke('generate_icons', {
+			img_path,
+			sizes,
+			output_folder,
+			file_name,
+		});
+	}
+
+	async export_icon_set(
+		img_path: string,
+		platforms: IIconPlatform[],
+		output_path: string,
+		zip?: boolean,
+		background?: string,
+		app_name?: string,
+	): Promise<IIconSetResult> {
+		return invoke('export_icon_set', {
+			img_path,
+			platforms,
+			output_path,
+			zip,
+			background,
+			app_name,
 		});
 	}
 
@@ -132,41 +521,118 @@ class API {
 		img_path: string,
 		quality: number,
 		output_folder?: string,
-	): Promise<string> {
+		backend?: ICompressionBackend,
+		target_size_kb?: number,
+		force?: boolean,
+		min_ssim?: number,
+	): Promise<ICompressionReport> {
 		return invoke('image_compress', {
 			img_path,
 			quality,
 			output_folder,
+			backend,
+			target_size_kb,
+			force,
+			min_ssim,
+		});
+	}
+
+	async image_compare(
+		original_path: string,
+		processed_path: string,
+		heatmap_path?: string,
+	): Promise<IImageComparison> {
+		return invoke('image_compare', {
+			original_path,
+			processed_path,
+			heatmap_path,
 		});
 	}
 
 	async image_resize(
 		img_path: string,
-		width?: number,
-		height?: number,
+		options: IResizeOptions,
 		output_folder?: string,
 		file_name?: string,
+		metadata?: IMetadataMode,
 	): Promise<string> {
 		return invoke('image_resize', {
 			img_path,
-			width: String(width),
-			height: String(height),
+			options,
 			output_folder,
 			file_name,
+			metadata,
 		});
 	}
 
 	async image_crop(
 		img_path: string,
-		crop_details: Crop,
+		crop_details?: Crop,
 		output_folder?: string,
 		file_name?: string,
+		options?: ICropOptions,
+		metadata?: IMetadataMode,
 	): Promise<string> {
 		return invoke('image_crop', {
 			img_path,
 			crop_details,
 			output_folder,
 			file_name,
+			options,
+			metadata,
+		});
+	}
+
+	async image_metadata(img_path: string): Promise<IImageMetadata> {
+		return invoke('image_metadata', { img_path });
+	}
+
+	// Batches go through image_pipeline with a { type: 'smart_crop' } operation
+	async smart_crop(
+		img_path: string,
+		width: number,
+		height: number,
+		output_folder?: string,
+		file_name?: string,
+		metadata?: IMetadataMode,
+	): Promise<ISmartCrop> {
+		return invoke('smart_crop', {
+			img_path,
+			width,
+			height,
+			output_folder,
+			file_name,
+			metadata,
+		});
+	}
+
+	async image_pipeline(
+		img_paths: string[],
+		operations: IImageOperation[],
+		output_folder?: string,
+		threads?: number,
+	): Promise<IPipelineFileResult[]> {
+		return invoke('image_pipeline', {
+			img_paths,
+			operations,
+			output_folder,
+			threads,
+		});
+	}
+
+	async image_watermark(
+		img_paths: string[],
+		options: IWatermarkOptions,
+		output_folder?: string,
+		metadata?: IMetadataMode,
+		threads?: number,
+	): Promise<IPipelineFileResult[]> {
+		return invoke('image_watermark', {
+			img_paths,
+			options,
+			output_folder,
+			metadata,
+			threads,
 		});
 	}
 
@@ -176,12 +642,15 @@ class API {
 		});
 	}
 
-	async generate_video_thumbnails(video_path: string): Promise<{
+	async generate_video_thumbnails(
+		video_path: string,
+		job_id?: string,
+	): Promise<{
 		video_path: string;
 		thumbnail_folder: string;
 		vtt_file_path: string;
 	}> {
-		return invoke('generate_video_thumbnails', { video_path });
+		return invoke('generate_video_thumbnails', { video_path, job_id });
 	}
 
 	async stop_video_thumbnail_generation(): Promise<void> {
@@ -192,6 +661,22 @@ class API {
 		return invoke('trash_folder', { folder_path });
 	}
 
+	async trash_files(file_paths: string[]): Promise<number> {
+		return invoke('trash_files', { file_paths });
+	}
+
+	async find_similar_images(
+		folder: string,
+		recursive: boolean,
+		threshold?: number,
+	): Promise<ISimilarImages> {
+		return invoke('find_similar_images', {
+			folder,
+			recursive,
+			threshold,
+		});
+	}
+
 	async intro_outro_prediction(episodes_folder: string): Promise<IVideoIO[]> {
 		return invoke('intro_outro_prediction', {
 			episodes_folder,
@@ -201,8 +686,9 @@ class API {
 	async no_intro_outro(
 		folder_path: string,
 		video: IVideoIO,
-		use_cuda: boolean = true,
+		use_cuda?: boolean,
 		overwrite: boolean = false,
+		job_id?: string,
 	) {
 		return invoke('no_intro_outro', {
 			folder_path,
@@ -213,36 +699,32 @@ class API {
 			},
 			use_cuda,
 			overwrite,
+			job_id,
 		});
 	}
 
 	async interpolate(
 		video_path: string,
-		encoder: string = 'h264_nvenc',
-		rife_model: string = 'rife-v4.6',
-		multiplier: number = 2,
+		encoder?: string,
+		rife_model?: string,
+		multiplier?: number,
 		overwrite: boolean = false,
+		job_id?: string,
 	) {
-		const video2xPath = await Setting.get('video2x_binary_path');
-		if (
-			video2xPath &&
-			video2xPath.value !== null &&
-			video2xPath.value !== ''
-		) {
-			return invoke('interpolate', {
-				video2x_path: video2xPath.value,
-				video_path,
-				encoder,
-				rife_model,
-				multiplier,
-				overwrite,
-			});
-		}
-	}
-
-	async convert_to_mp4(video_path: string) {
+		return invoke('interpolate', {
+			video_path,
+			encoder,
+			rife_model,
+			multiplier,
+			overwrite,
+			job_id,
+		});
+	}
+
+	async convert_to_mp4(video_path: string, job_id?: string) {
 		return invoke('convert_to_mp4', {
 			video_path,
+			job_id,
 		});
 	}
 
@@ -285,12 +767,14 @@ class API {
 	async download_file(
 		url: string,
 		destination: string,
-		rate_limit_kb: number = 20000,
+		rate_limit_kb?: number,
+		job_id?: string,
 	): Promise<string> {
 		return invoke('download_file', {
 			url,
 			destination,
 			rate_limit_kb,
+			job_id,
 		});
 	}
 
@@ -298,17 +782,66 @@ class API {
 		return invoke('abort_download');
 	}
 
+	async check_tools(): Promise<IToolStatus[]> {
+		return invoke('check_tools');
+	}
+
 	async yt_download(
 		url: string,
-		output_path: string,
-		download_rate: number = 20000,
+		output_path?: string,
+		download_rate?: number,
+		job_id?: string,
 	): Promise<string> {
 		return invoke('yt_download', {
 			url,
 			output_path,
 			download_rate,
+			job_id,
 		});
 	}
+
+	async list_jobs(): Promise<IJobInfo[]> {
+		return invoke('list_jobs');
+	}
+
+	async get_job(job_id: string): Promise<IJobInfo> {
+		return invoke('get_job', { job_id });
+	}
+
+	async cancel_job(job_id: string): Promise<void> {
+		return invoke('cancel_job', { job_id });
+	}
+
+	async enqueue_job(params: IJobParams): Promise<string> {
+		return invoke('enqueue_job', { params });
+	}
+
+	async list_job_queue(): Promise<IQueuedJob[]> {
+		return invoke('list_job_queue');
+	}
+
+	async retry_job(job_id: string): Promise<void> {
+		return invoke('retry_job', { job_id });
+	}
+
+	async set_job_concurrency(
+		kind: IJobParams['kind'],
+		limit: number,
+	): Promise<void> {
+		return invoke('set_job_concurrency', { kind, limit });
+	}
+
+	async get_settings(): Promise<ISettings> {
+		return invoke('get_settings');
+	}
+
+	async update_settings(changes: Partial<ISettings>): Promise<ISettings> {
+		return invoke('update_settings', { changes });
+	}
+
+	async export_diagnostics(output_path?: string): Promise<string> {
+		return invoke('export_diagnostics', { output_path });
+	}
 }
 
 const api = new API();
@@ -5,7 +5,12 @@ import { cva } from 'class-variance-authority';
 import { convertFileSrc } from '@tauri-apps/api/core';
 import { WebviewWindow } from '@tauri-apps/api/webviewWindow';
 
-import { type IVideoMeta, type IVideoIO } from '@/lib/api';
+import {
+	type IVideoMeta,
+	type IVideoIO,
+	type IFfmpegProgress,
+	isSwiftKitError,
+} from '@/lib/api';
 
 import type { FileMeta } from '@/components/native-file-input';
 
@@ -13,6 +18,13 @@ export function cn(...inputs: ClassValue[]) {
 	return twMerge(clsx(inputs));
 }
 
+// Commands reject with `{ code, message, details }`, anything else is stringified
+export const errorMessage = (error: unknown): string =>
+	isSwiftKitError(error) ? error.message : String(error);
+
+export const isCancelledError = (error: unknown): boolean =>
+	isSwiftKitError(error) && error.code === 'cancelled';
+
 export const formatFileSize = (sizeInBytes: number): string => {
 	if (sizeInBytes === 0) return '0 Bytes';
 
@@ -23,35 +35,6 @@ export const formatFileSize = (sizeInBytes: number): string => {
 	return `${size} ${units[sizeIndex]}`;
 };
 
-type FFmpegProgress = {
-	frame: number;
-	fps: number;
-	time: string;
-	speed: number;
-};
-
-export const parseFFmpegProgress = (stdout: string): FFmpegProgress | null => {
-	try {
-		const fpsMatch = stdout.match(/fps=(\d+)/);
-		const frameMatch = stdout.match(/frame=\s*(\d+)/);
-		const timeMatch = stdout.match(/time=(\d+:\d+:\d+\.\d+)/);
-		const speedMatch = stdout.match(/speed=(\d+\.?\d*)x/);
-
-		if (!fpsMatch || !frameMatch || !timeMatch || !speedMatch) {
-			return null;
-		}
-
-		return {
-			frame: parseInt(frameMatch[1]),
-			fps: parseInt(fpsMatch[1]),
-			time: timeMatch[1],
-			speed: parseFloat(speedMatch[1]),
-		};
-	} catch (_error) {
-		return null;
-	}
-};
-
 export const timeToSeconds = (timeStr: string): number => {
 	const [hours, minutes, seconds] = timeStr.split(':').map(Number);
 	return hours * 3600 + minutes * 60 + seconds;
@@ -67,60 +50,36 @@ export const formatDuration = (seconds: number): string => {
 		.padStart(2, '0')}:${secs.toString().padStart(2, '0')}`;
 };
 
-export const calculateFFmpegETA = (
-	ffmpegOutput: string,
-	totalDuration: string,
-) => {
-	const progress = parseFFmpegProgress(ffmpegOutput);
-	if (!progress) {
-		return {
-			eta: null,
-			etaInSeconds: 0,
-			progress: 0,
-			currentTime: '00:00:00',
-			totalDuration: formatDuration(timeToSeconds(totalDuration)),
-			speed: 0,
-		};
-	}
-
-	const totalSeconds = timeToSeconds(totalDuration);
-	const processedSeconds = timeToSeconds(progress.time);
-	const remainingSeconds = totalSeconds - processedSeconds;
-
-	const estimatedSecondsRemaining = remainingSeconds / progress.speed;
-	const progressPercent = (processedSeconds / totalSeconds) * 100;
+export const describeFFmpegProgress = (
+	progress?: IFfmpegProgress,
+): string => {
+	if (!progress) return '';
 
-	return {
-		eta: formatDuration(Math.ceil(estimatedSecondsRemaining)),
-		etaInSeconds: Math.ceil(estimatedSecondsRemaining),
-		progress: Math.min(100, Math.round(progressPercent)),
-		currentTime: formatDuration(processedSeconds),
-		totalDuration: formatDuration(totalSeconds),
-		speed: progress.speed,
-	};
+	const parts = [formatDuration(progress.out_time)];
+	if (progress.fps) parts.push(`${progress.fps} fps`);
+	if (progress.speed) parts.push(`${progress.speed}x`);
+	return parts.join(' | ');
 };
 
 export const calculateQueueETA = (
 	currentVideo: IVideoIO | IVideoMeta,
 	videoQueue: IVideoIO[] | IVideoMeta[],
-	ffmpegOutput: string,
+	progress?: IFfmpegProgress,
 ): string => {
 	const currentIndex = videoQueue.findIndex(
 		v => v.filename === currentVideo.filename,
 	);
 	if (currentIndex === -1) return '';
+	if (!progress?.speed || progress.eta == null) return '';
 
-	const currentETA = calculateFFmpegETA(ffmpegOutput, currentVideo.duration);
-	if (!currentETA.speed || currentETA.speed === 0) return '';
-
-	let totalETASeconds = currentETA.etaInSeconds;
+	let totalETASeconds = progress.eta;
 	for (let i = currentIndex + 1; i < videoQueue.length; i++) {
 		const video = videoQueue[i];
 		const videoDurationSeconds = timeToSeconds(video.duration);
-		totalETASeconds += videoDurationSeconds / currentETA.speed;
+		totalETASeconds += videoDurationSeconds / progress.speed;
 	}
 
-	return `${currentETA.eta} | ${currentIndex + 1}/${
+	return `${formatDuration(Math.ceil(progress.eta))} | ${currentIndex + 1}/${
 		videoQueue.length
 	} ${formatDuration(Math.ceil(totalETASeconds))}`;
 };
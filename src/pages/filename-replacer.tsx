@@ -18,6 +18,7 @@ import { Input } from '@/components/input';
 import { Button } from '@/components/button';
 
 import api, { type IFileMeta } from '@/lib/api';
+import { errorMessage } from '@/lib/utils';
 
 const filenameReplacerSchema = z.object({
 	folder_path: z.string().min(1, {
@@ -65,7 +66,7 @@ const FilenameReplacer = () => {
 		} catch (error) {
 			console.error('Error:', error);
 			setFetchLoading(false);
-			toast.error(String(error));
+			toast.error(errorMessage(error));
 		}
 	}
 
@@ -87,7 +88,7 @@ const FilenameReplacer = () => {
 		} catch (error) {
 			console.error('Error:', error);
 			setProcessLoading(false);
-			toast.error(String(error));
+			toast.error(errorMessage(error));
 		}
 	}
 
@@ -130,7 +131,7 @@ const FilenameReplacer = () => {
 		} catch (error) {
 			console.error('Error:', error);
 			setProcessLoading(false);
-			toast.error(String(error));
+			toast.error(errorMessage(error));
 		}
 	}
 
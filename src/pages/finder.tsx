@@ -27,7 +27,8 @@ import { Button } from '@/components/button';
 import { Loading } from '@/components/loading';
 import { ScrollToTop } from '@/components/scroll-to-top';
 
-import api from '@/lib/api';
+import api, { type IDiskInfo } from '@/lib/api';
+import { formatFileSize, errorMessage } from '@/lib/utils';
 
 const finderSchema = z.object({
 	search_term: z.string().min(3, {
@@ -38,7 +39,7 @@ const finderSchema = z.object({
 
 const Finder = () => {
 	const [processLoading, setProcessLoading] = useState(false);
-	const [disks, setDisks] = useState<string[]>([]);
+	const [disks, setDisks] = useState<IDiskInfo[]>([]);
 	const [results, setResults] = useState<string[]>([]);
 	const [resultFilter, setResultFilter] = useState<string>('');
 	const form = useForm<z.infer<typeof finderSchema>>({
@@ -79,7 +80,7 @@ const Finder = () => {
 			toast.success(`Search finished in ${durationInSeconds} seconds.`);
 		} catch (error) {
 			try {
-				toast.error(String(error));
+				toast.error(errorMessage(error));
 			} catch (_e) {}
 			console.error(error);
 		} finally {
@@ -148,9 +149,11 @@ const Finder = () => {
 										{disks.map((d, di) => (
 											<SelectItem
 												key={`finder_disk_${di}`}
-												value={d}
+												value={d.mount_point}
 											>
-												{d}
+												{d.label ?? d.mount_point} (
+												{formatFileSize(d.free_bytes)}{' '}
+												free)
 											</SelectItem>
 										))}
 									</SelectContent>
@@ -29,11 +29,12 @@ import { type FileMeta, NativeFileInput } from '@/components/native-file-input';
 
 import { iconPresetNames, iconPresets } from './icon-presets';
 import api from '@/lib/api';
+import { errorMessage } from '@/lib/utils';
 
 const iconGeneratorSchema = z.object({
 	base_image: z.coerce.string(),
 	icon_preset_key: z
-		.enum(['tauri-app', 'android-mobile', 'ios-mobile'])
+		.enum(['tauri-app', 'android-mobile', 'ios-mobile', 'pwa'])
 		.optional(),
 	output_path: z.string().optional(),
 });
@@ -68,6 +69,20 @@ const IconGenerator = () => {
 				return;
 			}
 
+			const output_folder =
+				data.output_path ?? (await dirname(currentFile.path));
+			if (iconPreset.platform) {
+				const iconSet = await api.export_icon_set(
+					currentFile.path,
+					[iconPreset.platform],
+					output_folder,
+				);
+				toast.success(
+					`Icon set is exported with ${iconSet.files.length} files.`,
+				);
+				return;
+			}
+
 			const trashLater: string[] = [];
 			const currentImageFormat = currentFile?.mime.replace(
 				'image/',
@@ -76,13 +91,11 @@ const IconGenerator = () => {
 			const baseImages = {
 				[currentImageFormat]: currentFile.path,
 			};
-			const output_folder =
-				data.output_path ?? (await dirname(currentFile.path));
 			let additionalFormats = iconPreset.icons
 				.map(ip =>
 					ip.format ? ip.format.replace('image/', '') : 'png',
 				)
-				.filter(ipf => ipf !== currentImageFormat);
+				.filter(ipf => ipf !== currentImageFormat && ipf !== 'ico');
 			additionalFormats = [...new Set(additionalFormats)];
 			if (additionalFormats.length > 0) {
 				for (let afi = 0; afi < additionalFormats.length; afi++) {
@@ -99,10 +112,19 @@ const IconGenerator = () => {
 			for (let ipi = 0; ipi < iconPreset.icons.length; ipi++) {
 				const iconItem = iconPreset.icons[ipi];
 				const format = iconItem.format ?? 'png';
+				// One multi-size .ico, plus the .icns macOS builds need
+				if (format === 'ico') {
+					await api.generate_icons(
+						currentFile.path,
+						undefined,
+						output_folder,
+						iconItem.fileName ?? 'icon',
+					);
+					continue;
+				}
 				const iconImg = await api.image_resize(
 					baseImages[format],
-					iconItem.size,
-					iconItem.size,
+					{ width: iconItem.size, height: iconItem.size },
 					output_folder,
 					iconItem.fileName ?? `${iconItem.size}x${iconItem.size}`,
 				);
@@ -114,7 +136,7 @@ const IconGenerator = () => {
 			toast.success('Icon generation is complete.');
 		} catch (error) {
 			try {
-				toast.error(String(error));
+				toast.error(errorMessage(error));
 			} catch (_e) {}
 			console.error(error);
 		} finally {
@@ -1,5 +1,6 @@
 // fileName defaults to: {size}x{size}
 // format defaults to: png
+// platform presets are exported as a whole set by the backend, icons only set the minimum size
 export const iconPresets = {
 	'tauri-app': {
 		title: 'Tauri App',
@@ -71,6 +72,7 @@ export const iconPresets = {
 	},
 	'android-mobile': {
 		title: 'Android Mobile',
+		platform: 'android',
 		icons: [
 			{
 				size: 48,
@@ -104,6 +106,7 @@ export const iconPresets = {
 	},
 	'ios-mobile': {
 		title: 'iOS Mobile',
+		platform: 'ios',
 		icons: [
 			{
 				size: 60,
@@ -151,6 +154,15 @@ export const iconPresets = {
 			},
 		],
 	},
+	pwa: {
+		title: 'PWA',
+		platform: 'pwa',
+		icons: [
+			{
+				size: 512,
+			},
+		],
+	},
 };
 
 export const iconPresetNames = Object.keys(iconPresets);
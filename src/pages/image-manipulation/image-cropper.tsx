@@ -18,17 +18,38 @@ import {
 } from '@/components/form';
 import { Input } from '@/components/input';
 import { Button } from '@/components/button';
+import { Checkbox } from '@/components/checkbox';
+import {
+	Select,
+	SelectContent,
+	SelectItem,
+	SelectTrigger,
+	SelectValue,
+} from '@/components/select';
 import { Loading } from '@/components/loading';
 import { type FileMeta, NativeFileInput } from '@/components/native-file-input';
 
 import { convertFileSrc } from '@tauri-apps/api/core';
 import api from '@/lib/api';
+import { errorMessage } from '@/lib/utils';
 
 const imageCropperSchema = z.object({
 	base_image: z.string(),
 	output_path: z.string().optional(),
+	aspect: z.enum(['free', '1:1', '4:3', '3:2', '16:9', '9:16']),
+	rotate: z.coerce.number().optional(),
+	rotate_mode: z.enum(['expand', 'crop_to_fit']),
+	flip_horizontal: z.boolean(),
+	flip_vertical: z.boolean(),
+	auto_trim: z.boolean(),
 });
 
+function aspectRatio(aspect: string) {
+	if (aspect === 'free') return undefined;
+	const [width, height] = aspect.split(':').map(Number);
+	return width / height;
+}
+
 const ImageCropper = () => {
 	const imgRef = useRef<HTMLImageElement>(null);
 	const [processLoading, setProcessLoading] = useState(false);
@@ -45,8 +66,15 @@ const ImageCropper = () => {
 		defaultValues: {
 			base_image: '',
 			output_path: '',
+			aspect: 'free',
+			rotate: 0,
+			rotate_mode: 'expand',
+			flip_horizontal: false,
+			flip_vertical: false,
+			auto_trim: false,
 		},
 	});
+	const aspect = aspectRatio(form.watch('aspect'));
 
 	useEffect(() => {
 		if (imgRef.current) {
@@ -58,9 +86,14 @@ const ImageCropper = () => {
 	}, [imgSrc]);
 
 	async function onSubmit(data: z.infer<typeof imageCropperSchema>) {
+		const hasOptions =
+			!!data.rotate ||
+			data.flip_horizontal ||
+			data.flip_vertical ||
+			data.auto_trim;
 		if (
 			typeof currentFile === 'undefined' ||
-			typeof crop === 'undefined' ||
+			(typeof crop === 'undefined' && !hasOptions) ||
 			!currentFile.width ||
 			!currentFile.height ||
 			imgRef.current === null
@@ -73,16 +106,28 @@ const ImageCropper = () => {
 			const scaleY =
 				imgRef.current.naturalHeight / displayedDimensions.height;
 
-			const scaledCrop = { ...crop };
-			scaledCrop.x = crop.x * scaleX;
-			scaledCrop.y = crop.y * scaleY;
-			scaledCrop.width = Math.round(crop.width * scaleX);
-			scaledCrop.height = Math.round(crop.height * scaleY);
+			let scaledCrop: Crop | undefined;
+			if (crop && crop.width && crop.height) {
+				scaledCrop = { ...crop };
+				scaledCrop.x = crop.x * scaleX;
+				scaledCrop.y = crop.y * scaleY;
+				scaledCrop.width = crop.width * scaleX;
+				scaledCrop.height = crop.height * scaleY;
+			}
 
 			await api.image_crop(
 				currentFile.path,
 				scaledCrop,
 				data.output_path,
+				undefined,
+				{
+					aspect_ratio: scaledCrop ? aspect : undefined,
+					rotate: data.rotate || undefined,
+					rotate_mode: data.rotate_mode,
+					flip_horizontal: data.flip_horizontal,
+					flip_vertical: data.flip_vertical,
+					auto_trim: data.auto_trim,
+				},
 			);
 
 			setCurrentFile(undefined);
@@ -91,7 +136,7 @@ const ImageCropper = () => {
 			toast.success('Cropping is complete.');
 		} catch (error) {
 			try {
-				toast.error(String(error));
+				toast.error(errorMessage(error));
 			} catch (_e) {}
 			console.error(error);
 		} finally {
@@ -128,6 +173,129 @@ const ImageCropper = () => {
 					/>
 				</div>
 
+				<div className="grid grid-cols-3 gap-4">
+					<FormField
+						control={form.control}
+						name="aspect"
+						render={({ field }) => (
+							<FormItem className="grid gap-1">
+								<FormLabel>Aspect Ratio</FormLabel>
+								<Select
+									onValueChange={value => {
+										field.onChange(value);
+										setCrop(undefined);
+									}}
+									value={field.value}
+								>
+									<FormControl>
+										<SelectTrigger>
+											<SelectValue />
+										</SelectTrigger>
+									</FormControl>
+									<SelectContent>
+										<SelectItem value="free">Free</SelectItem>
+										<SelectItem value="1:1">1:1</SelectItem>
+										<SelectItem value="4:3">4:3</SelectItem>
+										<SelectItem value="3:2">3:2</SelectItem>
+										<SelectItem value="16:9">16:9</SelectItem>
+										<SelectItem value="9:16">9:16</SelectItem>
+									</SelectContent>
+								</Select>
+								<FormMessage />
+							</FormItem>
+						)}
+					/>
+					<FormField
+						control={form.control}
+						name="rotate"
+						render={({ field }) => (
+							<FormItem className="grid gap-1">
+								<FormLabel>Rotation (degrees)</FormLabel>
+								<FormControl>
+									<Input type="number" step="any" {...field} />
+								</FormControl>
+								<FormMessage />
+							</FormItem>
+						)}
+					/>
+					<FormField
+						control={form.control}
+						name="rotate_mode"
+						render={({ field }) => (
+							<FormItem className="grid gap-1">
+								<FormLabel>Rotation Mode</FormLabel>
+								<Select
+									onValueChange={field.onChange}
+									value={field.value}
+								>
+									<FormControl>
+										<SelectTrigger>
+											<SelectValue />
+										</SelectTrigger>
+									</FormControl>
+									<SelectContent>
+										<SelectItem value="expand">
+											Expand canvas
+										</SelectItem>
+										<SelectItem value="crop_to_fit">
+											Straighten (crop to fit)
+										</SelectItem>
+									</SelectContent>
+								</Select>
+								<FormMessage />
+							</FormItem>
+						)}
+					/>
+				</div>
+
+				<div className="flex gap-6">
+					<FormField
+						control={form.control}
+						name="flip_horizontal"
+						render={({ field }) => (
+							<FormItem className="flex items-center gap-2 space-y-0">
+								<FormControl>
+									<Checkbox
+										checked={field.value}
+										onCheckedChange={field.onChange}
+									/>
+								</FormControl>
+								<FormLabel>Flip horizontally</FormLabel>
+							</FormItem>
+						)}
+					/>
+					<FormField
+						control={form.control}
+						name="flip_vertical"
+						render={({ field }) => (
+							<FormItem className="flex items-center gap-2 space-y-0">
+								<FormControl>
+									<Checkbox
+										checked={field.value}
+										onCheckedChange={field.onChange}
+									/>
+								</FormControl>
+								<FormLabel>Flip vertically</FormLabel>
+							</FormItem>
+						)}
+					/>
+					<FormField
+						control={form.control}
+						name="auto_trim"
+						render={({ field }) => (
+							<FormItem className="flex items-center gap-2 space-y-0">
+								<FormControl>
+									<Checkbox
+										checked={field.value}
+										onCheckedChange={field.onChange}
+									/>
+								</FormControl>
+								<FormLabel>Trim borders</FormLabel>
+							</FormItem>
+						)}
+					/>
+				</div>
+
 				<NativeFileInput
 					key={currentFile?.path}
 					onFileChange={file => {
@@ -144,6 +312,7 @@ const ImageCropper = () => {
 						<ReactCrop
 							className="max-h-[350px] w-full h-full !flex mx-auto"
 							crop={crop}
+							aspect={aspect}
 							onChange={c => setCrop(c)}
 							disabled={processLoading}
 						>
@@ -30,19 +30,32 @@ import { Loading } from '@/components/loading';
 
 import { useDragEvent } from '@/hooks/use-drag-event';
 
-import { resolveImageDetails } from '@/lib/utils';
+import { resolveImageDetails, errorMessage } from '@/lib/utils';
 import api from '@/lib/api';
 
 const imgManipulatorSchema = z.object({
 	width: z.coerce.number().optional(),
 	height: z.coerce.number().optional(),
+	resize_mode: z.enum(['exact', 'contain', 'cover']).optional(),
 	quality: z.coerce
 		.number()
 		.min(0, { message: 'Quality must be between 0 and 100' })
 		.max(100, { message: 'Quality must be between 0 and 100' })
 		.optional(),
 	convert_to: z
-		.enum(['none', 'jpeg', 'png', 'webp', 'bmp', 'ico'])
+		.enum([
+			'none',
+			'jpeg',
+			'png',
+			'webp',
+			'avif',
+			'tiff',
+			'gif',
+			'tga',
+			'qoi',
+			'bmp',
+			'ico',
+		])
 		.optional(),
 	output_path: z.string().optional(),
 });
@@ -57,6 +70,7 @@ const ImageManipulator = () => {
 		defaultValues: {
 			width: '' as any,
 			height: '' as any,
+			resize_mode: 'exact',
 			quality: '' as any,
 			convert_to: 'none',
 			output_path: '',
@@ -81,6 +95,11 @@ const ImageManipulator = () => {
 				data.convert_to !== 'none'
 			) {
 				let showSuccessAlert = false;
+				// Lossy formats are written at the requested quality right away
+				const quality =
+					typeof data.quality === 'number' && data.quality !== 0
+						? data.quality
+						: undefined;
 				for (let mi = 0; mi < images.length; mi++) {
 					const imageFile = images[mi];
 					if (imageFile.path.endsWith(data.convert_to)) continue;
@@ -88,6 +107,11 @@ const ImageManipulator = () => {
 						imageFile.path,
 						data.convert_to as string,
 						data.output_path,
+						{
+							jpeg_quality: quality,
+							webp_quality: quality,
+							avif_quality: quality,
+						},
 					);
 					showSuccessAlert = true;
 				}
@@ -104,8 +128,11 @@ const ImageManipulator = () => {
 					const imageFile = images[mi];
 					await api.image_resize(
 						imageFile.path,
-						data.width as number,
-						data.height as number,
+						{
+							mode: data.resize_mode,
+							width: data.width || undefined,
+							height: data.height || undefined,
+						},
 						data.output_path,
 					);
 					showSuccessAlert = true;
@@ -117,18 +144,33 @@ const ImageManipulator = () => {
 
 			if (typeof data.quality === 'number' && data.quality !== 0) {
 				let showSuccessAlert = false;
+				let originalSize = 0;
+				let compressedSize = 0;
 				for (let mi = 0; mi < images.length; mi++) {
 					const imageFile = images[mi];
-					await api.image_compress(
+					// The compressors only handle these, other formats were encoded at the quality above
+					if (!/\.(png|jpe?g|webp)$/i.test(imageFile.path)) continue;
+					const report = await api.image_compress(
 						imageFile.path,
 						data.quality as number,
 						data.output_path,
 					);
+					originalSize += report.original_size;
+					compressedSize += report.compressed_size;
 					showSuccessAlert = true;
 				}
 
-				if (showSuccessAlert)
-					toast.success('Image compression is complete.');
+				if (showSuccessAlert) {
+					const savedKb = Math.round(
+						(originalSize - compressedSize) / 1024,
+					);
+					const savedPercent = originalSize
+						? Math.round((1 - compressedSize / originalSize) * 100)
+						: 0;
+					toast.success('Image compression is complete.', {
+						description: `Saved ${savedKb} KB (${savedPercent}%).`,
+					});
+				}
 			}
 
 			setProcessLoading(false);
@@ -137,7 +179,7 @@ const ImageManipulator = () => {
 		} catch (error) {
 			console.error(error);
 			try {
-				toast.error(String(error));
+				toast.error(errorMessage(error));
 			} catch (_e) {}
 			setProcessLoading(false);
 		}
@@ -191,7 +233,7 @@ const ImageManipulator = () => {
 					/>
 				</div>
 
-				<div className="grid grid-cols-3 gap-4">
+				<div className="grid grid-cols-4 gap-4">
 					<FormField
 						control={form.control}
 						name="convert_to"
@@ -220,6 +262,21 @@ const ImageManipulator = () => {
 										<SelectItem value="webp">
 											WebP (.webp)
 										</SelectItem>
+										<SelectItem value="avif">
+											AVIF (.avif)
+										</SelectItem>
+										<SelectItem value="tiff">
+											TIFF (.tiff)
+										</SelectItem>
+										<SelectItem value="gif">
+											GIF (.gif)
+										</SelectItem>
+										<SelectItem value="tga">
+											TGA (.tga)
+										</SelectItem>
+										<SelectItem value="qoi">
+											QOI (.qoi)
+										</SelectItem>
 										<SelectItem value="bmp">
 											BMP (.bmp)
 										</SelectItem>
@@ -262,6 +319,35 @@ const ImageManipulator = () => {
 							</FormItem>
 						)}
 					/>
+					<FormField
+						control={form.control}
+						name="resize_mode"
+						render={({ field }) => (
+							<FormItem className="grid gap-1">
+								<FormLabel>Resize Mode</FormLabel>
+								<Select
+									onValueChange={field.onChange}
+									defaultValue={field.value}
+								>
+									<FormControl>
+										<SelectTrigger>
+											<SelectValue placeholder="Exact" />
+										</SelectTrigger>
+									</FormControl>
+									<SelectContent>
+										<SelectItem value="exact">Exact</SelectItem>
+										<SelectItem value="contain">
+											Fit inside
+										</SelectItem>
+										<SelectItem value="cover">
+											Fill and crop
+										</SelectItem>
+									</SelectContent>
+								</Select>
+								<FormMessage />
+							</FormItem>
+						)}
+					/>
 				</div>
 
 				<div className="grid w-full items-center gap-3">
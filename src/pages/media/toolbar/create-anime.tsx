@@ -41,6 +41,7 @@ import { Loading } from '@/components/loading';
 import { MediaCache } from '@/lib/models/media-cache';
 import api, { MediaQueryR } from '@/lib/api';
 import { AnimeModel } from '@/lib/models/anime';
+import { errorMessage } from '@/lib/utils';
 
 const createAnimeSchema = z.object({
 	scraped_url: z.string().optional(),
@@ -117,7 +118,7 @@ export function CreateAnime({ closeDialog }) {
 				}
 			} catch (error) {
 				try {
-					toast.error(String(error));
+					toast.error(errorMessage(error));
 				} catch (_e) {}
 			} finally {
 				setFetchLoading(false);
@@ -147,7 +148,7 @@ export function CreateAnime({ closeDialog }) {
 				setSearchAnimeData([]);
 			} catch (error) {
 				try {
-					toast.error(String(error));
+					toast.error(errorMessage(error));
 				} catch (_e) {}
 			} finally {
 				setFetchLoading(false);
@@ -165,7 +166,7 @@ export function CreateAnime({ closeDialog }) {
 			closeDialog();
 		} catch (error) {
 			try {
-				toast.error(String(error));
+				toast.error(errorMessage(error));
 			} catch (_e) {}
 		} finally {
 			setProcessLoading(false);
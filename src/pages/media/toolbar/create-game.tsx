@@ -42,6 +42,7 @@ import Tiptap from '@/components/tiptap';
 import { MediaCache } from '@/lib/models/media-cache';
 import api, { MediaQueryR } from '@/lib/api';
 import { GameModel } from '@/lib/models/game';
+import { errorMessage } from '@/lib/utils';
 
 const createGameSchema = z.object({
 	scraped_url: z.string().optional(),
@@ -122,7 +123,7 @@ export function CreateGame({ closeDialog }) {
 				}
 			} catch (error) {
 				try {
-					toast.error(String(error));
+					toast.error(errorMessage(error));
 				} catch (_e) {}
 			} finally {
 				setFetchLoading(false);
@@ -156,7 +157,7 @@ export function CreateGame({ closeDialog }) {
 				setSearchGameData([]);
 			} catch (error) {
 				try {
-					toast.error(String(error));
+					toast.error(errorMessage(error));
 				} catch (_e) {}
 			} finally {
 				setFetchLoading(false);
@@ -179,7 +180,7 @@ export function CreateGame({ closeDialog }) {
 			closeDialog();
 		} catch (error) {
 			try {
-				toast.error(String(error));
+				toast.error(errorMessage(error));
 			} catch (_e) {}
 		} finally {
 			setProcessLoading(false);
@@ -45,6 +45,7 @@ import { Loading } from '@/components/loading';
 import { MediaCache } from '@/lib/models/media-cache';
 import api, { MediaQueryR } from '@/lib/api';
 import { MovieModel } from '@/lib/models/movie';
+import { errorMessage } from '@/lib/utils';
 
 const createMovieSchema = z.object({
 	scraped_url: z.string().optional(),
@@ -133,7 +134,7 @@ export function CreateMovie({ closeDialog }) {
 				}
 			} catch (error) {
 				try {
-					toast.error(String(error));
+					toast.error(errorMessage(error));
 				} catch (_e) {}
 			} finally {
 				setFetchLoading(false);
@@ -163,7 +164,7 @@ export function CreateMovie({ closeDialog }) {
 				setSearchMovieData([]);
 			} catch (error) {
 				try {
-					toast.error(String(error));
+					toast.error(errorMessage(error));
 				} catch (_e) {}
 			} finally {
 				setFetchLoading(false);
@@ -181,7 +182,7 @@ export function CreateMovie({ closeDialog }) {
 			closeDialog();
 		} catch (error) {
 			try {
-				toast.error(String(error));
+				toast.error(errorMessage(error));
 			} catch (_e) {}
 		} finally {
 			setProcessLoading(false);
@@ -18,7 +18,7 @@ import api from '@/lib/api';
 import { MovieModel } from '@/lib/models/movie';
 import { AnimeModel } from '@/lib/models/anime';
 import { GameModel } from '@/lib/models/game';
-import { hasNewLines, sleep } from '@/lib/utils';
+import { hasNewLines, sleep, errorMessage } from '@/lib/utils';
 
 function determineLinkType(
 	link: string,
@@ -153,7 +153,7 @@ export function Import() {
 			toast.success('Import successfull.');
 		} catch (error) {
 			try {
-				toast.error(String(error));
+				toast.error(errorMessage(error));
 			} catch (_e) {}
 		} finally {
 			setProcessLoading(false);
@@ -23,7 +23,7 @@ import { dbWrapper } from '@/lib/db';
 import { MovieModel } from '@/lib/models/movie';
 import { AnimeModel } from '@/lib/models/anime';
 import { GameModel } from '@/lib/models/game';
-import { sleep } from '@/lib/utils';
+import { sleep, errorMessage } from '@/lib/utils';
 
 async function checkTotalAsset(
 	tableName: string,
@@ -131,7 +131,7 @@ export function OfflineAssets() {
 			setOtherImages(false);
 		} catch (error) {
 			try {
-				toast.error(String(error));
+				toast.error(errorMessage(error));
 			} catch (_e) {}
 		} finally {
 			setProcessLoading(false);
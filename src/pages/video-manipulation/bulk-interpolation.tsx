@@ -5,7 +5,7 @@ import { toast } from 'sonner';
 import { z } from 'zod';
 import { zodResolver } from '@hookform/resolvers/zod';
 
-import { emit, listen } from '@tauri-apps/api/event';
+import { listen } from '@tauri-apps/api/event';
 
 import {
 	Form,
@@ -36,7 +36,7 @@ import { Checkbox } from '@/components/checkbox';
 import { Loading } from '@/components/loading';
 
 import api, { IVideoMeta } from '@/lib/api';
-import { formatFileSize } from '@/lib/utils';
+import { formatFileSize, errorMessage } from '@/lib/utils';
 import { emitter } from '@/lib/event';
 
 const bulkInterpolationSchema = z.object({
@@ -50,6 +50,7 @@ const bulkInterpolationSchema = z.object({
 
 const BulkInterpolation = () => {
 	const shouldStopRef = useRef(false);
+	const jobIdRef = useRef<string | undefined>(undefined);
 	const [processLoading, setProcessLoading] = useState(false);
 	const [fetchedVideos, setFetchedVideos] = useState<IVideoMeta[]>([]);
 	const [fetchLoading, setFetchLoading] = useState(false);
@@ -79,27 +80,20 @@ const BulkInterpolation = () => {
 			},
 		);
 
-		const unlisten_stderr = listen<string>(
-			'interpolation_stderr',
-			event => {
-				console.error(event.payload);
-				toast.error(event.payload);
-			},
-		);
-
 		return () => {
 			unlisten_stdout.then(f => f());
-			unlisten_stderr.then(f => f());
-			emit('cancel_interpolation')
-				.then(() =>
-					console.log('Cancellation request sent to backend.'),
-				)
-				.catch(error =>
-					console.error(
-						'Failed to send cancellation request:',
-						error,
-					),
-				);
+			if (jobIdRef.current) {
+				api.cancel_job(jobIdRef.current)
+					.then(() =>
+						console.log('Cancellation request sent to backend.'),
+					)
+					.catch(error =>
+						console.error(
+							'Failed to send cancellation request:',
+							error,
+						),
+					);
+			}
 		};
 	}, []);
 
@@ -107,8 +101,10 @@ const BulkInterpolation = () => {
 	const input_path = getValues('input_path');
 
 	const onStopRequest = async () => {
-		await emit('cancel_interpolation');
 		shouldStopRef.current = true;
+		if (jobIdRef.current) {
+			await api.cancel_job(jobIdRef.current).catch(console.error);
+		}
 	};
 
 	const handleFetch = async () => {
@@ -135,7 +131,7 @@ const BulkInterpolation = () => {
 			setFetchedVideos(videos);
 		} catch (e) {
 			try {
-				toast.error(String(e));
+				toast.error(errorMessage(e));
 			} catch (_e) {}
 			console.error(e);
 		} finally {
@@ -165,12 +161,14 @@ const BulkInterpolation = () => {
 
 				const videoItem = fetchedVideos[dvi];
 				setProcessingVideo(videoItem as any);
+				jobIdRef.current = crypto.randomUUID();
 				await api.interpolate(
 					`${data.input_path}\\${videoItem.filename}`,
 					data.encoder,
 					data.rife_model,
 					data.multiplier,
 					data.overwrite,
+					jobIdRef.current,
 				);
 				currentProg += progRatePerVideo;
 				setProgress(currentProg);
@@ -187,7 +185,7 @@ const BulkInterpolation = () => {
 		} catch (e) {
 			console.error(e);
 			try {
-				toast.error(String(e));
+				toast.error(errorMessage(e));
 			} catch (_e) {}
 		} finally {
 			setProcessLoading(false);
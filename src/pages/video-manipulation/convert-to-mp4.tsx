@@ -5,7 +5,7 @@ import { toast } from 'sonner';
 import { z } from 'zod';
 import { zodResolver } from '@hookform/resolvers/zod';
 
-import { emit, listen } from '@tauri-apps/api/event';
+import { listen } from '@tauri-apps/api/event';
 
 import {
 	Form,
@@ -27,9 +27,15 @@ import { Button } from '@/components/button';
 import { Progress } from '@/components/progress';
 import { Loading } from '@/components/loading';
 
-import api, { IVideoMeta } from '@/lib/api';
+import api, { IVideoMeta, type IFfmpegProgress } from '@/lib/api';
 import { emitter } from '@/lib/event';
-import { calculateQueueETA, formatFileSize } from '@/lib/utils';
+import {
+	calculateQueueETA,
+	describeFFmpegProgress,
+	formatFileSize,
+	errorMessage,
+	isCancelledError,
+} from '@/lib/utils';
 
 const converToMp4Schema = z.object({
 	input_path: z.string(),
@@ -37,10 +43,13 @@ const converToMp4Schema = z.object({
 
 const ConverToMp4 = () => {
 	const shouldStopRef = useRef(false);
+	const jobIdRef = useRef<string | undefined>(undefined);
 	const [processLoading, setProcessLoading] = useState(false);
 	const [fetchedVideos, setFetchedVideos] = useState<IVideoMeta[]>([]);
 	const [fetchLoading, setFetchLoading] = useState(false);
-	const [converToMp4Stdout, setConverToMp4Stdout] = useState<string>('');
+	const [ffmpegProgress, setFfmpegProgress] = useState<
+		IFfmpegProgress | undefined
+	>(undefined);
 	const [currentProcessETA, setCurrentProcessETA] = useState<string>('');
 	const [processingVideo, setProcessingVideo] = useState<
 		IVideoMeta | undefined
@@ -55,34 +64,29 @@ const ConverToMp4 = () => {
 	});
 
 	useEffect(() => {
-		const unlisten_stdout = listen<string>(
-			'convert_to_mp4_stdout',
+		const unlisten_progress = listen<IFfmpegProgress>(
+			'convert_to_mp4_progress',
 			event => {
-				setConverToMp4Stdout(event.payload);
-			},
-		);
-
-		const unlisten_stderr = listen<string>(
-			'convert_to_mp4_stderr',
-			event => {
-				console.error(event.payload);
-				toast.error(event.payload);
+				if (event.payload.job_id === jobIdRef.current) {
+					setFfmpegProgress(event.payload);
+				}
 			},
 		);
 
 		return () => {
-			unlisten_stdout.then(f => f());
-			unlisten_stderr.then(f => f());
-			emit('cancel_converToMp4')
-				.then(() =>
-					console.log('Cancellation request sent to backend.'),
-				)
-				.catch(error =>
-					console.error(
-						'Failed to send cancellation request:',
-						error,
-					),
-				);
+			unlisten_progress.then(f => f());
+			if (jobIdRef.current) {
+				api.cancel_job(jobIdRef.current)
+					.then(() =>
+						console.log('Cancellation request sent to backend.'),
+					)
+					.catch(error =>
+						console.error(
+							'Failed to send cancellation request:',
+							error,
+						),
+					);
+			}
 		};
 	}, []);
 
@@ -92,18 +96,20 @@ const ConverToMp4 = () => {
 				calculateQueueETA(
 					processingVideo,
 					fetchedVideos,
-					converToMp4Stdout,
+					ffmpegProgress,
 				),
 			);
 		}
-	}, [fetchedVideos, processingVideo, converToMp4Stdout]);
+	}, [fetchedVideos, processingVideo, ffmpegProgress]);
 
 	const { getValues } = form;
 	const input_path = getValues('input_path');
 
 	const onStopRequest = async () => {
-		await emit('cancel_convert_to_mp4');
 		shouldStopRef.current = true;
+		if (jobIdRef.current) {
+			await api.cancel_job(jobIdRef.current).catch(console.error);
+		}
 	};
 
 	const handleFetch = async () => {
@@ -124,7 +130,7 @@ const ConverToMp4 = () => {
 			setFetchedVideos(videos);
 		} catch (e) {
 			try {
-				toast.error(String(e));
+				toast.error(errorMessage(e));
 			} catch (_e) {}
 			console.error(e);
 		} finally {
@@ -133,7 +139,7 @@ const ConverToMp4 = () => {
 	};
 
 	const processCleanup = () => {
-		setConverToMp4Stdout('');
+		setFfmpegProgress(undefined);
 		setCurrentProcessETA('');
 		setProgress(0);
 		setProcessingVideo(undefined);
@@ -155,8 +161,10 @@ const ConverToMp4 = () => {
 
 				const videoItem = fetchedVideos[dvi];
 				setProcessingVideo(videoItem as any);
+				jobIdRef.current = crypto.randomUUID();
 				await api.convert_to_mp4(
 					`${data.input_path}\\${videoItem.filename}`,
+					jobIdRef.current,
 				);
 				currentProg += progRatePerVideo;
 				setProgress(currentProg);
@@ -171,9 +179,13 @@ const ConverToMp4 = () => {
 			setFetchedVideos([]);
 			toast.success('Conversion is complete.');
 		} catch (e) {
+			if (isCancelledError(e)) {
+				processCleanup();
+				return;
+			}
 			console.error(e);
 			try {
-				toast.error(String(e));
+				toast.error(errorMessage(e));
 			} catch (_e) {}
 		} finally {
 			setProcessLoading(false);
@@ -328,7 +340,7 @@ const ConverToMp4 = () => {
 									)}
 								</div>
 								<div className="rounded-md text-sm text-green-500">
-									{converToMp4Stdout}
+									{describeFFmpegProgress(ffmpegProgress)}
 								</div>
 							</div>
 						</div>
@@ -5,7 +5,7 @@ import { toast } from 'sonner';
 import { z } from 'zod';
 import { zodResolver } from '@hookform/resolvers/zod';
 
-import { emit, listen } from '@tauri-apps/api/event';
+import { listen } from '@tauri-apps/api/event';
 
 import {
 	Form,
@@ -22,8 +22,13 @@ import { ScrollToTop } from '@/components/scroll-to-top';
 import { Progress } from '@/components/progress';
 import { Loading } from '@/components/loading';
 
-import api, { type IVideoIO } from '@/lib/api';
-import { calculateQueueETA } from '@/lib/utils';
+import api, { type IVideoIO, type IFfmpegProgress } from '@/lib/api';
+import {
+	calculateQueueETA,
+	describeFFmpegProgress,
+	errorMessage,
+	isCancelledError,
+} from '@/lib/utils';
 import { emitter } from '@/lib/event';
 
 import { VideoItem } from './video-item';
@@ -67,10 +72,13 @@ const noIntroOutroSchema = z.object({
 
 const NoIntroOutro = () => {
 	const shouldStopRef = useRef(false);
+	const jobIdRef = useRef<string | undefined>(undefined);
 	const [processLoading, setProcessLoading] = useState(false);
 	const [fetchedVideos, setFetchedVideos] = useState<IVideoIO[]>([]);
 	const [fetchLoading, setFetchLoading] = useState(false);
-	const [ffmpegStdout, setFfmpegStdout] = useState<string>('');
+	const [ffmpegProgress, setFfmpegProgress] = useState<
+		IFfmpegProgress | undefined
+	>(undefined);
 	const [currentProcessETA, setCurrentProcessETA] = useState<string>('');
 	const [processingVideo, setProcessingVideo] = useState<
 		IVideoIO | undefined
@@ -90,38 +98,43 @@ const NoIntroOutro = () => {
 	});
 
 	useEffect(() => {
-		const unlisten_stdout = listen<string>('noio_stdout', event => {
-			setFfmpegStdout(event.payload);
-		});
-
-		const unlisten_stderr = listen<string>('noio_stderr', event => {
-			console.error(event.payload);
-			toast.error(event.payload);
-		});
+		const unlisten_progress = listen<IFfmpegProgress>(
+			'noio_progress',
+			event => {
+				if (event.payload.job_id === jobIdRef.current) {
+					setFfmpegProgress(event.payload);
+				}
+			},
+		);
 
 		return () => {
-			unlisten_stdout.then(f => f());
-			unlisten_stderr.then(f => f());
-			emit('cancel_noio')
-				.then(() =>
-					console.log('Cancellation request sent to backend.'),
-				)
-				.catch(error =>
-					console.error(
-						'Failed to send cancellation request:',
-						error,
-					),
-				);
+			unlisten_progress.then(f => f());
+			if (jobIdRef.current) {
+				api.cancel_job(jobIdRef.current)
+					.then(() =>
+						console.log('Cancellation request sent to backend.'),
+					)
+					.catch(error =>
+						console.error(
+							'Failed to send cancellation request:',
+							error,
+						),
+					);
+			}
 		};
 	}, []);
 
 	useEffect(() => {
 		if (processingVideo) {
 			setCurrentProcessETA(
-				calculateQueueETA(processingVideo, fetchedVideos, ffmpegStdout),
+				calculateQueueETA(
+					processingVideo,
+					fetchedVideos,
+					ffmpegProgress,
+				),
 			);
 		}
-	}, [fetchedVideos, processingVideo, ffmpegStdout]);
+	}, [fetchedVideos, processingVideo, ffmpegProgress]);
 
 	const { control, handleSubmit, getValues, setValue, watch } = form;
 	const { fields } = useFieldArray({ control, name: 'videos' });
@@ -188,7 +201,7 @@ const NoIntroOutro = () => {
 			);
 		} catch (e) {
 			try {
-				toast.error(String(e));
+				toast.error(errorMessage(e));
 			} catch (_e) {}
 			console.error(e);
 		} finally {
@@ -197,12 +210,14 @@ const NoIntroOutro = () => {
 	};
 
 	const onStopRequest = async () => {
-		await emit('cancel_noio');
 		shouldStopRef.current = true;
+		if (jobIdRef.current) {
+			await api.cancel_job(jobIdRef.current).catch(console.error);
+		}
 	};
 
 	const processCleanup = () => {
-		setFfmpegStdout('');
+		setFfmpegProgress(undefined);
 		setCurrentProcessETA('');
 		setProgress(0);
 		setProcessingVideo(undefined);
@@ -237,10 +252,13 @@ const NoIntroOutro = () => {
 					videoItem.subtitle_tracks.length > 1
 				) {
 					setProcessingVideo(videoItem as any);
+					jobIdRef.current = crypto.randomUUID();
 					await api.no_intro_outro(
 						input_path,
 						videoItem as any,
+						undefined,
 						data.overwrite,
+						jobIdRef.current,
 					);
 					currentProg += progRatePerVideo;
 					setProgress(currentProg);
@@ -264,9 +282,13 @@ const NoIntroOutro = () => {
 			setFetchedVideos([]);
 			toast.success('Done.');
 		} catch (e) {
+			if (isCancelledError(e)) {
+				processCleanup();
+				return;
+			}
 			console.error(e);
 			try {
-				toast.error(String(e));
+				toast.error(errorMessage(e));
 			} catch (_e) {}
 		} finally {
 			setProcessLoading(false);
@@ -514,7 +536,7 @@ const NoIntroOutro = () => {
 									)}
 								</div>
 								<div className="rounded-md text-sm text-green-500">
-									{ffmpegStdout}
+									{describeFFmpegProgress(ffmpegProgress)}
 								</div>
 							</div>
 						</div>